@@ -0,0 +1,135 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+//! Measures the time and the allocations of small, typical and pathological parses. Run it
+//! with `cargo bench --features bench`: it fails if a parse allocates more than its budget.
+
+use std::{hint::black_box, process, time::Instant};
+
+use tiny_args::{
+    internals::{self, Counting},
+    *,
+};
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ITERATIONS: u32 = 10_000;
+
+// A command with 16 arguments of every common kind and a subcommand.
+fn command() -> Command {
+    Command::create("tinycloud", "Runs the Tiny Cloud server.")
+        .version("1.0.0")
+        .auto_version(true)
+        .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity.")
+        .arg(arg!(-'q', --quiet), value!(), "Prints nothing.")
+        .arg(
+            arg!(-'c', --config),
+            value!(path),
+            "Uses another configuration.",
+        )
+        .arg(
+            arg!(-'p', --port),
+            value!(num in 1..=65535, 8080),
+            "Port to listen on.",
+        )
+        .arg(
+            arg!(--host),
+            value!(string, "localhost"),
+            "Host to listen on.",
+        )
+        .arg(arg!(--workers), value!(num), "How many workers to start.")
+        .arg(arg!(--ratio), value!(float), "Cache ratio.")
+        .arg(
+            arg!(--format),
+            value!(choice ["json", "yaml"]),
+            "Output format.",
+        )
+        .arg(arg!(--tag), value!(list), "Tags the server.")
+        .arg(
+            arg!(--data - directory),
+            value!(path),
+            "Where the data is stored.",
+        )
+        .arg(arg!(--tls), value!(), "Enables TLS.")
+        .arg(arg!(--cert), value!(path), "TLS certificate.")
+        .arg(arg!(--key), value!(path), "TLS key.")
+        .arg(arg!(--timeout), value!(num, 30), "Timeout in seconds.")
+        .arg(arg!(--name), value!(string), "Name of the server.")
+        .arg(arg!(-'d', --dry - run), value!(), "Does nothing.")
+        .subcommand(Command::create("serve", "Starts the server.").arg(
+            arg!(--detach),
+            value!(),
+            "Runs in the background.",
+        ))
+}
+
+fn argv(line: &str) -> Vec<String> {
+    split(line).expect("The command line is valid")
+}
+
+// Parses the command line many times, printing the time and the allocations of one parse.
+// Returns the allocations, which do not include building the command and the input.
+fn bench(name: &str, line: &str) -> usize {
+    let mut allocations = 0;
+    let mut elapsed = 0;
+    for _ in 0..ITERATIONS {
+        let (cmd, input) = (command(), argv(line));
+        let before = internals::stats();
+        let start = Instant::now();
+        let parsed = black_box(cmd.parse_from(input));
+        elapsed += start.elapsed().as_nanos();
+        allocations = internals::stats().since(before).allocations;
+        drop(parsed);
+    }
+    let nanos = elapsed / ITERATIONS as u128;
+    println!("{name:<12} {nanos:>8} ns/parse {allocations:>6} allocations/parse");
+    allocations
+}
+
+fn main() {
+    // The budgets are documented in `Command::parse_from`.
+    let typical = "tinycloud -v --port 9090 --host=0.0.0.0 -c /etc/tinycloud.toml --tag a \
+                   --tag b --data-directory /srv/data --format json serve --detach";
+    // A thousand values of a list and a cluster of a thousand flags.
+    let long = format!(
+        "tinycloud {} -{}",
+        "--tag value ".repeat(1000),
+        "v".repeat(1000)
+    );
+    let cases = [
+        ("empty", "tinycloud".to_string(), 200),
+        ("flags", "tinycloud -vvv --tls -d".to_string(), 200),
+        ("typical", typical.to_string(), 80),
+        ("long", long, 3500),
+    ];
+    let mut failed = false;
+    for (name, line, budget) in cases {
+        let allocations = bench(name, &line);
+        if allocations > budget {
+            eprintln!("'{name}' allocates {allocations} times, but its budget is {budget}");
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
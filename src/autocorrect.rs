@@ -0,0 +1,116 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crate::*;
+
+/// What to do with a subcommand that does not exist but is close to one that does (e.g.
+/// `stauts` instead of `status`).
+///
+/// See [`Command::autocorrect`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Autocorrect {
+    /// It is an [`ErrorKind::UnknownSubcommand`] error. This is the default.
+    Off,
+
+    /// The closest subcommand is run in its place, if there is only one, and a warning is added
+    /// to [`ParsedCommand::warnings`].
+    Run,
+
+    /// The close subcommands are listed and the user is asked which one to run. It is an error
+    /// if the user picks none of them, or if the standard input or error is not a terminal.
+    Prompt,
+}
+
+// The edit distance between the two strings (insertions, deletions and substitutions).
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+// The subcommands close enough to the token, closest first. A third of the name can differ,
+// but at least one character.
+fn near_misses(subcommands: &[Command], token: &str) -> Vec<(usize, &'static str)> {
+    let mut near: Vec<(usize, &'static str)> = subcommands
+        .iter()
+        .map(|s| (distance(token, s.name), s.name))
+        .filter(|(d, name)| *d <= (name.chars().count() / 3).max(1))
+        .collect();
+    near.sort();
+    near
+}
+
+// Asks the user which of the subcommands to run.
+fn prompt(token: &str, names: &[&'static str], strings: &Strings) -> Option<&'static str> {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return None;
+    }
+    let mut stderr = io::stderr();
+    let question = Strings::fill(strings.autocorrect_prompt, &[("subcommand", token)]);
+    let _ = writeln!(stderr, "{question}");
+    for (i, name) in names.iter().enumerate() {
+        let _ = writeln!(stderr, "\t{}. {name}", i + 1);
+    }
+    let count = names.len().to_string();
+    let _ = write!(
+        stderr,
+        "{} ",
+        Strings::fill(strings.autocorrect_choice, &[("count", &count)])
+    );
+    let _ = stderr.flush();
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).ok()?;
+    let choice: usize = answer.trim().parse().ok()?;
+    names.get(choice.checked_sub(1)?).copied()
+}
+
+// Returns the subcommand to run in place of the unknown token, if any.
+pub(crate) fn resolve(
+    policy: Autocorrect,
+    subcommands: &[Command],
+    token: &str,
+    strings: &Strings,
+) -> Option<&'static str> {
+    let near = near_misses(subcommands, token);
+    match policy {
+        Autocorrect::Off => None,
+        Autocorrect::Run => match near.as_slice() {
+            [(best, name), rest @ ..] if rest.first().is_none_or(|(d, _)| d > best) => Some(*name),
+            _ => None,
+        },
+        Autocorrect::Prompt if near.is_empty() => None,
+        Autocorrect::Prompt => {
+            let names: Vec<&'static str> = near.iter().map(|(_, name)| *name).collect();
+            prompt(token, &names, strings)
+        }
+    }
+}
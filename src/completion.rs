@@ -0,0 +1,507 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::*;
+
+/// A shell supported by [`generate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    /// The Bourne Again SHell.
+    Bash,
+
+    /// The Z shell.
+    Zsh,
+
+    /// The friendly interactive shell.
+    Fish,
+}
+
+impl Shell {
+    /// Every supported shell, to generate all the completion files at once.
+    pub const ALL: [Shell; 3] = [Shell::Bash, Shell::Zsh, Shell::Fish];
+
+    /// Returns the name of the completion file of the given program, as expected by the shell
+    /// (e.g. `_myapp` for zsh).
+    pub fn file_name(self, program: &str) -> String {
+        match self {
+            Self::Bash => format!("{program}.bash"),
+            Self::Zsh => format!("_{program}"),
+            Self::Fish => format!("{program}.fish"),
+        }
+    }
+}
+
+/// What kind of value an argument takes, so that completion scripts can suggest values.
+///
+/// See [`Arg::value_hint`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueHint {
+    /// A path to a file (or to anything else).
+    FilePath,
+
+    /// A path to a directory.
+    DirPath,
+
+    /// The name of a host.
+    Hostname,
+
+    /// The name of a user.
+    Username,
+
+    /// The name of a command in `PATH`.
+    CommandName,
+
+    /// Any other value, which is not completed.
+    Other,
+}
+
+// What the value of an argument can be completed with.
+enum Hint {
+    None,
+    Any,
+    Files,
+    Dirs,
+    Hosts,
+    Users,
+    Commands,
+    Choices(&'static [&'static str]),
+}
+
+impl Hint {
+    fn of(arg: &Arg) -> Self {
+        match (&arg.argvalue, arg.value_hint) {
+            (ArgValue::Flag | ArgValue::Count(_), _) => Self::None,
+            (_, Some(ValueHint::FilePath)) => Self::Files,
+            (_, Some(ValueHint::DirPath)) => Self::Dirs,
+            (_, Some(ValueHint::Hostname)) => Self::Hosts,
+            (_, Some(ValueHint::Username)) => Self::Users,
+            (_, Some(ValueHint::CommandName)) => Self::Commands,
+            (_, Some(ValueHint::Other)) => Self::Any,
+            (ArgValue::Path(_) | ArgValue::ExistingPath(_) | ArgValue::PathList(_), None) => {
+                Self::Files
+            }
+            (ArgValue::Choice(choices, _), None) => Self::Choices(choices),
+            _ => Self::Any,
+        }
+    }
+}
+
+// A command with every argument it accepts once parsed: its own, the inherited global ones,
+// the help flag and the version flag.
+struct Node<'a> {
+    // Identifies the command in the scripts.
+    id: String,
+    cmd: &'a Command,
+    args: Vec<Arg>,
+    subcommands: Vec<(&'static str, &'static str)>,
+}
+
+impl Node<'_> {
+    // The identifier of each subcommand, with the word that leads to it.
+    fn children(&self) -> impl Iterator<Item = (&'static str, String)> + '_ {
+        public(&self.cmd.subcommands, |s| s.visibility)
+            .map(|s| (s.name, format!("{}__{}", self.id, ident(s.name))))
+    }
+}
+
+// Completion scripts are installed for every user, so they only have the public arguments and
+// subcommands.
+fn public<T>(items: &[T], visibility: impl Fn(&T) -> Visibility) -> impl Iterator<Item = &T> {
+    items
+        .iter()
+        .filter(move |item| visibility(item) == Visibility::Public)
+}
+
+fn nodes<'a>(
+    root: &Command,
+    cmd: &'a Command,
+    id: String,
+    inherited: &[Arg],
+    buf: &mut Vec<Node<'a>>,
+) {
+    let mut args: Vec<Arg> = public(&cmd.args.args, |a| a.visibility).cloned().collect();
+    for arg in inherited {
+        if !cmd.without_globals.contains(&arg.argname)
+            && !args.iter().any(|a| a.argname == arg.argname)
+        {
+            args.push(arg.clone());
+        }
+    }
+    let globals: Vec<Arg> = args.iter().filter(|a| a.global).cloned().collect();
+    if let Some(flag) = &root.help_flag {
+        if !args.iter().any(|a| a.argname == *flag) {
+            args.push(Arg::new(
+                flag.clone(),
+                ArgValue::Flag,
+                root.strings.help_flag,
+            ));
+        }
+    }
+    if cmd.auto_version && !args.iter().any(|a| a.argname == cmd.version_flag) {
+        args.push(Arg::new(
+            cmd.version_flag.clone(),
+            ArgValue::Flag,
+            root.strings.version_flag,
+        ));
+    }
+    let mut subcommands: Vec<_> = public(&cmd.subcommands, |s| s.visibility)
+        .map(|s| (s.name, s.description))
+        .collect();
+    if root.builtin_help
+        && !subcommands.is_empty()
+        && !subcommands.iter().any(|(n, _)| *n == "help")
+    {
+        subcommands.push(("help", root.strings.help_subcommand));
+    }
+    let node = Node {
+        id,
+        cmd,
+        args,
+        subcommands,
+    };
+    let children: Vec<_> = node.children().map(|(_, id)| id).collect();
+    buf.push(node);
+    for (subcmd, id) in public(&cmd.subcommands, |s| s.visibility).zip(children) {
+        nodes(root, subcmd, id, &globals, buf);
+    }
+}
+
+// Turns a name into a valid function name.
+fn ident(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+// Every name of the argument as it is written on the command line.
+fn names(argname: &ArgName) -> Vec<String> {
+    match argname {
+        ArgName::Short(short) => vec![format!("-{short}")],
+        ArgName::Long(long) => vec![format!("--{long}")],
+        ArgName::Both { short, long } => vec![format!("-{short}"), format!("--{long}")],
+    }
+}
+
+// Quotes text for bash and zsh.
+fn dquote(text: &str) -> String {
+    let mut buf = String::from('"');
+    for c in text.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            buf.push('\\');
+        }
+        buf.push(c);
+    }
+    buf.push('"');
+    buf
+}
+
+// Quotes text for zsh, where backslashes are kept in single quotes.
+fn squote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+// Quotes text for fish, where backslashes escape quotes in single quotes.
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn description(text: &str) -> String {
+    help::escape(text, false).into_owned()
+}
+
+fn bash(nodes: &[Node], program: &str) -> String {
+    let func = ident(program);
+    let mut buf = format!(
+        "# bash completion for {program}
+
+_{func}() {{
+    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"
+    local cmd=\"{func}\" i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case \"${{cmd}},${{COMP_WORDS[i]}}\" in
+"
+    );
+    for node in nodes {
+        for (name, id) in node.children() {
+            let word = dquote(&format!("{},{name}", node.id));
+            buf.push_str(&format!("            {word}) cmd=\"{id}\" ;;\n"));
+        }
+    }
+    buf.push_str("        esac\n    done\n    case \"${cmd}\" in\n");
+    for node in nodes {
+        buf.push_str(&format!("        {})\n", node.id));
+        let valued: Vec<&Arg> = node
+            .args
+            .iter()
+            .filter(|a| !matches!(Hint::of(a), Hint::None))
+            .collect();
+        if !valued.is_empty() {
+            buf.push_str("            case \"${prev}\" in\n");
+            for arg in valued {
+                let reply = match Hint::of(arg) {
+                    Hint::Files => "COMPREPLY=($(compgen -f -- \"${cur}\"))".into(),
+                    Hint::Dirs => "COMPREPLY=($(compgen -d -- \"${cur}\"))".into(),
+                    Hint::Hosts => "COMPREPLY=($(compgen -A hostname -- \"${cur}\"))".into(),
+                    Hint::Users => "COMPREPLY=($(compgen -u -- \"${cur}\"))".into(),
+                    Hint::Commands => "COMPREPLY=($(compgen -c -- \"${cur}\"))".into(),
+                    Hint::Choices(choices) => format!(
+                        "COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))",
+                        dquote(&choices.join(" "))
+                    ),
+                    _ => "COMPREPLY=()".into(),
+                };
+                let names: Vec<String> = names(&arg.argname).iter().map(|n| dquote(n)).collect();
+                buf.push_str(&format!(
+                    "                {})\n                    {reply}\n                    return 0\n                    ;;\n",
+                    names.join("|")
+                ));
+            }
+            buf.push_str("            esac\n");
+        }
+        let words: Vec<String> = node
+            .args
+            .iter()
+            .flat_map(|a| names(&a.argname))
+            .chain(node.subcommands.iter().map(|(name, _)| name.to_string()))
+            .collect();
+        buf.push_str(&format!(
+            "            COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))\n            ;;\n",
+            dquote(&words.join(" "))
+        ));
+    }
+    buf.push_str(&format!(
+        "    esac\n}}\n\ncomplete -F _{func} -o bashdefault -o default {program}\n"
+    ));
+    buf
+}
+
+fn zsh(nodes: &[Node], program: &str) -> String {
+    let func = ident(program);
+    let mut buf = format!(
+        "#compdef {program}
+
+_{func}() {{
+    local cmd=\"{func}\" start=1 i
+    for ((i = 2; i < CURRENT; i++)); do
+        case \"${{cmd}},${{words[i]}}\" in
+"
+    );
+    for node in nodes {
+        for (name, id) in node.children() {
+            let word = dquote(&format!("{},{name}", node.id));
+            buf.push_str(&format!("            {word}) cmd=\"{id}\"; start=$i ;;\n"));
+        }
+    }
+    buf.push_str(
+        "        esac
+    done
+    words=(\"${(@)words[start,-1]}\")
+    (( CURRENT -= start - 1 ))
+    case \"${cmd}\" in
+",
+    );
+    for node in nodes {
+        let mut specs = Vec::new();
+        for arg in &node.args {
+            let repeat = match arg.argvalue {
+                ArgValue::List(_) | ArgValue::PathList(_) | ArgValue::Count(_) => "*",
+                _ => "",
+            };
+            let value = arg.value_name.unwrap_or("value").replace(':', r"\:");
+            let action = match Hint::of(arg) {
+                Hint::None => "".into(),
+                Hint::Any => format!(":{value}:"),
+                Hint::Files => format!(":{value}:_files"),
+                Hint::Dirs => format!(":{value}:_files -/"),
+                Hint::Hosts => format!(":{value}:_hosts"),
+                Hint::Users => format!(":{value}:_users"),
+                Hint::Commands => format!(":{value}:_command_names -e"),
+                Hint::Choices(choices) => format!(
+                    ":{value}:({})",
+                    choices
+                        .iter()
+                        .map(|c| dquote(c).replace(':', r"\:"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+            };
+            let description = description(arg.description)
+                .replace('\\', r"\\")
+                .replace('[', r"\[")
+                .replace(']', r"\]");
+            for name in names(&arg.argname) {
+                let attached = match Hint::of(arg) {
+                    Hint::None => "",
+                    _ if name.starts_with("--") => "=",
+                    _ => "+",
+                };
+                specs.push(squote(&format!(
+                    "{repeat}{name}{attached}[{description}]{action}"
+                )));
+            }
+        }
+        if !node.subcommands.is_empty() {
+            let subcommands: Vec<String> = node
+                .subcommands
+                .iter()
+                .map(|(name, description)| {
+                    format!(
+                        "{name}\\:{}",
+                        dquote(&self::description(description)).replace(':', r"\:")
+                    )
+                })
+                .collect();
+            specs.push(squote(&format!(
+                "1:subcommand:(({}))",
+                subcommands.join(" ")
+            )));
+        }
+        if let Some(rest) = &node.cmd.rest {
+            specs.push(squote(&format!(
+                "*:{}:_default",
+                rest.name.replace(':', r"\:")
+            )));
+        }
+        buf.push_str(&format!("        {})\n            _arguments -s", node.id));
+        for spec in specs {
+            buf.push_str(&format!(" \\\n                {spec}"));
+        }
+        buf.push_str("\n            ;;\n");
+    }
+    buf.push_str(&format!("    esac\n}}\n\n_{func} \"$@\"\n"));
+    buf
+}
+
+fn fish(nodes: &[Node], program: &str) -> String {
+    let func = ident(program);
+    let mut buf = format!(
+        "# fish completion for {program}
+
+function __{func}_command
+    set -l cmd {func}
+    for word in (commandline -opc)[2..-1]
+        switch \"$cmd,$word\"
+"
+    );
+    for node in nodes {
+        for (name, id) in node.children() {
+            let word = fish_quote(&format!("{},{name}", node.id));
+            buf.push_str(&format!(
+                "            case {word}\n                set cmd {id}\n"
+            ));
+        }
+    }
+    buf.push_str("        end\n    end\n    echo $cmd\nend\n");
+    for node in nodes {
+        let complete = format!(
+            "complete -c {program} -n 'test (__{func}_command) = {}'",
+            node.id
+        );
+        buf.push('\n');
+        if node.cmd.rest.is_none() {
+            buf.push_str(&format!("{complete} -f\n"));
+        }
+        for arg in &node.args {
+            let names = match &arg.argname {
+                ArgName::Short(short) => format!("-s {}", fish_quote(&short.to_string())),
+                ArgName::Long(long) => format!("-l {}", fish_quote(long)),
+                ArgName::Both { short, long } => format!(
+                    "-s {} -l {}",
+                    fish_quote(&short.to_string()),
+                    fish_quote(long)
+                ),
+            };
+            let value = match Hint::of(arg) {
+                Hint::None => "".into(),
+                Hint::Any => " -r".into(),
+                Hint::Files => " -r -F".into(),
+                Hint::Dirs => " -r -f -a '(__fish_complete_directories)'".into(),
+                Hint::Hosts => " -r -f -a '(__fish_print_hostnames)'".into(),
+                Hint::Users => " -r -f -a '(__fish_complete_users)'".into(),
+                Hint::Commands => " -r -f -a '(__fish_complete_command)'".into(),
+                Hint::Choices(choices) => format!(" -r -f -a {}", fish_quote(&choices.join(" "))),
+            };
+            buf.push_str(&format!(
+                "{complete} {names}{value} -d {}\n",
+                fish_quote(&description(arg.description))
+            ));
+        }
+        for (name, description) in &node.subcommands {
+            buf.push_str(&format!(
+                "{complete} -f -a {} -d {}\n",
+                fish_quote(name),
+                fish_quote(&self::description(description))
+            ));
+        }
+    }
+    buf
+}
+
+/// Returns the completion script of the command for the given shell.
+///
+/// The script completes arguments (with the possible values of [`ArgValue::Choice`], files for
+/// paths, or what their [`ValueHint`] says) and subcommands at any depth. The output only depends
+/// on the command, so it can be generated at build time and packaged.
+pub fn generate(shell: Shell, cmd: &Command) -> String {
+    let mut buf = Vec::new();
+    nodes(cmd, cmd, ident(cmd.name), &[], &mut buf);
+    match shell {
+        Shell::Bash => bash(&buf, cmd.name),
+        Shell::Zsh => zsh(&buf, cmd.name),
+        Shell::Fish => fish(&buf, cmd.name),
+    }
+}
+
+/// Writes the completion script of the command (see [`generate`]) in the given directory,
+/// creating it if needed, and returns the path of the file. The file is named as expected by
+/// the shell (see [`Shell::file_name`]).
+///
+/// It is meant to be called from a build script or an `xtask`.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// fn cli() -> Command {
+///     Command::create("myapp", "This is my cool app.")
+///         .arg(arg!(-'f', --format), ArgValue::Choice(&["json", "yaml"], None), "Output format.")
+/// }
+///
+/// let dir = std::env::temp_dir().join("myapp-completions");
+/// for shell in Shell::ALL {
+///     let path = generate_to(&dir, shell, &cli()).unwrap();
+///     println!("Generated {}", path.display());
+/// }
+/// assert!(dir.join("_myapp").exists());
+/// ```
+pub fn generate_to(dir: impl AsRef<Path>, shell: Shell, cmd: &Command) -> io::Result<PathBuf> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let path = dir.join(shell.file_name(cmd.name));
+    fs::write(&path, generate(shell, cmd))?;
+    Ok(path)
+}
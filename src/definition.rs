@@ -0,0 +1,833 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{error, fmt, iter::Peekable, str::Chars};
+
+use crate::*;
+
+/// An error found while loading a command definition. See [`Command::from_toml_str`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DefinitionError {
+    /// The document is not valid TOML or uses a part of TOML that is not supported.
+    /// It carries the line of the error and a description.
+    Syntax(usize, String),
+
+    /// The document does not define a proper command. It carries the table with the error
+    /// (e.g. `subcommands[0].args[1]`, empty for the root command) and a description.
+    Invalid(String, String),
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Syntax(line, reason) => write!(f, "line {line}: {reason}"),
+            Self::Invalid(path, reason) if path.is_empty() => f.write_str(reason),
+            Self::Invalid(path, reason) => write!(f, "{path}: {reason}"),
+        }
+    }
+}
+
+impl error::Error for DefinitionError {}
+
+#[derive(Debug)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Array(Vec<Value>),
+    Table(Table),
+}
+
+// Tables keep the order of their keys, which is the order of arguments and subcommands.
+type Table = Vec<(String, Value)>;
+
+// Returns the table at the given path, creating the missing ones.
+// Arrays of tables lead to their last table.
+fn table<'t>(mut table: &'t mut Table, path: &[String]) -> Result<&'t mut Table, String> {
+    for key in path {
+        let index = match table.iter().position(|(k, _)| k == key) {
+            Some(index) => index,
+            None => {
+                table.push((key.clone(), Value::Table(Table::new())));
+                table.len() - 1
+            }
+        };
+        table = match &mut table[index].1 {
+            Value::Table(inner) => inner,
+            Value::Array(array) => match array.last_mut() {
+                Some(Value::Table(inner)) => inner,
+                _ => return Err(format!("'{key}' is not a table")),
+            },
+            _ => return Err(format!("'{key}' is not a table")),
+        };
+    }
+    Ok(table)
+}
+
+fn insert(root: &mut Table, path: &[String], value: Value) -> Result<(), String> {
+    let (key, parents) = path.split_last().expect("Keys are never empty");
+    let table = table(root, parents)?;
+    if table.iter().any(|(k, _)| k == key) {
+        return Err(format!("'{key}' is defined twice"));
+    }
+    table.push((key.clone(), value));
+    Ok(())
+}
+
+// A parser of the subset of TOML used by definitions: tables, arrays of tables, dotted keys,
+// single-line strings, integers, floats, booleans, arrays and inline tables.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn error<T>(&self, reason: impl Into<String>) -> Result<T, DefinitionError> {
+        Err(DefinitionError::Syntax(self.line, reason.into()))
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.next();
+        }
+        found
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.next();
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), None | Some('\n')) {
+                self.next();
+            }
+        }
+    }
+
+    // Skips whitespace, comments and newlines.
+    fn skip_blank(&mut self) {
+        loop {
+            self.skip_spaces();
+            self.skip_comment();
+            if !self.eat('\n') && !self.eat('\r') {
+                break;
+            }
+        }
+    }
+
+    fn end_of_line(&mut self) -> Result<(), DefinitionError> {
+        self.skip_spaces();
+        self.skip_comment();
+        self.eat('\r');
+        match self.next() {
+            None | Some('\n') => Ok(()),
+            Some(c) => self.error(format!("unexpected '{c}'")),
+        }
+    }
+
+    fn key(&mut self) -> Result<String, DefinitionError> {
+        match self.peek() {
+            Some('"') => self.basic_string(),
+            Some('\'') => self.literal_string(),
+            _ => {
+                let mut key = String::new();
+                while let Some(c) = self
+                    .peek()
+                    .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                {
+                    key.push(c);
+                    self.next();
+                }
+                if key.is_empty() {
+                    return self.error("expected a key");
+                }
+                Ok(key)
+            }
+        }
+    }
+
+    // Parses a (dotted) key.
+    fn path(&mut self) -> Result<Vec<String>, DefinitionError> {
+        let mut path = Vec::new();
+        loop {
+            self.skip_spaces();
+            path.push(self.key()?);
+            self.skip_spaces();
+            if !self.eat('.') {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn unicode(&mut self, digits: usize) -> Result<char, DefinitionError> {
+        let code: String = (0..digits).filter_map(|_| self.next()).collect();
+        match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+            Some(c) => Ok(c),
+            None => self.error(format!("invalid unicode escape '{code}'")),
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, DefinitionError> {
+        self.next();
+        let mut buf = String::new();
+        loop {
+            match self.next() {
+                None | Some('\n') => return self.error("unterminated string"),
+                Some('"') => return Ok(buf),
+                Some('\\') => match self.next() {
+                    Some('n') => buf.push('\n'),
+                    Some('t') => buf.push('\t'),
+                    Some('r') => buf.push('\r'),
+                    Some('b') => buf.push('\u{8}'),
+                    Some('f') => buf.push('\u{c}'),
+                    Some('"') => buf.push('"'),
+                    Some('\\') => buf.push('\\'),
+                    Some('u') => buf.push(self.unicode(4)?),
+                    Some('U') => buf.push(self.unicode(8)?),
+                    _ => return self.error("invalid escape in string"),
+                },
+                Some(c) => buf.push(c),
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<String, DefinitionError> {
+        self.next();
+        let mut buf = String::new();
+        loop {
+            match self.next() {
+                None | Some('\n') => return self.error("unterminated string"),
+                Some('\'') => return Ok(buf),
+                Some(c) => buf.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, DefinitionError> {
+        self.next();
+        let mut values = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.eat(']') {
+                return Ok(Value::Array(values));
+            }
+            values.push(self.value()?);
+            self.skip_blank();
+            if !self.eat(',') {
+                self.skip_blank();
+                if self.eat(']') {
+                    return Ok(Value::Array(values));
+                }
+                return self.error("expected ',' or ']'");
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Value, DefinitionError> {
+        self.next();
+        let mut table = Table::new();
+        self.skip_spaces();
+        if self.eat('}') {
+            return Ok(Value::Table(table));
+        }
+        loop {
+            let path = self.path()?;
+            if !self.eat('=') {
+                return self.error("expected '='");
+            }
+            self.skip_spaces();
+            let value = self.value()?;
+            insert(&mut table, &path, value).or_else(|e| self.error(e))?;
+            self.skip_spaces();
+            if self.eat('}') {
+                return Ok(Value::Table(table));
+            }
+            if !self.eat(',') {
+                return self.error("expected ',' or '}'");
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, DefinitionError> {
+        match self.peek() {
+            Some('"') => return self.basic_string().map(Value::String),
+            Some('\'') => return self.literal_string().map(Value::String),
+            Some('[') => return self.array(),
+            Some('{') => return self.inline_table(),
+            _ => (),
+        }
+        let mut token = String::new();
+        while let Some(c) = self
+            .peek()
+            .filter(|c| !matches!(c, ',' | ']' | '}' | '#' | ' ' | '\t' | '\r' | '\n'))
+        {
+            token.push(c);
+            self.next();
+        }
+        let number = token.replace('_', "");
+        match token.as_str() {
+            "true" => Ok(Value::Boolean(true)),
+            "false" => Ok(Value::Boolean(false)),
+            "inf" | "+inf" => Ok(Value::Float(f64::INFINITY)),
+            "-inf" => Ok(Value::Float(f64::NEG_INFINITY)),
+            "nan" | "+nan" | "-nan" => Ok(Value::Float(f64::NAN)),
+            _ if token.is_empty() => self.error("expected a value"),
+            _ => {
+                if let Ok(integer) = number.parse() {
+                    Ok(Value::Integer(integer))
+                } else if let Ok(float) = number.parse() {
+                    Ok(Value::Float(float))
+                } else {
+                    self.error(format!("invalid value '{token}'"))
+                }
+            }
+        }
+    }
+
+    fn document(&mut self) -> Result<Table, DefinitionError> {
+        let mut root = Table::new();
+        let mut current = Vec::new();
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None => return Ok(root),
+                Some('[') => {
+                    self.next();
+                    let array = self.eat('[');
+                    let path = self.path()?;
+                    if !self.eat(']') || (array && !self.eat(']')) {
+                        return self.error("expected ']'");
+                    }
+                    if array {
+                        let (key, parents) = path.split_last().expect("Keys are never empty");
+                        let table = table(&mut root, parents).or_else(|e| self.error(e))?;
+                        match table.iter_mut().find(|(k, _)| k == key) {
+                            Some((_, Value::Array(array))) => {
+                                array.push(Value::Table(Table::new()))
+                            }
+                            Some(_) => {
+                                return self.error(format!("'{key}' is not an array of tables"))
+                            }
+                            None => table.push((
+                                key.clone(),
+                                Value::Array(vec![Value::Table(Table::new())]),
+                            )),
+                        }
+                    } else {
+                        table(&mut root, &path).or_else(|e| self.error(e))?;
+                    }
+                    current = path;
+                }
+                Some(_) => {
+                    let mut path = current.clone();
+                    path.extend(self.path()?);
+                    if !self.eat('=') {
+                        return self.error("expected '='");
+                    }
+                    self.skip_spaces();
+                    let value = self.value()?;
+                    insert(&mut root, &path, value).or_else(|e| self.error(e))?;
+                }
+            }
+            self.end_of_line()?;
+        }
+    }
+}
+
+// Commands only hold static strings, so the ones of the definition are leaked.
+fn leak(text: String) -> &'static str {
+    Box::leak(text.into_boxed_str())
+}
+
+// The fields of a table of the definition, which are taken out one by one so that the unknown
+// ones are left at the end.
+struct Fields {
+    path: String,
+    table: Table,
+}
+
+impl Fields {
+    fn error<T>(&self, reason: impl Into<String>) -> Result<T, DefinitionError> {
+        Err(DefinitionError::Invalid(self.path.clone(), reason.into()))
+    }
+
+    fn take(&mut self, key: &str) -> Option<Value> {
+        let index = self.table.iter().position(|(k, _)| k == key)?;
+        Some(self.table.remove(index).1)
+    }
+
+    fn string(&mut self, key: &str) -> Result<Option<String>, DefinitionError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value)),
+            Some(_) => self.error(format!("'{key}' must be a string")),
+        }
+    }
+
+    fn text(&mut self, key: &str) -> Result<Option<&'static str>, DefinitionError> {
+        Ok(self.string(key)?.map(leak))
+    }
+
+    fn required(&mut self, key: &str) -> Result<&'static str, DefinitionError> {
+        match self.text(key)? {
+            Some(text) => Ok(text),
+            None => self.error(format!("'{key}' is missing")),
+        }
+    }
+
+    fn boolean(&mut self, key: &str) -> Result<Option<bool>, DefinitionError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::Boolean(value)) => Ok(Some(value)),
+            Some(_) => self.error(format!("'{key}' must be a boolean")),
+        }
+    }
+
+    fn visibility(&mut self) -> Result<Option<Visibility>, DefinitionError> {
+        match self.string("visibility")?.as_deref() {
+            None => Ok(None),
+            Some("public") => Ok(Some(Visibility::Public)),
+            Some("internal") => Ok(Some(Visibility::Internal)),
+            Some("hidden") => Ok(Some(Visibility::Hidden)),
+            Some(_) => self.error("'visibility' must be one of: public, internal, hidden"),
+        }
+    }
+
+    fn child(&self, key: String, table: Table) -> Fields {
+        let path = if self.path.is_empty() {
+            key
+        } else {
+            format!("{}.{key}", self.path)
+        };
+        Fields { path, table }
+    }
+
+    fn table(&mut self, key: &str) -> Result<Option<Fields>, DefinitionError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::Table(table)) => Ok(Some(self.child(key.into(), table))),
+            Some(_) => self.error(format!("'{key}' must be a table")),
+        }
+    }
+
+    fn tables(&mut self, key: &str) -> Result<Vec<Fields>, DefinitionError> {
+        let values = match self.take(key) {
+            None => return Ok(Vec::new()),
+            Some(Value::Array(values)) => values,
+            Some(_) => return self.error(format!("'{key}' must be an array of tables")),
+        };
+        let mut tables = Vec::new();
+        for (i, value) in values.into_iter().enumerate() {
+            let Value::Table(table) = value else {
+                return self.error(format!("'{key}' must be an array of tables"));
+            };
+            tables.push(self.child(format!("{key}[{i}]"), table));
+        }
+        Ok(tables)
+    }
+
+    fn finish(self) -> Result<(), DefinitionError> {
+        match self.table.first() {
+            Some((key, _)) => self.error(format!("unknown field '{key}'")),
+            None => Ok(()),
+        }
+    }
+}
+
+fn strings(fields: &Fields, key: &str, values: Vec<Value>) -> Result<Vec<String>, DefinitionError> {
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::String(value) => Ok(value),
+            _ => fields.error(format!("'{key}' must be an array of strings")),
+        })
+        .collect()
+}
+
+const KINDS: &[&str] = &[
+    "string", "num", "float", "path", "flag", "choice", "range", "list", "count", "paths", "bytes",
+    "ratio",
+];
+
+fn value(
+    fields: &mut Fields,
+    kind: &str,
+    default: Option<Value>,
+) -> Result<ArgValue, DefinitionError> {
+    if !KINDS.contains(&kind) {
+        return fields.error(format!(
+            "unknown type '{kind}', must be one of: {}",
+            KINDS.join(", ")
+        ));
+    }
+    let exists = kind == "path" && fields.boolean("exists")? == Some(true);
+    let argvalue = match (kind, default) {
+        ("string", None) => ArgValue::String(None),
+        ("string", Some(Value::String(value))) => ArgValue::String(Some(value)),
+        ("num", None) => ArgValue::Num(None),
+        ("num", Some(Value::Integer(value))) => ArgValue::Num(Some(value)),
+        ("float", None) => ArgValue::Float(None),
+        ("float", Some(Value::Float(value))) => ArgValue::Float(Some(value)),
+        ("float", Some(Value::Integer(value))) => ArgValue::Float(Some(value as f64)),
+        ("path", None) if exists => ArgValue::ExistingPath(None),
+        ("path", Some(Value::String(value))) if exists => {
+            ArgValue::ExistingPath(Some(value.into()))
+        }
+        ("path", None) => ArgValue::Path(None),
+        ("path", Some(Value::String(value))) => ArgValue::Path(Some(value.into())),
+        ("flag", None) => ArgValue::Flag,
+        ("count", None) => ArgValue::Count(0),
+        ("count", Some(Value::Integer(value))) if value >= 0 => ArgValue::Count(value as usize),
+        ("list", None) => ArgValue::List(Vec::new()),
+        ("list", Some(Value::Array(values))) => ArgValue::List(strings(fields, "default", values)?),
+        ("paths", None) => ArgValue::PathList(Vec::new()),
+        ("paths", Some(Value::Array(values))) => ArgValue::PathList(
+            strings(fields, "default", values)?
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+        ),
+        ("ratio", None) => ArgValue::Ratio(None),
+        ("ratio", Some(Value::Float(value))) if (0.0..=1.0).contains(&value) => {
+            ArgValue::Ratio(Some(value))
+        }
+        ("ratio", Some(Value::Integer(value @ (0 | 1)))) => ArgValue::Ratio(Some(value as f64)),
+        ("ratio", Some(_)) => return fields.error("'default' must be between 0 and 1"),
+        ("bytes", None) => ArgValue::Bytes(None),
+        ("bytes", Some(Value::String(value))) => match encoding::decode(&value) {
+            Ok(bytes) => ArgValue::Bytes(Some(bytes)),
+            Err(e) => return fields.error(format!("'default' must be base64 or hex: {e}")),
+        },
+        ("choice", default) => {
+            let choices = match fields.take("choices") {
+                Some(Value::Array(values)) if !values.is_empty() => {
+                    strings(fields, "choices", values)?
+                }
+                _ => return fields.error("'choices' must be a non-empty array of strings"),
+            };
+            let default = match default {
+                None => None,
+                Some(Value::String(value)) if choices.contains(&value) => Some(value),
+                Some(_) => return fields.error("'default' must be one of the choices"),
+            };
+            let choices: Vec<&'static str> = choices.into_iter().map(leak).collect();
+            ArgValue::Choice(Box::leak(choices.into_boxed_slice()), default)
+        }
+        ("range", default) => {
+            let range = match fields.take("range") {
+                Some(Value::Array(values)) => match values[..] {
+                    [Value::Integer(start), Value::Integer(end)] if start <= end => start..=end,
+                    _ => return fields.error("'range' must be an array like [min, max]"),
+                },
+                _ => return fields.error("'range' must be an array like [min, max]"),
+            };
+            let default = match default {
+                None => None,
+                Some(Value::Integer(value)) if range.contains(&value) => Some(value),
+                Some(_) => return fields.error("'default' must be in the range"),
+            };
+            ArgValue::Range(range, default)
+        }
+        _ => return fields.error(format!("'default' is not a valid {kind}")),
+    };
+    Ok(argvalue)
+}
+
+fn arg(mut fields: Fields) -> Result<Arg, DefinitionError> {
+    let short = match fields.string("short")? {
+        None => None,
+        Some(short) => {
+            let mut chars = short.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => return fields.error("'short' must be a single character"),
+            }
+        }
+    };
+    let argname = match (short, fields.string("long")?) {
+        (Some(short), Some(long)) => ArgName::try_both(short, &long),
+        (Some(short), None) => ArgName::try_short(short),
+        (None, Some(long)) => ArgName::try_long(&long),
+        (None, None) => return fields.error("either 'short' or 'long' is required"),
+    };
+    let argname = argname.or_else(|e| fields.error(e.to_string()))?;
+    let description = fields.required("description")?;
+    let kind = fields.string("type")?.unwrap_or("flag".into());
+    let default = fields.take("default");
+    let mut arg = Arg::new(argname, value(&mut fields, &kind, default)?, description);
+    if let Some(long_description) = fields.text("long_description")? {
+        arg = arg.long_description(long_description);
+    }
+    if let Some(value_name) = fields.text("value_name")? {
+        arg = arg.value_name(value_name);
+    }
+    if let Some(example) = fields.text("example")? {
+        arg = arg.example(example);
+    }
+    if let Some(provider) = fields.text("provider")? {
+        arg = arg.provider(provider);
+    }
+    if let Some(visibility) = fields.visibility()? {
+        arg = arg.visibility(visibility);
+    }
+    if let Some(note) = fields.text("deprecated")? {
+        arg = arg.deprecated(note);
+    }
+    if let Some(global) = fields.boolean("global")? {
+        arg = arg.global(global);
+    }
+    match fields.string("placement")?.as_deref() {
+        None => (),
+        Some("anywhere") => arg = arg.placement(Placement::Anywhere),
+        Some("before") => arg = arg.placement(Placement::Before),
+        Some("after") => arg = arg.placement(Placement::After),
+        Some(_) => return fields.error("'placement' must be one of: anywhere, before, after"),
+    }
+    if arg.placement != Placement::Anywhere && !arg.global {
+        return fields.error("only global arguments can have a placement");
+    }
+    match fields.take("only_with") {
+        None => (),
+        Some(Value::Array(values)) if !values.is_empty() => {
+            for name in strings(&fields, "only_with", values)? {
+                arg = arg.only_with(leak(name));
+            }
+        }
+        _ => return fields.error("'only_with' must be a non-empty array of strings"),
+    }
+    if let Some(split_paths) = fields.boolean("split_paths")? {
+        arg = arg.split_paths(split_paths);
+    }
+    if fields.boolean("sensitive")? == Some(true) {
+        arg = arg.sensitive();
+    }
+    if let Some(trim) = fields.boolean("trim")? {
+        arg = arg.trim(trim);
+    }
+    if let Some(allow_empty) = fields.boolean("allow_empty")? {
+        arg = arg.allow_empty(allow_empty);
+    }
+    if fields.boolean("negatable")? == Some(true) {
+        if kind != "flag" || matches!(arg.argname, ArgName::Short(_)) {
+            return fields.error("only flags with a long name can be negatable");
+        }
+        arg = arg.negatable();
+    }
+    match fields.string("value_hint")?.as_deref() {
+        None => (),
+        Some("file") => arg = arg.value_hint(ValueHint::FilePath),
+        Some("dir") => arg = arg.value_hint(ValueHint::DirPath),
+        Some("hostname") => arg = arg.value_hint(ValueHint::Hostname),
+        Some("username") => arg = arg.value_hint(ValueHint::Username),
+        Some("command") => arg = arg.value_hint(ValueHint::CommandName),
+        Some("other") => arg = arg.value_hint(ValueHint::Other),
+        Some(_) => {
+            return fields.error(
+                "'value_hint' must be one of: file, dir, hostname, username, command, other",
+            )
+        }
+    }
+    match fields.take("num_values") {
+        None => (),
+        Some(Value::Array(values)) if kind == "list" => match values[..] {
+            [Value::Integer(min), Value::Integer(max)] if 0 < min && min <= max => {
+                arg = arg.num_values(min as usize..=max as usize)
+            }
+            _ => return fields.error("'num_values' must be an array like [min, max]"),
+        },
+        Some(Value::Array(_)) => return fields.error("only lists can take more values"),
+        Some(_) => return fields.error("'num_values' must be an array like [min, max]"),
+    }
+    match fields.string("delimiter")? {
+        None => (),
+        Some(delimiter) if kind == "list" && arg.num_values.is_none() => {
+            let mut chars = delimiter.chars();
+            match (chars.next(), chars.next()) {
+                (Some(delimiter), None) => arg = arg.delimiter(delimiter),
+                _ => return fields.error("'delimiter' must be a single character"),
+            }
+        }
+        Some(_) => return fields.error("only lists that take one value can have a delimiter"),
+    }
+    match fields.string("merge")?.as_deref() {
+        None => (),
+        Some(_) if !matches!(kind.as_str(), "list" | "paths") => {
+            return fields.error("only lists can merge values")
+        }
+        Some("append") => arg = arg.merge(MergePolicy::Append),
+        Some("dedupe") => arg = arg.merge(MergePolicy::Dedupe),
+        Some("error") => arg = arg.merge(MergePolicy::Error),
+        Some(_) => return fields.error("'merge' must be one of: append, dedupe, error"),
+    }
+    match fields.string("duplicates")?.as_deref() {
+        None => (),
+        Some("overwrite") => arg = arg.duplicates(DuplicatePolicy::Overwrite),
+        Some("error") => arg = arg.duplicates(DuplicatePolicy::Error),
+        Some("append") if matches!(kind.as_str(), "list" | "paths") => {
+            arg = arg.duplicates(DuplicatePolicy::Append)
+        }
+        Some("append") => return fields.error("only lists can append values"),
+        Some(_) => return fields.error("'duplicates' must be one of: overwrite, error, append"),
+    }
+    fields.finish()?;
+    Ok(arg)
+}
+
+fn command(mut fields: Fields) -> Result<Command, DefinitionError> {
+    let mut cmd = Command::create(fields.required("name")?, fields.required("description")?);
+    if let Some(version) = fields.text("version")? {
+        cmd = cmd.version(version);
+    }
+    if let Some(long_version) = fields.text("long_version")? {
+        cmd = cmd.long_version(long_version);
+    }
+    if let Some(author) = fields.text("author")? {
+        cmd = cmd.author(author);
+    }
+    if let Some(license) = fields.text("license")? {
+        cmd = cmd.license(license);
+    }
+    if let Some(homepage) = fields.text("homepage")? {
+        cmd = cmd.homepage(homepage);
+    }
+    if let Some(repository) = fields.text("repository")? {
+        cmd = cmd.repository(repository);
+    }
+    if let Some(epilogue) = fields.text("epilogue")? {
+        cmd = cmd.epilogue(epilogue);
+    }
+    if let Some(provider) = fields.text("provider")? {
+        cmd = cmd.provider(provider);
+    }
+    if let Some(visibility) = fields.visibility()? {
+        cmd = cmd.visibility(visibility);
+    }
+    for mut example in fields.tables("examples")? {
+        cmd = cmd.example(example.required("line")?, example.required("description")?);
+        example.finish()?;
+    }
+    if let Some(var) = fields.text("args_env")? {
+        cmd = cmd.args_env(var);
+    }
+    if let Some(prefix) = fields.text("env_prefix")? {
+        cmd = cmd.env_prefix(prefix);
+    }
+    if let Some(color) = fields.boolean("color")? {
+        cmd = cmd.color(color);
+    }
+    if let Some(auto_version) = fields.boolean("auto_version")? {
+        cmd = cmd.auto_version(auto_version);
+    }
+    if fields.boolean("verbosity_flags")? == Some(true) {
+        cmd = cmd.verbosity_flags();
+    }
+    if fields.boolean("color_flag")? == Some(true) {
+        cmd = cmd.color_flag();
+    }
+    if let Some(subcommand_required) = fields.boolean("subcommand_required")? {
+        cmd = cmd.subcommand_required(subcommand_required);
+    }
+    if let Some(multicall) = fields.boolean("multicall")? {
+        cmd = cmd.multicall(multicall);
+    }
+    if let Some(posix_strict) = fields.boolean("posix_strict")? {
+        cmd = cmd.posix_strict(posix_strict);
+    }
+    if let Some(collect_errors) = fields.boolean("collect_errors")? {
+        cmd = cmd.collect_errors(collect_errors);
+    }
+    match fields.string("autocorrect")?.as_deref() {
+        None => (),
+        Some("off") => cmd = cmd.autocorrect(Autocorrect::Off),
+        Some("run") => cmd = cmd.autocorrect(Autocorrect::Run),
+        Some("prompt") => cmd = cmd.autocorrect(Autocorrect::Prompt),
+        Some(_) => return fields.error("'autocorrect' must be one of: off, run, prompt"),
+    }
+    if let Some(mut rest) = fields.table("rest")? {
+        cmd = cmd.rest(rest.required("name")?, rest.required("description")?);
+        match rest.take("min") {
+            None => (),
+            Some(Value::Integer(min)) if min >= 0 => cmd = cmd.rest_min(min as usize),
+            Some(_) => return rest.error("'min' must be a non-negative integer"),
+        }
+        rest.finish()?;
+    }
+    for fields in fields.tables("args")? {
+        let path = fields.path.clone();
+        let arg = arg(fields)?;
+        if cmd.args.try_get(arg.argname.clone()).is_some() {
+            let reason = format!("the argument '{}' already exists", arg.argname);
+            return Err(DefinitionError::Invalid(path, reason));
+        }
+        cmd = cmd.add_arg(arg);
+    }
+    for fields in fields.tables("subcommands")? {
+        let path = fields.path.clone();
+        let subcmd = command(fields)?;
+        if let Some(existing) = cmd.sibling(subcmd.name) {
+            let reason = if existing.name == subcmd.name {
+                format!("the subcommand '{}' already exists", subcmd.name)
+            } else {
+                format!(
+                    "the subcommand '{}' differs only in case from '{}'",
+                    subcmd.name, existing.name
+                )
+            };
+            return Err(DefinitionError::Invalid(path, reason));
+        }
+        if let Some([(_, name), (_, other)]) = cmd.name_conflict(&subcmd) {
+            let reason = format!("the arguments '{name}' and '{other}' share only one name");
+            return Err(DefinitionError::Invalid(path, reason));
+        }
+        cmd = cmd.subcommand(subcmd);
+    }
+    fields.finish()?;
+    Ok(cmd)
+}
+
+pub fn load(document: &str) -> Result<Command, DefinitionError> {
+    let mut parser = Parser {
+        chars: document.chars().peekable(),
+        line: 1,
+    };
+    let table = parser.document()?;
+    command(Fields {
+        path: String::new(),
+        table,
+    })
+}
@@ -0,0 +1,84 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+fn hex_digit(c: u8) -> Result<u8, String> {
+    (c as char)
+        .to_digit(16)
+        .map(|d| d as u8)
+        .ok_or_else(|| format!("invalid hex digit '{}'", c as char))
+}
+
+fn hex(input: &str) -> Result<Vec<u8>, String> {
+    if !input.len().is_multiple_of(2) {
+        return Err("odd number of hex digits".into());
+    }
+    input
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| Ok(hex_digit(pair[0])? << 4 | hex_digit(pair[1])?))
+        .collect()
+}
+
+fn base64_digit(c: u8) -> Result<u32, String> {
+    match c {
+        b'A'..=b'Z' => Ok((c - b'A') as u32),
+        b'a'..=b'z' => Ok((c - b'a' + 26) as u32),
+        b'0'..=b'9' => Ok((c - b'0' + 52) as u32),
+        b'+' | b'-' => Ok(62),
+        b'/' | b'_' => Ok(63),
+        _ => Err(format!("invalid base64 character '{}'", c as char)),
+    }
+}
+
+// Decodes both the standard and the URL-safe alphabets, with or without padding.
+fn base64(input: &str) -> Result<Vec<u8>, String> {
+    let input = input.trim_end_matches('=').as_bytes();
+    if input.len() % 4 == 1 {
+        return Err("invalid base64 length".into());
+    }
+    let mut buf = Vec::with_capacity(input.len() * 3 / 4);
+    for chunk in input.chunks(4) {
+        let mut bits = 0;
+        for (i, &c) in chunk.iter().enumerate() {
+            bits |= base64_digit(c)? << (18 - 6 * i);
+        }
+        let bytes = bits.to_be_bytes();
+        buf.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Ok(buf)
+}
+
+// Decodes `hex:`-prefixed or base64 input.
+pub fn decode(input: &str) -> Result<Vec<u8>, String> {
+    match input.strip_prefix("hex:") {
+        Some(input) => hex(input),
+        None => base64(input),
+    }
+}
+
+// Encodes bytes so that they can be decoded again.
+pub fn encode(bytes: &[u8]) -> String {
+    let mut buf = String::from("hex:");
+    for byte in bytes {
+        buf.push_str(&format!("{byte:02x}"));
+    }
+    buf
+}
@@ -0,0 +1,627 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{
+    error, fmt,
+    io::{self, Write},
+    iter,
+    sync::Arc,
+};
+
+#[cfg(feature = "color")]
+use owo_colors::OwoColorize;
+
+use crate::*;
+
+/// The kind of a [`ParseError`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// An argument that does not exist in the command was used.
+    UnknownArg(ArgName),
+
+    /// A token that cannot be an argument (e.g. `-` followed by a combining character).
+    InvalidArg(String),
+
+    /// A command line argument that is not valid UTF-8. It carries its lossy conversion.
+    /// See [`Command::utf8_policy`].
+    NonUtf8Arg(String),
+
+    /// A token that starts with more than two dashes (e.g. `---verbose`).
+    TooManyDashes(String),
+
+    /// A lone `-` that is not a value, given to a command without positional values.
+    LoneDash,
+
+    /// A `--` that is the last token.
+    TrailingDoubleDash,
+
+    /// A token that is neither an argument nor the value of one.
+    UnexpectedToken(String),
+
+    /// The value of an argument could not be parsed or is not allowed.
+    InvalidValue {
+        /// The argument the value was given to.
+        arg: ArgName,
+
+        /// The given value.
+        value: String,
+
+        /// What the value must be (e.g. `"a valid number: invalid digit found in string"`).
+        reason: String,
+    },
+
+    /// An argument that takes a value was given none: it was the last token, or it was followed
+    /// by `--` or by the name of a subcommand.
+    MissingValue(ArgName),
+
+    /// A global argument was used where its placement does not allow it. See
+    /// [`Arg::placement`].
+    MisplacedArg {
+        /// The misplaced argument.
+        arg: ArgName,
+
+        /// Where the argument can be used.
+        placement: Placement,
+    },
+
+    /// None of the arguments of a group was used. See [`Command::require_one_of`].
+    MissingOneOf(Vec<ArgName>),
+
+    /// An argument was used without any of the subcommands it is valid with.
+    /// See [`Arg::only_with`].
+    RequiresSubcommand {
+        /// The misused argument.
+        arg: ArgName,
+
+        /// The subcommands the argument can be used with.
+        subcommands: Vec<&'static str>,
+    },
+
+    /// An argument that cannot be used more than once was used again.
+    /// See [`DuplicatePolicy::Error`].
+    Duplicate(ArgName),
+
+    /// A subcommand that does not exist in the command was used.
+    UnknownSubcommand(String),
+
+    /// No subcommand was given to a command that requires one.
+    /// It carries the names of the available subcommands.
+    MissingSubcommand(Vec<&'static str>),
+
+    /// The environment variable with extra arguments could not be split into arguments (e.g.
+    /// because of an unterminated quote), or its last argument is missing its value. It carries
+    /// the name of the variable.
+    /// See [`Command::args_env`].
+    InvalidEnvArgs(String),
+
+    /// The line given to [`Command::parse_from_str`] could not be split into arguments.
+    InvalidLine(SplitError),
+
+    /// Fewer positional values than required were given. See [`Command::rest_min`].
+    TooFewValues {
+        /// The name of the positional values.
+        name: &'static str,
+
+        /// The minimum number of values.
+        min: usize,
+    },
+
+    /// The parsed command was rejected by a validator. It carries the reason given by the
+    /// validator. See [`Command::validator`].
+    Validation(String),
+
+    /// The version flag was used (see [`Command::auto_version`]). It is not a mistake of the
+    /// user: it carries the version page, which should be printed to [`io::stdout`] before
+    /// exiting successfully, as [`exit_usage`] does.
+    DisplayVersion(String),
+
+    /// The help flag or the built-in `help` subcommand was used (see [`Command::help_flag`]).
+    /// Like [`ErrorKind::DisplayVersion`], it carries the requested page.
+    DisplayHelp(String),
+}
+
+impl ErrorKind {
+    /// Returns a code that identifies the kind of the error (e.g. `E_UNKNOWN_ARG`), so that the
+    /// programs that wrap the command line can tell the errors apart without reading their
+    /// messages, which can be localized (see [`Strings`]).
+    ///
+    /// The codes are stable: a code is never changed nor given to another kind.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// assert_eq!(ErrorKind::UnknownArg(arg!(--what)).code(), "E_UNKNOWN_ARG");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownArg(_) => "E_UNKNOWN_ARG",
+            Self::InvalidArg(_) => "E_BAD_ARG",
+            Self::NonUtf8Arg(_) => "E_NON_UTF8_ARG",
+            Self::TooManyDashes(_) => "E_TOO_MANY_DASHES",
+            Self::LoneDash => "E_LONE_DASH",
+            Self::TrailingDoubleDash => "E_TRAILING_DOUBLE_DASH",
+            Self::UnexpectedToken(_) => "E_UNEXPECTED_TOKEN",
+            Self::InvalidValue { .. } => "E_BAD_VALUE",
+            Self::MissingValue(_) => "E_MISSING_VALUE",
+            Self::MisplacedArg { .. } => "E_MISPLACED_ARG",
+            Self::MissingOneOf(_) => "E_MISSING_ONE_OF",
+            Self::RequiresSubcommand { .. } => "E_REQUIRES_SUBCOMMAND",
+            Self::Duplicate(_) => "E_DUPLICATE_ARG",
+            Self::UnknownSubcommand(_) => "E_UNKNOWN_SUBCOMMAND",
+            Self::MissingSubcommand(_) => "E_MISSING_SUBCOMMAND",
+            Self::InvalidEnvArgs(_) => "E_BAD_ENV_ARGS",
+            Self::InvalidLine(_) => "E_BAD_LINE",
+            Self::TooFewValues { .. } => "E_TOO_FEW_VALUES",
+            Self::Validation(_) => "E_VALIDATION",
+            Self::DisplayVersion(_) => "E_DISPLAY_VERSION",
+            Self::DisplayHelp(_) => "E_DISPLAY_HELP",
+        }
+    }
+}
+
+/// An error found while creating an invalid [`ArgName`].
+///
+/// See [`ArgName::try_short`], [`ArgName::try_long`] and [`ArgName::try_both`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArgNameError {
+    /// The long name is empty.
+    Empty,
+
+    /// The long name contains whitespace.
+    Whitespace(String),
+
+    /// The long name starts with a dash, which is added only on the command line.
+    LeadingDash(String),
+
+    /// The short name is a dash or whitespace.
+    InvalidShort(char),
+}
+
+impl fmt::Display for ArgNameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("The long name of an argument cannot be empty"),
+            Self::Whitespace(name) => {
+                write!(f, "The long name '{name}' cannot contain whitespace")
+            }
+            Self::LeadingDash(name) => {
+                write!(f, "The long name '{name}' cannot start with a dash")
+            }
+            Self::InvalidShort(name) => write!(f, "'{name}' cannot be a short name"),
+        }
+    }
+}
+
+impl error::Error for ArgNameError {}
+
+/// An accessor of [`Arg`] was used on an argument that carries another kind of value (e.g.
+/// [`Arg::expect_num`] on an [`ArgValue::String`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrongKind {
+    /// The argument the accessor was used on.
+    pub arg: ArgName,
+
+    /// The kinds of value the accessor reads.
+    pub expected: &'static [ValueKind],
+
+    /// The kind of value the argument carries.
+    pub found: ValueKind,
+}
+
+impl fmt::Display for WrongKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let expected: Vec<String> = self.expected.iter().map(ValueKind::to_string).collect();
+        write!(
+            f,
+            "'{}' carries a {}, not a {}",
+            self.arg,
+            self.found,
+            expected.join(" nor a ")
+        )
+    }
+}
+
+impl error::Error for WrongKind {}
+
+/// An error found while parsing the command line.
+///
+/// Its [`Display`](fmt::Display) implementation gives a short message, while
+/// [`ParseError::render`] gives a complete report that can be displayed to the user.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError(Box<Inner>);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Inner {
+    kind: ErrorKind,
+    more: Vec<ErrorKind>,
+    command: String,
+    try_help: Option<String>,
+    usage: String,
+    strings: Strings,
+    terminal: TerminalInfo,
+    formatter: Option<ErrorFormatter>,
+}
+
+// A function that checks the parsed command. See `Command::validator`.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub(crate) struct Validator(pub Arc<dyn Fn(&ParsedCommand) -> Result<(), String> + Send + Sync>);
+
+// A function that renders errors in place of `ParseError::render`. See `Command::error_formatter`.
+#[derive(Clone)]
+pub(crate) struct ErrorFormatter(pub Arc<dyn Fn(&ParseError) -> String + Send + Sync>);
+
+impl fmt::Debug for ErrorFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ErrorFormatter")
+    }
+}
+
+impl PartialEq for ErrorFormatter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ErrorKind, cmd: &Command) -> Self {
+        Self(Box::new(Inner {
+            kind,
+            more: Vec::new(),
+            command: help::fullname(cmd),
+            try_help: Self::try_help(cmd),
+            usage: help::usage(cmd),
+            strings: cmd.strings,
+            terminal: cmd.terminal_info(),
+            formatter: cmd.error_formatter.clone(),
+        }))
+    }
+
+    // Creates an error reporting every given error, which must not be empty.
+    pub(crate) fn collected(mut errors: Vec<ErrorKind>, cmd: &Command) -> Self {
+        let mut err = Self::new(errors.remove(0), cmd);
+        err.0.more = errors;
+        err
+    }
+
+    /// Returns what went wrong. If every error was collected (see
+    /// [`Command::collect_errors`]) it is the first one.
+    #[inline]
+    pub fn kind(&self) -> &ErrorKind {
+        &self.0.kind
+    }
+
+    /// Returns the stable code of [`ParseError::kind`] (see [`ErrorKind::code`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--num), value!(num), "Insert a number.")
+    ///     .parse_from_str("--num 6x")
+    ///     .err()
+    ///     .unwrap();
+    /// assert_eq!(err.code(), "E_BAD_VALUE");
+    /// ```
+    #[inline]
+    pub fn code(&self) -> &'static str {
+        self.0.kind.code()
+    }
+
+    /// Returns every error that was found, in order. Unless they are collected with
+    /// [`Command::collect_errors`] there is only [`ParseError::kind`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--num), value!(num), "Insert a number.")
+    ///     .collect_errors(true)
+    ///     .parse_from_str("--what --num 6x")
+    ///     .err()
+    ///     .unwrap();
+    /// let errors: Vec<&ErrorKind> = err.errors().collect();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0], &ErrorKind::UnknownArg(arg!(--what)));
+    /// ```
+    pub fn errors(&self) -> impl Iterator<Item = &ErrorKind> {
+        iter::once(&self.0.kind).chain(&self.0.more)
+    }
+
+    /// Returns the full name of the command being parsed when the error was found
+    /// (e.g. `myapp subcmd`).
+    #[inline]
+    pub fn command(&self) -> &str {
+        &self.0.command
+    }
+
+    /// Returns the usage of the command being parsed when the error was found, as shown in
+    /// [`ParseError::render`].
+    #[inline]
+    pub fn usage(&self) -> &str {
+        &self.0.usage
+    }
+
+    // Tells the user how to reach the help page, through the built-in help subcommand or the
+    // help flag, if they were not disabled.
+    fn try_help(cmd: &Command) -> Option<String> {
+        let command = help::fullname(cmd);
+        if cmd.builtin_help {
+            Some(match command.split_once(' ') {
+                Some((root, path)) => format!("{root} help {path}"),
+                None => format!("{command} help"),
+            })
+        } else {
+            let flag = match cmd.help_flag.as_ref()? {
+                ArgName::Both { long, .. } => ArgName::Long(long.clone()),
+                flag => flag.clone(),
+            };
+            Some(format!("{command} {flag}"))
+        }
+    }
+
+    // Builds the message of each error, one per line, each one after the given prefix.
+    fn messages(&self, prefix: &str, detailed: bool, color: bool) -> String {
+        self.errors()
+            .map(|kind| format!("{prefix}{}", self.message(kind, detailed, color)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Builds the error's message, highlighting the given values if needed.
+    #[cfg_attr(not(feature = "color"), allow(unused_variables))]
+    fn message(&self, kind: &ErrorKind, detailed: bool, color: bool) -> String {
+        let strings = &self.0.strings;
+        let highlight = |value: &dyn fmt::Display| {
+            #[cfg(feature = "color")]
+            if color {
+                return value.bold().to_string();
+            }
+            value.to_string()
+        };
+        match kind {
+            ErrorKind::UnknownArg(arg) => {
+                Strings::fill(strings.unknown_arg, &[("arg", &highlight(arg))])
+            }
+            ErrorKind::InvalidArg(token) => {
+                Strings::fill(strings.invalid_arg, &[("arg", &highlight(token))])
+            }
+            ErrorKind::NonUtf8Arg(token) => {
+                Strings::fill(strings.non_utf8_arg, &[("arg", &highlight(token))])
+            }
+            ErrorKind::TooManyDashes(token) => {
+                Strings::fill(strings.too_many_dashes, &[("arg", &highlight(token))])
+            }
+            ErrorKind::LoneDash => Strings::fill(strings.lone_dash, &[("arg", &highlight(&"-"))]),
+            ErrorKind::TrailingDoubleDash => {
+                Strings::fill(strings.trailing_double_dash, &[("arg", &highlight(&"--"))])
+            }
+            ErrorKind::UnexpectedToken(token) => {
+                Strings::fill(strings.unexpected_token, &[("token", &highlight(token))])
+            }
+            ErrorKind::InvalidValue { arg, value, reason } if detailed => Strings::fill(
+                strings.invalid_value_detailed,
+                &[
+                    ("arg", &highlight(arg)),
+                    ("value", &highlight(value)),
+                    ("reason", reason),
+                ],
+            ),
+            ErrorKind::InvalidValue { arg, reason, .. } => Strings::fill(
+                strings.invalid_value,
+                &[("arg", &highlight(arg)), ("reason", reason)],
+            ),
+            ErrorKind::MissingValue(arg) => {
+                Strings::fill(strings.missing_value, &[("arg", &highlight(arg))])
+            }
+            ErrorKind::MisplacedArg { arg, placement } => Strings::fill(
+                if *placement == Placement::After {
+                    strings.misplaced_after
+                } else {
+                    strings.misplaced_before
+                },
+                &[("arg", &highlight(arg))],
+            ),
+            ErrorKind::MissingOneOf(group) => {
+                let args: Vec<String> = group
+                    .iter()
+                    .map(|arg| format!("'{}'", highlight(arg)))
+                    .collect();
+                Strings::fill(strings.missing_one_of, &[("args", &args.join(", "))])
+            }
+            ErrorKind::RequiresSubcommand { arg, subcommands } => Strings::fill(
+                strings.requires_subcommand,
+                &[
+                    ("arg", &highlight(arg)),
+                    ("subcommands", &subcommands.join(", ")),
+                ],
+            ),
+            ErrorKind::Duplicate(arg) => {
+                Strings::fill(strings.duplicate, &[("arg", &highlight(arg))])
+            }
+            ErrorKind::UnknownSubcommand(name) => Strings::fill(
+                strings.unknown_subcommand,
+                &[("subcommand", &highlight(name))],
+            ),
+            ErrorKind::MissingSubcommand(subcommands) => Strings::fill(
+                strings.missing_subcommand,
+                &[
+                    ("command", &highlight(&self.command())),
+                    ("subcommands", &subcommands.join(", ")),
+                ],
+            ),
+            ErrorKind::InvalidEnvArgs(var) => {
+                Strings::fill(strings.invalid_env_args, &[("var", &highlight(var))])
+            }
+            ErrorKind::InvalidLine(error) => {
+                Strings::fill(strings.invalid_line, &[("reason", &error.to_string())])
+            }
+            ErrorKind::TooFewValues { name, min } => Strings::fill(
+                strings.too_few_values,
+                &[("name", &highlight(name)), ("min", &min.to_string())],
+            ),
+            ErrorKind::Validation(reason) => {
+                Strings::fill(strings.validation, &[("reason", reason)])
+            }
+            ErrorKind::DisplayVersion(page) | ErrorKind::DisplayHelp(page) => page.clone(),
+        }
+    }
+
+    /// Renders a complete error report, with the usage of the command and a hint on how to
+    /// get more information (unless the help was disabled with [`Command::disable_help`]).
+    /// The pages that were requested (see [`ParseError::is_page`]) are returned as they are.
+    ///
+    /// If the command has an error formatter (see [`Command::error_formatter`]) its output is
+    /// returned instead. Without the `color` feature it is never colored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--num), value!(num), "Insert a number.")
+    ///     .parse_from(vec!["myapp".into(), "--num".into(), "6x".into()])
+    ///     .err()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     err.render(false),
+    ///     "error: invalid value '6x' for '--num': must be a valid number: \
+    ///      invalid digit found in string
+    ///
+    /// USAGE:
+    /// \tmyapp [--num <NUM>]
+    ///
+    /// For more information, try 'myapp help'."
+    /// );
+    /// ```
+    #[cfg_attr(not(feature = "color"), allow(unused_variables))]
+    pub fn render(&self, color: bool) -> String {
+        if let Some(page) = self.page() {
+            return page.into();
+        }
+        if let Some(formatter) = &self.0.formatter {
+            return (formatter.0)(self);
+        }
+        #[cfg(feature = "color")]
+        if color {
+            return self.render_color();
+        }
+        let strings = &self.0.strings;
+        format!(
+            "{messages}\n\n{usage}{try_help}",
+            messages = self.messages(&format!("{} ", strings.error), true, false),
+            usage = self.0.usage,
+            try_help = self.0.try_help.as_ref().map_or("".into(), |help| format!(
+                "\n\n{}",
+                Strings::fill(strings.try_help, &[("help", help)])
+            ))
+        )
+    }
+
+    #[cfg(feature = "color")]
+    fn render_color(&self) -> String {
+        let strings = &self.0.strings;
+        format!(
+            "{messages}\n\n{usage}{try_help}",
+            messages = self.messages(&format!("{} ", strings.error.red().bold()), true, true),
+            usage = self.0.usage,
+            try_help = self.0.try_help.as_ref().map_or("".into(), |help| format!(
+                "\n\n{}",
+                Strings::fill(strings.try_help, &[("help", &help.bold().to_string())])
+            ))
+        )
+    }
+
+    /// Writes the rendered error (see [`ParseError::render`]) to the given writer, usually
+    /// [`io::stderr`]. It is colored if the command's terminal supports it (see
+    /// [`Command::terminal`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let result = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--num), value!(num), "Insert a number.")
+    ///     .color(false)
+    ///     .parse_from_str("--num 6x");
+    /// if let Err(err) = result {
+    ///     let mut buf = Vec::new();
+    ///     err.print(&mut buf).unwrap();
+    ///     assert!(String::from_utf8(buf).unwrap().starts_with("error: invalid value"));
+    ///
+    ///     err.print(&mut std::io::stderr()).unwrap();
+    /// }
+    /// ```
+    pub fn print(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "{}", self.render(self.0.terminal.color))
+    }
+
+    /// Returns whether the user asked for a page (e.g. with the help flag) instead of making
+    /// a mistake. The page should be printed to [`io::stdout`] and the program should exit
+    /// successfully, as [`exit_usage`] does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .version("1.0.0")
+    ///     .auto_version(true)
+    ///     .color(false)
+    ///     .parse_from_str("--version")
+    ///     .err()
+    ///     .unwrap();
+    /// assert!(err.is_page());
+    /// assert_eq!(err.kind(), &ErrorKind::DisplayVersion("myapp 1.0.0".into()));
+    /// assert_eq!(err.render(false), "myapp 1.0.0");
+    /// ```
+    #[inline]
+    pub fn is_page(&self) -> bool {
+        self.page().is_some()
+    }
+
+    // Returns the requested page, if it is one.
+    fn page(&self) -> Option<&str> {
+        match &self.0.kind {
+            ErrorKind::DisplayVersion(page) | ErrorKind::DisplayHelp(page) => Some(page),
+            _ => None,
+        }
+    }
+
+    /// Returns the terminal the error is rendered for (see [`Command::terminal`]), which lets
+    /// an error formatter make the same decisions as the built-in renderer.
+    #[inline]
+    pub fn terminal(&self) -> TerminalInfo {
+        self.0.terminal
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.messages("", false, false))
+    }
+}
+
+impl error::Error for ParseError {}
@@ -0,0 +1,82 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{io, process};
+
+use crate::ParseError;
+
+// Conventional exit codes, from the BSD `sysexits.h`.
+
+/// Successful termination.
+pub const EX_OK: i32 = 0;
+
+/// The command was used incorrectly (e.g. an unknown argument or an invalid value).
+pub const EX_USAGE: i32 = 64;
+
+/// The input data was incorrect.
+pub const EX_DATAERR: i32 = 65;
+
+/// An input file did not exist or was not readable.
+pub const EX_NOINPUT: i32 = 66;
+
+/// A service is unavailable (e.g. a support program or file does not exist).
+pub const EX_UNAVAILABLE: i32 = 69;
+
+/// An internal software error was detected.
+pub const EX_SOFTWARE: i32 = 70;
+
+/// An error occurred while doing I/O on some file.
+pub const EX_IOERR: i32 = 74;
+
+/// A temporary failure: the user is invited to retry.
+pub const EX_TEMPFAIL: i32 = 75;
+
+/// The user does not have sufficient permissions to perform the operation.
+pub const EX_NOPERM: i32 = 77;
+
+/// Something was found in an unconfigured or misconfigured state.
+pub const EX_CONFIG: i32 = 78;
+
+/// Prints the rendered error (see [`ParseError::print`]), which ends with the usage of the
+/// command, to [`io::stderr`] and terminates the program with [`EX_USAGE`].
+///
+/// The pages requested by the user (see [`ParseError::is_page`]) are printed to [`io::stdout`]
+/// instead, and the program terminates with [`EX_OK`].
+///
+/// Using it for every parsing error makes the programs behave the same way on bad input.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tiny_args::*;
+/// let parsed = Command::create("myapp", "This is my cool app.")
+///     .arg(arg!(--num), value!(num), "Insert a number.")
+///     .parse()
+///     .unwrap_or_else(|err| exit_usage(err));
+/// ```
+pub fn exit_usage(err: ParseError) -> ! {
+    if err.is_page() {
+        let _ = err.print(&mut io::stdout());
+        process::exit(EX_OK)
+    }
+    let _ = err.print(&mut io::stderr());
+    process::exit(EX_USAGE)
+}
@@ -0,0 +1,229 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{fmt, path::Path, str::FromStr};
+
+use crate::ArgValueKind;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    // `?`, any character but a slash.
+    Any,
+    // `*`, any sequence of characters without slashes.
+    Star,
+    // `**`, any sequence of whole path components. If it is followed by a slash the slash is
+    // part of it, so that `**/a` matches `a` too.
+    Globstar {
+        slash: bool,
+    },
+    // `[...]` or `[!...]`, a character in (or not in) the ranges.
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+/// A glob pattern (e.g. `src/**/*.rs`), checked when it is parsed. Requires the `glob` feature.
+///
+/// - `?` matches any character but `/`.
+/// - `*` matches any sequence of characters without `/`.
+/// - `**` matches any sequence of path components, even none (`**/a` matches `a` and `b/c/a`).
+///   It must be a whole component.
+/// - `[abc]`, `[a-z]` and `[!a-z]` match a character in (or not in) the set. A `]` right after
+///   the opening bracket is part of the set.
+/// - `\` escapes the next character.
+///
+/// It can be used as the value of an argument with [`ArgValue::Other`](crate::ArgValue::Other),
+/// holding an optional default, and read with [`Arg::glob`](crate::Arg::glob).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = || {
+///     Command::create("backup", "Backs up the files.").add_arg(
+///         Arg::new(arg!(--exclude), ArgValue::Other(Box::new(None::<Glob>)), "Skip the files.")
+///     )
+/// };
+/// let parsed = cmd().parse_from_str("--exclude '**/*.tmp'").unwrap();
+/// let exclude = parsed.args.try_get(arg!(--exclude)).unwrap().glob().unwrap();
+/// assert!(exclude.matches("cache/a.tmp"));
+/// assert!(exclude.matches("b.tmp"));
+/// assert!(!exclude.matches("b.txt"));
+/// assert!(cmd().parse_from_str("--exclude '[a-'").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Glob {
+    pattern: String,
+    tokens: Vec<Token>,
+}
+
+impl Glob {
+    /// Returns the pattern as it was given.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Checks whether or not the whole text matches the pattern, `/` being the path separator.
+    pub fn matches(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        let len = chars.len();
+        // matched[j] tells whether the tokens after the current one match the text from `j`.
+        let mut matched = vec![false; len + 1];
+        matched[len] = true;
+        for token in self.tokens.iter().rev() {
+            let mut current = vec![false; len + 1];
+            for j in (0..=len).rev() {
+                let c = chars.get(j).copied();
+                let single = |ok: bool| ok && c.is_some_and(|c| c != '/') && matched[j + 1];
+                current[j] = match token {
+                    Token::Literal(literal) => c == Some(*literal) && matched[j + 1],
+                    Token::Any => single(true),
+                    Token::Class { negated, ranges } => single(c.is_some_and(|c| {
+                        ranges.iter().any(|&(a, b)| (a..=b).contains(&c)) != *negated
+                    })),
+                    Token::Star => matched[j] || (c.is_some_and(|c| c != '/') && current[j + 1]),
+                    Token::Globstar { slash: false } => {
+                        matched[j] || (c.is_some() && current[j + 1])
+                    }
+                    Token::Globstar { slash: true } => {
+                        matched[j] || (j..len).any(|k| chars[k] == '/' && matched[k + 1])
+                    }
+                };
+            }
+            matched = current;
+        }
+        matched[0]
+    }
+
+    /// Checks whether or not the path matches the pattern. On Windows backslashes are
+    /// separators too.
+    pub fn matches_path(&self, path: &Path) -> bool {
+        let text = path.to_string_lossy();
+        if cfg!(windows) {
+            self.matches(&text.replace('\\', "/"))
+        } else {
+            self.matches(&text)
+        }
+    }
+}
+
+// Parses the inside of a character class, after the opening bracket.
+fn class(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<Token, String> {
+    let negated = chars.next_if_eq(&'!').is_some();
+    let mut ranges = Vec::new();
+    let mut first = true;
+    loop {
+        let start = match chars.next() {
+            None => return Err("unclosed '['".into()),
+            Some(']') if !first => return Ok(Token::Class { negated, ranges }),
+            Some('\\') => chars.next().ok_or("unclosed '['")?,
+            Some(c) => c,
+        };
+        first = false;
+        let mut end = start;
+        if chars.peek() == Some(&'-') {
+            let mut lookahead = chars.clone();
+            lookahead.next();
+            if lookahead.peek().is_some_and(|&c| c != ']') {
+                chars.next();
+                end = match chars.next() {
+                    Some('\\') => chars.next().ok_or("unclosed '['")?,
+                    Some(c) => c,
+                    None => return Err("unclosed '['".into()),
+                };
+                if end < start {
+                    return Err(format!("invalid range '{start}-{end}'"));
+                }
+            }
+        }
+        ranges.push((start, end));
+    }
+}
+
+impl FromStr for Glob {
+    type Err = String;
+
+    /// Parses a pattern. On failure it returns what the pattern must be.
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| format!("a valid glob pattern: {reason}");
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars().peekable();
+        let mut previous = None;
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '?' => Token::Any,
+                '*' if chars.next_if_eq(&'*').is_some() => {
+                    let whole = matches!(previous, None | Some('/'))
+                        && matches!(chars.peek(), None | Some('/'));
+                    if !whole {
+                        return Err(invalid("'**' must be a whole path component".into()));
+                    }
+                    Token::Globstar {
+                        slash: chars.next_if_eq(&'/').is_some(),
+                    }
+                }
+                '*' => Token::Star,
+                '[' => class(&mut chars).map_err(invalid)?,
+                '\\' => match chars.next() {
+                    Some(c) => Token::Literal(c),
+                    None => return Err(invalid("trailing '\\'".into())),
+                },
+                c => Token::Literal(c),
+            };
+            previous = match token {
+                Token::Globstar { slash: true } => Some('/'),
+                _ => Some(c),
+            };
+            tokens.push(token);
+        }
+        Ok(Self {
+            pattern: pattern.into(),
+            tokens,
+        })
+    }
+}
+
+impl fmt::Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+impl ArgValueKind for Option<Glob> {
+    fn parse(&mut self, input: &str) -> Result<(), String> {
+        *self = Some(input.parse()?);
+        Ok(())
+    }
+
+    fn type_name(&self) -> &str {
+        "glob"
+    }
+
+    fn default_display(&self) -> Option<String> {
+        self.as_ref().map(Glob::to_string)
+    }
+
+    fn clone_box(&self) -> Box<dyn ArgValueKind> {
+        Box::new(self.clone())
+    }
+}
@@ -19,72 +19,567 @@
 //
 // Email: hex0x0000@protonmail.com
 
+//! Help pages rendered with custom options.
+//!
+//! The help page is usually read from [`ParsedCommand::help`] or printed with
+//! [`Command::print_help`]. This module renders it into any buffer instead, e.g. to show it in a
+//! user interface that is not a terminal.
+
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+};
+
 use crate::*;
+#[cfg(feature = "color")]
 use owo_colors::OwoColorize;
 
-fn tabs(len: usize) -> &'static str {
-    match len / 8 {
-        0 => "\t\t",
-        1 => "\t",
-        _ => "\n\t\t\t",
+/// How a help page is rendered. See [`render`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::help::HelpOptions;
+/// let options = HelpOptions {
+///     width: Some(60),
+///     ..HelpOptions::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HelpOptions {
+    /// The columns the lines are wrapped at, if any. Tabs are expanded to multiples of 8
+    /// columns. By default lines are not wrapped.
+    pub width: Option<usize>,
+
+    /// Whether or not the page is colored with ANSI escape sequences. It has no effect without
+    /// the `color` feature. By default it is not colored.
+    pub color: bool,
+
+    /// Whether or not the long descriptions of the arguments are used (see
+    /// [`Arg::long_description`]), like in [`ParsedCommand::long_help`]. By default they are
+    /// not.
+    pub long: bool,
+
+    /// The least public arguments and subcommands that are shown (see [`Visibility`]). By
+    /// default only the public ones are, since rendered pages are usually published.
+    pub visibility: Visibility,
+}
+
+impl From<TerminalInfo> for HelpOptions {
+    /// Renders the short page for the given terminal (see [`Command::terminal`]).
+    fn from(terminal: TerminalInfo) -> Self {
+        Self {
+            width: terminal.width,
+            color: terminal.color,
+            long: false,
+            visibility: Visibility::Internal,
+        }
+    }
+}
+
+// A function that returns the text at the end of the help page. See `Command::epilogue_with`.
+#[derive(Clone)]
+pub(crate) struct Epilogue(pub Arc<dyn Fn() -> String + Send + Sync>);
+
+// The text at the end of the page, followed by an empty line, if any.
+fn epilogue(cmd: &Command) -> String {
+    match cmd.epilogue.as_ref().map(|epilogue| (epilogue.0)()) {
+        Some(text) if !text.is_empty() => format!("{}\n\n", escape(text.trim_end(), true)),
+        _ => "".into(),
+    }
+}
+
+// The widest name that shares its line with the description. Wider names are on their own line,
+// so that a single long name does not push all the descriptions to the right.
+const MAX_NAME: usize = 24;
+
+// The columns of the sections that list names and descriptions, shared by all the sections of
+// the page. The names are indented by a tab and the descriptions are aligned after the widest
+// name, as are the lines that continue them.
+#[derive(Clone, Copy)]
+struct Layout {
+    column: usize,
+}
+
+impl Layout {
+    fn new(cmd: &Command, shown: Visibility) -> Self {
+        let args = shown_args(cmd, shown).map(argname_width);
+        let rest = cmd.rest.map(|r| 5 + width::width(&escape(r.name, false)));
+        let subcommands = shown_subcommands(cmd, shown).map(|s| width::width(s.name));
+        let plugins = plugins(cmd).map(|p| width::width(&escape(&p.name, false)));
+        let help = builtin_help(cmd).then_some(width::width("help"));
+        let widest = args
+            .chain(rest)
+            .chain(subcommands)
+            .chain(plugins)
+            .chain(help)
+            .filter(|&w| w <= MAX_NAME)
+            .max();
+        Layout {
+            column: 8 + widest.unwrap_or(0) + 2,
+        }
+    }
+
+    // What follows the name to reach the description column.
+    fn gap(self, name: &str) -> Gap {
+        let width = width::width(name);
+        if width > MAX_NAME {
+            Gap::Break(self.column - 8)
+        } else {
+            Gap::Spaces(self.column - 8 - width)
+        }
+    }
+
+    // The indentation of the lines beneath a description.
+    fn indent(self) -> Gap {
+        Gap::Indent(self.column - 8)
     }
 }
 
+// The whitespace that reaches the description column, written without allocating.
+#[derive(Clone, Copy)]
+enum Gap {
+    // The spaces after a name.
+    Spaces(usize),
+    // A line break after a name that is too wide, then the indentation.
+    Break(usize),
+    // A tab and the spaces that reach the column from the start of a line.
+    Indent(usize),
+}
+
+impl fmt::Display for Gap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Gap::Spaces(spaces) => write!(f, "{:spaces$}", ""),
+            Gap::Break(spaces) => write!(f, "\n\t{:spaces$}", ""),
+            Gap::Indent(spaces) => write!(f, "\t{:spaces$}", ""),
+        }
+    }
+}
+
+// Escapes control characters (e.g. ANSI escape sequences) so that the text cannot break the
+// layout of the page nor control the terminal. Line breaks are kept only if `lines` is set,
+// otherwise they are replaced with spaces like tabs. Text without control characters is
+// borrowed, since it is the most common.
+pub(crate) fn escape(text: &str, lines: bool) -> Cow<'_, str> {
+    if text
+        .chars()
+        .all(|c| !c.is_control() || (lines && c == '\n'))
+    {
+        return Cow::Borrowed(text);
+    }
+    let mut buf = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\n' if lines => buf.push('\n'),
+            '\n' | '\r' | '\t' => buf.push(' '),
+            c if c.is_control() => buf.extend(c.escape_default()),
+            c => buf.push(c),
+        }
+    }
+    Cow::Owned(buf)
+}
+
+// Like `escape`, but it gives the text back if it has nothing to escape.
+fn escape_owned(text: String) -> String {
+    if let Cow::Owned(escaped) = escape(&text, false) {
+        return escaped;
+    }
+    text
+}
+
 fn license(cmd: &Command) -> String {
     if let Some(license) = &cmd.license {
-        format!("Licensed under {license}")
+        format!("{} {license}", cmd.strings.licensed_under)
     } else {
         "".into()
     }
 }
 
-fn subcommands_normal(cmd: &Command) -> String {
-    if cmd.subcommands.is_empty() {
-        return "".into();
-    }
-    let mut buf = String::from("SUBCOMMANDS:\n");
-    for subcmd in &cmd.subcommands {
+// The homepage and the repository, one per line.
+fn links(cmd: &Command) -> String {
+    let mut buf = String::new();
+    if let Some(homepage) = cmd.homepage {
         buf.push_str(&format!(
-            "\t{name}{tabs}{description}\n",
-            name = subcmd.name,
-            description = subcmd.description,
-            tabs = tabs(subcmd.name.len())
+            "{}: {}\n",
+            cmd.strings.homepage,
+            escape(homepage, false)
         ));
     }
-    buf.push('\n');
+    if let Some(repository) = cmd.repository {
+        let repository = escape(repository, false);
+        buf.push_str(&format!("{}: {repository}\n", cmd.strings.repository));
+    }
     buf
 }
 
-fn args_normal(cmd: &Command) -> String {
-    if cmd.args.args.is_empty() {
+fn default(arg: &Arg) -> Option<String> {
+    match &arg.argvalue {
+        // Counters always start from a number, which is not worth showing.
+        ArgValue::Count(_) => None,
+        argvalue => argvalue.display(),
+    }
+}
+
+fn description(arg: &Arg, long: bool) -> Cow<'static, str> {
+    if long {
+        escape(arg.long_description.unwrap_or(arg.description), false)
+    } else {
+        escape(arg.description, false)
+    }
+}
+
+// The width of the name written by `argname`, computed without building it.
+fn argname_width(arg: &Arg) -> usize {
+    let mut buf = [0; 4];
+    let (short, long) = match &arg.argname {
+        ArgName::Short(short) => (Some(*short), None),
+        ArgName::Long(long) => (None, Some(long)),
+        ArgName::Both { short, long } => (Some(*short), Some(long)),
+    };
+    let short = short.map_or(0, |short| 1 + width::width(short.encode_utf8(&mut buf)));
+    let long = long.map_or(0, |long| {
+        let negatable = if arg.negatable { "[no-]".len() } else { 0 };
+        2 + negatable + width::width(long)
+    });
+    let separator = if short > 0 && long > 0 { 2 } else { 0 };
+    let value = arg
+        .value_name
+        .map_or(0, |name| 3 + width::width(&escape(name, false)));
+    short + separator + long + value
+}
+
+fn argname(arg: &Arg) -> String {
+    let mut name = arg.argname.to_string();
+    if arg.negatable {
+        name = name.replacen("--", "--[no-]", 1);
+    }
+    if let Some(value_name) = arg.value_name {
+        escape_owned(format!("{name} <{value_name}>"))
+    } else {
+        name
+    }
+}
+
+fn constraint(arg: &Arg, strings: &Strings) -> Option<String> {
+    match &arg.argvalue {
+        ArgValue::Choice(choices, _) => {
+            Some(format!("{}: {}", strings.possible, choices.join(", ")))
+        }
+        ArgValue::Range(range, _) => Some(format!(
+            "{}: {}-{}",
+            strings.range,
+            range.start(),
+            range.end()
+        )),
+        _ => None,
+    }
+}
+
+// The example of the argument on its own line, beneath the description.
+fn example(arg: &Arg, strings: &Strings, layout: Layout) -> Option<String> {
+    let example = escape(arg.example?, false);
+    Some(format!(
+        "{}{}: {example}\n",
+        layout.indent(),
+        strings.example
+    ))
+}
+
+fn extras(arg: &Arg, strings: &Strings) -> String {
+    let mut buf = String::new();
+    if arg.deprecated.is_some() {
+        let _ = write!(buf, " [{}]", strings.deprecated);
+    }
+    if let Some(constraint) = constraint(arg, strings) {
+        let _ = write!(buf, " [{constraint}]");
+    }
+    if let Some(default) = default(arg).filter(|_| !arg.sensitive) {
+        let _ = write!(buf, " [{}: {default}]", strings.default);
+    }
+    escape_owned(buf)
+}
+
+// The built-in help subcommand is available only if it was not disabled and if the command did
+// not define its own.
+pub(crate) fn builtin_help(cmd: &Command) -> bool {
+    cmd.builtin_help && !cmd.subcommands.iter().any(|s| s.name == "help")
+}
+
+// The plugins that are not shadowed by a subcommand.
+fn plugins(cmd: &Command) -> impl Iterator<Item = &Plugin> {
+    cmd.plugins
+        .iter()
+        .filter(|plugin| !cmd.subcommands.iter().any(|s| s.name == plugin.name))
+}
+
+// The entries of a section grouped by the component that provides them, beginning with those
+// that have no provider.
+struct Groups(Vec<(Option<&'static str>, String)>);
+
+impl Groups {
+    fn new() -> Self {
+        Groups(vec![(None, String::new())])
+    }
+
+    fn push(&mut self, provider: Option<&'static str>, entry: fmt::Arguments) {
+        let i = match self.0.iter().position(|(p, _)| *p == provider) {
+            Some(i) => i,
+            None => {
+                self.0.push((provider, String::new()));
+                self.0.len() - 1
+            }
+        };
+        let _ = self.0[i].1.write_fmt(entry);
+    }
+
+    // Joins the groups, giving each one a heading only if there are more of them.
+    fn finish(mut self, core: &str, heading: impl Fn(String) -> String) -> String {
+        self.0.retain(|(_, buf)| !buf.is_empty());
+        if self.0.len() < 2 {
+            return self.0.into_iter().map(|(_, buf)| buf).collect();
+        }
+        let groups: Vec<String> = self
+            .0
+            .into_iter()
+            .map(|(provider, buf)| {
+                let name = escape(provider.unwrap_or(core), false).into_owned();
+                format!("\t{}:\n{buf}", heading(name))
+            })
+            .collect();
+        groups.join("\n")
+    }
+}
+
+// The arguments shown on a page that shows the given visibility.
+fn shown_args(cmd: &Command, shown: Visibility) -> impl Iterator<Item = &Arg> {
+    cmd.args
+        .args
+        .iter()
+        .filter(move |arg| arg.visibility <= shown)
+}
+
+// The subcommands shown on a page that shows the given visibility.
+fn shown_subcommands(cmd: &Command, shown: Visibility) -> impl Iterator<Item = &Command> {
+    cmd.subcommands
+        .iter()
+        .filter(move |s| s.visibility <= shown)
+}
+
+fn has_subcommands(cmd: &Command, shown: Visibility) -> bool {
+    shown_subcommands(cmd, shown).next().is_some() || !cmd.plugins.is_empty()
+}
+
+fn subcommands_normal(cmd: &Command, layout: Layout, shown: Visibility) -> String {
+    if !has_subcommands(cmd, shown) {
         return "".into();
     }
-    let mut buf = String::from("ARGS:\n");
-    for arg in &cmd.args.args {
-        let name = arg.argname.to_string();
-        buf.push_str(&format!(
-            "\t{name}{tabs}{description}\n",
-            description = arg.description,
-            tabs = tabs(name.len())
-        ));
+    let mut groups = Groups::new();
+    for subcmd in shown_subcommands(cmd, shown) {
+        groups.push(
+            subcmd.provider,
+            format_args!(
+                "\t{name}{gap}{description}\n",
+                name = subcmd.name,
+                description = escape(subcmd.description, false),
+                gap = layout.gap(subcmd.name)
+            ),
+        );
+    }
+    for plugin in plugins(cmd) {
+        groups.push(
+            None,
+            format_args!(
+                "\t{name}{gap}{description}\n",
+                name = escape(&plugin.name, false),
+                description = cmd.strings.plugin,
+                gap = layout.gap(&escape(&plugin.name, false))
+            ),
+        );
+    }
+    if builtin_help(cmd) {
+        groups.push(
+            None,
+            format_args!(
+                "\thelp{}{}\n",
+                layout.gap("help"),
+                cmd.strings.help_subcommand
+            ),
+        );
+    }
+    let groups = groups.finish(cmd.strings.core, |name| name);
+    format!("{}\n{groups}\n", cmd.strings.subcommands)
+}
+
+fn rest(rest: &Rest) -> String {
+    if rest.min > 0 {
+        format!("<{}>...", escape(rest.name, false))
+    } else {
+        format!("[{}]...", escape(rest.name, false))
+    }
+}
+
+// How the argument is written in the usage: optional arguments are in brackets, followed by
+// a placeholder of their value and by `...` if they can be repeated.
+fn synopsis(arg: &Arg, optional: bool) -> String {
+    let mut buf = String::with_capacity(32);
+    if optional {
+        buf.push('[');
+    }
+    let _ = match &arg.argname {
+        ArgName::Short(short) => write!(buf, "-{short}"),
+        ArgName::Long(long) | ArgName::Both { long, .. } if arg.negatable => {
+            write!(buf, "--[no-]{long}")
+        }
+        ArgName::Long(long) | ArgName::Both { long, .. } => write!(buf, "--{long}"),
+    };
+    let value: Cow<str> = match (&arg.argvalue, arg.value_name, &arg.argname) {
+        (ArgValue::Flag | ArgValue::Count(_), _, _) => "".into(),
+        (_, Some(value_name), _) => format!("<{value_name}>").into(),
+        (ArgValue::Choice(choices, _), None, _) => format!("<{}>", choices.join("|")).into(),
+        (_, None, ArgName::Short(_)) => "<VALUE>".into(),
+        (_, None, ArgName::Long(long) | ArgName::Both { long, .. }) => {
+            format!("<{}>", long.to_uppercase()).into()
+        }
+    };
+    if !value.is_empty() {
+        // Lists that take more values each time show them all (e.g. `--map <MAP> <MAP>`), with
+        // the optional ones in brackets.
+        let (required, optional) = match &arg.num_values {
+            Some(range) => (*range.start(), range.end() - range.start()),
+            None => (1, 0),
+        };
+        for _ in 0..required {
+            let _ = write!(buf, " {value}");
+        }
+        let _ = match optional {
+            0 => Ok(()),
+            1 => write!(buf, " [{value}]"),
+            _ => write!(buf, " [{value}]..."),
+        };
+        // Lists with a delimiter show it too (e.g. `--tag <TAG>[,<TAG>]...`).
+        if let Some(delimiter) = arg.delimiter {
+            let _ = write!(buf, "[{delimiter}{value}]...");
+        }
+    }
+    if optional {
+        buf.push(']');
+    }
+    if matches!(arg.argvalue, ArgValue::Count(_)) || arg.duplicates == DuplicatePolicy::Append {
+        buf.push_str("...");
+    }
+    escape_owned(buf)
+}
+
+// The ways the command can be used, without its name.
+fn usage_lines(cmd: &Command, shown: Visibility) -> Vec<String> {
+    let mut args = String::new();
+    for arg in shown_args(cmd, shown) {
+        // The arguments of a required group are shown together where the first one is.
+        let group = cmd
+            .required_groups
+            .iter()
+            .find(|group| group.contains(&arg.argname))
+            .map(|group| {
+                group
+                    .iter()
+                    .filter_map(|argname| cmd.args.try_get(argname.clone()))
+                    .filter(|arg| arg.visibility <= shown)
+                    .collect::<Vec<_>>()
+            });
+        match group {
+            Some(group) if group[0].argname == arg.argname => {
+                let alternatives: Vec<String> =
+                    group.iter().map(|arg| synopsis(arg, false)).collect();
+                args.push_str(&format!(" ({})", alternatives.join(" | ")));
+            }
+            Some(_) => (),
+            None => {
+                args.push(' ');
+                args.push_str(&synopsis(arg, true));
+            }
+        }
+    }
+    let mut lines = Vec::new();
+    let subcommands = has_subcommands(cmd, shown);
+    if !subcommands || !cmd.subcommand_required {
+        if let Some(rest) = &cmd.rest {
+            lines.push(format!("{args} {}", self::rest(rest)));
+        } else if !subcommands || !args.is_empty() {
+            lines.push(args.clone());
+        }
+    }
+    if subcommands {
+        let subcommand = if cmd.subcommand_required {
+            "<SUBCOMMAND>"
+        } else {
+            "[SUBCOMMAND]"
+        };
+        lines.push(format!("{args} {subcommand}"));
+    }
+    lines
+}
+
+fn args_normal(cmd: &Command, long: bool, layout: Layout, shown: Visibility) -> String {
+    if shown_args(cmd, shown).next().is_none() && cmd.rest.is_none() {
+        return "".into();
+    }
+    let mut groups = Groups::new();
+    for arg in shown_args(cmd, shown) {
+        let name = argname(arg);
+        groups.push(
+            arg.provider,
+            format_args!(
+                "\t{name}{gap}{description}{extras}\n{example}",
+                description = description(arg, long),
+                extras = extras(arg, &cmd.strings),
+                gap = layout.gap(&name),
+                example = example(arg, &cmd.strings, layout).unwrap_or_default()
+            ),
+        );
+    }
+    if let Some(rest) = &cmd.rest {
+        let name = self::rest(rest);
+        groups.push(
+            None,
+            format_args!(
+                "\t{name}{gap}{description}\n",
+                description = escape(rest.description, false),
+                gap = layout.gap(&name)
+            ),
+        );
+    }
+    let groups = groups.finish(cmd.strings.core, |name| name);
+    format!("{}\n{groups}", cmd.strings.args)
+}
+
+fn usage_normal(cmd: &Command, fullname: &str, shown: Visibility) -> String {
+    let mut buf = String::from(cmd.strings.usage);
+    for line in usage_lines(cmd, shown) {
+        buf.push_str(&format!("\n\t{fullname}{line}"))
     }
     buf
 }
 
-fn usage_normal(cmd: &Command, fullname: &str) -> String {
-    let mut buf = String::from("USAGE:");
-    if !cmd.args.args.is_empty() {
-        buf.push_str(&format!("\n\t{fullname} [ARGS]"))
+fn examples_normal(cmd: &Command) -> String {
+    if cmd.examples.is_empty() {
+        return "".into();
     }
-    if !cmd.subcommands.is_empty() {
-        buf.push_str(&format!("\n\t{fullname} [SUBCOMMAND] [ARGS]"))
+    let mut buf = format!("{}\n", cmd.strings.examples);
+    for (line, description) in &cmd.examples {
+        buf.push_str(&format!("\t{}\n", escape(line, false)));
+        if !description.is_empty() {
+            buf.push_str(&format!("\t\t{}\n", escape(description, false)));
+        }
     }
+    buf.push('\n');
     buf
 }
 
-fn create_normal(cmd: &Command) -> String {
-    let fullname = format!("{} {}", cmd.parents.join(" "), cmd.name);
-    let fullname = fullname.trim();
+fn create_normal(cmd: &Command, long: bool, shown: Visibility) -> String {
+    let fullname = fullname(cmd);
+    let layout = Layout::new(cmd, shown);
+    let fullname = fullname.as_str();
     format!(
         "{fullname} {version}
 {author}{description}
@@ -92,72 +587,132 @@ fn create_normal(cmd: &Command) -> String {
 {usage}
 
 {args}
-{subcommands}{license}",
+{subcommands}{examples}{epilogue}{links}{license}",
         fullname = fullname,
-        description = cmd.description,
+        description = escape(cmd.description, true),
         version = cmd.version.unwrap_or(""),
         author = cmd.author.map(|a| format!("{a}\n")).unwrap_or("".into()),
-        usage = usage_normal(cmd, fullname),
-        args = args_normal(cmd),
-        subcommands = subcommands_normal(cmd),
+        usage = usage_normal(cmd, fullname, shown),
+        args = args_normal(cmd, long, layout, shown),
+        subcommands = subcommands_normal(cmd, layout, shown),
+        examples = examples_normal(cmd),
+        epilogue = epilogue(cmd),
+        links = links(cmd),
         license = license(cmd)
     )
 }
 
-fn subcommands_color(cmd: &Command) -> String {
-    if cmd.subcommands.is_empty() {
+#[cfg(feature = "color")]
+fn subcommands_color(cmd: &Command, layout: Layout, shown: Visibility) -> String {
+    if !has_subcommands(cmd, shown) {
         return "".into();
     }
-    let mut buf: String = format!("{}", "SUBCOMMANDS:\n".bold().underline());
-    for subcmd in &cmd.subcommands {
-        buf.push_str(&format!(
-            "\t{name}{tabs}{description}\n",
-            name = subcmd.name.bold(),
-            description = subcmd.description,
-            tabs = tabs(subcmd.name.len())
-        ));
+    let mut groups = Groups::new();
+    for subcmd in shown_subcommands(cmd, shown) {
+        groups.push(
+            subcmd.provider,
+            format_args!(
+                "\t{name}{gap}{description}\n",
+                name = subcmd.name.bold(),
+                description = escape(subcmd.description, false),
+                gap = layout.gap(subcmd.name)
+            ),
+        );
     }
-    buf.push('\n');
-    buf
+    for plugin in plugins(cmd) {
+        groups.push(
+            None,
+            format_args!(
+                "\t{name}{gap}{description}\n",
+                name = escape(&plugin.name, false).bold(),
+                description = cmd.strings.plugin,
+                gap = layout.gap(&escape(&plugin.name, false))
+            ),
+        );
+    }
+    if builtin_help(cmd) {
+        groups.push(
+            None,
+            format_args!(
+                "\t{}{}{}\n",
+                "help".bold(),
+                layout.gap("help"),
+                cmd.strings.help_subcommand
+            ),
+        );
+    }
+    let groups = groups.finish(cmd.strings.core, |name| name.underline().to_string());
+    format!("{}\n{groups}\n", cmd.strings.subcommands.bold().underline())
 }
 
-fn args_color(cmd: &Command) -> String {
-    if cmd.args.args.is_empty() {
+#[cfg(feature = "color")]
+fn args_color(cmd: &Command, long: bool, layout: Layout, shown: Visibility) -> String {
+    if shown_args(cmd, shown).next().is_none() && cmd.rest.is_none() {
         return "".into();
     }
-    let mut buf: String = format!("{}", "ARGS:\n".bold().underline());
-    for arg in &cmd.args.args {
-        let name = arg.argname.to_string();
-        buf.push_str(&format!(
-            "\t{name}{tabs}{description}\n",
-            name = name.bold(),
-            description = arg.description,
-            tabs = tabs(name.len())
-        ));
+    let mut groups = Groups::new();
+    for arg in shown_args(cmd, shown) {
+        let name = argname(arg);
+        groups.push(
+            arg.provider,
+            format_args!(
+                "\t{name}{gap}{description}{extras}\n{example}",
+                name = name.bold(),
+                description = description(arg, long),
+                extras = extras(arg, &cmd.strings).dimmed(),
+                gap = layout.gap(&name),
+                example = example(arg, &cmd.strings, layout)
+                    .unwrap_or_default()
+                    .dimmed()
+            ),
+        );
+    }
+    if let Some(rest) = &cmd.rest {
+        let name = self::rest(rest);
+        groups.push(
+            None,
+            format_args!(
+                "\t{name}{gap}{description}\n",
+                name = name.bold(),
+                description = escape(rest.description, false),
+                gap = layout.gap(&name)
+            ),
+        );
+    }
+    let groups = groups.finish(cmd.strings.core, |name| name.underline().to_string());
+    format!("{}\n{groups}", cmd.strings.args.bold().underline())
+}
+
+#[cfg(feature = "color")]
+fn usage_color(cmd: &Command, fullname: &str, shown: Visibility) -> String {
+    let mut buf: String = format!("{}", cmd.strings.usage.bold().underline());
+    for line in usage_lines(cmd, shown) {
+        buf.push_str(&format!("\n\t{fullname}{line}", fullname = fullname.bold()))
     }
     buf
 }
 
-fn usage_color(cmd: &Command, fullname: &str) -> String {
-    let mut buf: String = format!("{}", "USAGE:".bold().underline());
-    if !cmd.args.args.is_empty() {
-        buf.push_str(&format!(
-            "\n\t{fullname} [ARGS]",
-            fullname = fullname.bold()
-        ))
+#[cfg(feature = "color")]
+fn examples_color(cmd: &Command) -> String {
+    if cmd.examples.is_empty() {
+        return "".into();
     }
-    if !cmd.subcommands.is_empty() {
-        buf.push_str(&format!(
-            "\n\t{fullname} [SUBCOMMAND] [ARGS]",
-            fullname = fullname.bold()
-        ))
+    let mut buf = format!("{}\n", cmd.strings.examples.bold().underline());
+    for (line, description) in &cmd.examples {
+        buf.push_str(&format!("\t{}\n", escape(line, false).bold()));
+        if !description.is_empty() {
+            buf.push_str(&format!("\t\t{}\n", escape(description, false)));
+        }
     }
+    buf.push('\n');
     buf
 }
 
-fn create_color(cmd: &Command) -> String {
-    let fullname = format!("{} {}", cmd.parents.join(" "), cmd.name);
-    let fullname = fullname.trim();
+#[cfg(feature = "color")]
+fn create_color(cmd: &Command, long: bool, shown: Visibility) -> String {
+    let fullname = fullname(cmd);
+    let layout = Layout::new(cmd, shown);
+    let fullname = fullname.as_str();
     format!(
         "{fullname} {version}
 {author}{description}
@@ -165,26 +720,214 @@ fn create_color(cmd: &Command) -> String {
 {usage}
 
 {args}
-{subcommands}{license}",
+{subcommands}{examples}{epilogue}{links}{license}",
         fullname = fullname.bold(),
-        description = cmd.description,
+        description = escape(cmd.description, true),
         version = cmd.version.unwrap_or("").dimmed(),
         author = cmd
             .author
             .map(|a| format!("{a}\n"))
             .unwrap_or("".into())
             .italic(),
-        usage = usage_color(cmd, fullname),
-        args = args_color(cmd),
-        subcommands = subcommands_color(cmd),
+        usage = usage_color(cmd, fullname, shown),
+        args = args_color(cmd, long, layout, shown),
+        subcommands = subcommands_color(cmd, layout, shown),
+        examples = examples_color(cmd),
+        epilogue = epilogue(cmd),
+        links = links(cmd),
         license = license(cmd).bold()
     )
 }
 
-pub fn create(cmd: &Command) -> String {
-    if cmd.color {
-        create_color(cmd)
-    } else {
-        create_normal(cmd)
+fn build_info(info: &BuildInfo, strings: &Strings) -> String {
+    let mut buf = String::new();
+    if let Some(git_hash) = info.git_hash {
+        buf.push_str(&format!("\n{}: {git_hash}", strings.commit));
+    }
+    if let Some(build_date) = info.build_date {
+        buf.push_str(&format!("\n{}: {build_date}", strings.build_date));
+    }
+    if let Some(target) = info.target {
+        buf.push_str(&format!("\n{}: {target}", strings.target));
     }
+    buf
+}
+
+fn version_normal(cmd: &Command, fullname: &str) -> String {
+    format!(
+        "{fullname} {version}{long_version}{build_info}",
+        version = cmd.version.unwrap_or(""),
+        long_version = cmd
+            .long_version
+            .map(|l| format!("\n{l}"))
+            .unwrap_or("".into()),
+        build_info = build_info(&cmd.build_info, &cmd.strings)
+    )
+}
+
+#[cfg(feature = "color")]
+fn version_color(cmd: &Command, fullname: &str) -> String {
+    format!(
+        "{fullname} {version}{long_version}{build_info}",
+        fullname = fullname.bold(),
+        version = cmd.version.unwrap_or(""),
+        long_version = cmd
+            .long_version
+            .map(|l| format!("\n{l}"))
+            .unwrap_or("".into()),
+        build_info = build_info(&cmd.build_info, &cmd.strings).dimmed()
+    )
+}
+
+pub(crate) fn fullname(cmd: &Command) -> String {
+    format!("{} {}", cmd.parents.join(" "), cmd.name)
+        .trim()
+        .into()
+}
+
+pub(crate) fn usage(cmd: &Command) -> String {
+    usage_normal(cmd, &fullname(cmd), Visibility::Internal)
+}
+
+pub(crate) fn version(cmd: &Command) -> String {
+    let fullname = fullname(cmd);
+    let fullname = fullname.as_str();
+    #[cfg(feature = "color")]
+    if cmd.terminal_info().color {
+        return version_color(cmd, fullname);
+    }
+    version_normal(cmd, fullname)
+}
+
+// The page of the program itself, which shows the internal arguments and subcommands.
+pub(crate) fn create(cmd: &Command, long: bool) -> String {
+    let terminal = cmd.terminal_info();
+    let shown = Visibility::Internal;
+    let page = page(cmd, long, terminal.color, shown);
+    match terminal.width {
+        Some(width) => wrap(&page, width, &Layout::new(cmd, shown).indent().to_string()),
+        None => page,
+    }
+}
+
+#[cfg_attr(not(feature = "color"), allow(unused_variables))]
+fn page(cmd: &Command, long: bool, color: bool, shown: Visibility) -> String {
+    #[cfg(feature = "color")]
+    if color {
+        return create_color(cmd, long, shown);
+    }
+    create_normal(cmd, long, shown)
+}
+
+// The column reached by writing the text from the given one. Tabs move to the next multiple of 8
+// and color escape sequences take no space.
+fn column(mut col: usize, text: &str) -> usize {
+    let mut chunk = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\t' => {
+                col += width::width(&chunk);
+                chunk.clear();
+                col = (col / 8 + 1) * 8;
+            }
+            '\x1b' => {
+                chars.by_ref().find(|&c| c == 'm');
+            }
+            c => chunk.push(c),
+        }
+    }
+    col + width::width(&chunk)
+}
+
+// Wraps the lines of the page at the given width, breaking them only at spaces. Indented lines
+// continue at the given indentation, under the descriptions of the arguments.
+fn wrap(page: &str, width: usize, indent: &str) -> String {
+    let mut buf = String::with_capacity(page.len());
+    for (i, line) in page.split('\n').enumerate() {
+        if i > 0 {
+            buf.push('\n');
+        }
+        let indent = if line.starts_with('\t') { indent } else { "" };
+        let start = column(0, indent);
+        let mut col = 0;
+        for (j, word) in line.split(' ').enumerate() {
+            if j > 0 && col > start && column(col + 1, word) > width {
+                buf.push('\n');
+                buf.push_str(indent);
+                col = start;
+            } else if j > 0 {
+                buf.push(' ');
+                col += 1;
+            }
+            buf.push_str(word);
+            col = column(col, word);
+        }
+    }
+    buf
+}
+
+/// Writes the help page of the command to the given writer, rendered with the given options.
+///
+/// Like [`Command::print_help`], the page is created before parsing, so it does not contain the
+/// arguments inherited from the parent commands nor the automatic version flag.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// use tiny_args::help::{self, HelpOptions};
+///
+/// let cmd = Command::create("myapp", "This is my cool app.").arg(
+///     arg!(--name),
+///     value!(string),
+///     "Insert the name that will be shown to the other users of the service.",
+/// );
+/// let mut buf = String::new();
+/// let options = HelpOptions {
+///     width: Some(50),
+///     ..HelpOptions::default()
+/// };
+/// help::render(&cmd, &mut buf, options).unwrap();
+/// assert!(buf.contains("\t--name  Insert the name that will be shown\n\t        to the other users"));
+/// ```
+pub fn render(cmd: &Command, writer: &mut impl fmt::Write, options: HelpOptions) -> fmt::Result {
+    let page = page(cmd, options.long, options.color, options.visibility);
+    match options.width {
+        Some(width) => writer.write_str(&wrap(
+            &page,
+            width,
+            &Layout::new(cmd, options.visibility).indent().to_string(),
+        )),
+        None => writer.write_str(&page),
+    }
+}
+
+/// Returns the help page of the command in a stable plain text form: it is never colored, it is
+/// wrapped at 80 columns and it does not depend on the terminal nor on the environment. It is
+/// meant for snapshot tests, which catch accidental changes to the interface of a program.
+///
+/// It only shows the public arguments and subcommands (see [`Visibility`]).
+/// Like [`Command::print_help`], the page is created before parsing, so it does not contain the
+/// arguments inherited from the parent commands nor the automatic version flag.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = Command::create("myapp", "This is my cool app.")
+///     .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity.");
+/// assert_eq!(
+///     tiny_args::help::plain(&cmd),
+///     "myapp \nThis is my cool app.\n\nUSAGE:\n\tmyapp [--verbose]...\n\nARGS:\n\t-v, --verbose  Increases verbosity.\n\n"
+/// );
+/// ```
+pub fn plain(cmd: &Command) -> String {
+    let options = HelpOptions {
+        width: Some(80),
+        ..HelpOptions::default()
+    };
+    let mut buf = String::new();
+    render(cmd, &mut buf, options).expect("Writing to a String cannot fail");
+    buf
 }
@@ -0,0 +1,360 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::collections::VecDeque;
+
+use crate::{parser::valued, *};
+
+/// Parses a command line one token at a time, for interactive shells that validate and complete
+/// the line while the user types.
+///
+/// Each token is checked as soon as it is pushed: unknown arguments and subcommands, values that
+/// are not allowed and arguments that cannot be repeated are reported right away, and the token
+/// is rejected. What needs the whole line (e.g. a required subcommand, the minimum number of
+/// positional values or the validators) is checked only when the accepted tokens are parsed
+/// with [`Command::parse_from_iter`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = Command::create("console", "The admin console.").subcommand(
+///     Command::create("user", "Manages users.")
+///         .arg(arg!(--name), value!(string), "Insert a name.")
+///         .arg(arg!(--role), value!(choice ["admin", "guest"]), "Insert a role."),
+/// );
+/// let mut parser = Parser::new(&cmd);
+/// parser.push("user").unwrap();
+/// assert_eq!(parser.path(), ["user"]);
+/// assert_eq!(parser.candidates(), ["--name", "--role"]);
+/// parser.push("--role").unwrap();
+/// assert_eq!(parser.expecting(), Some(&arg!(--role)));
+/// assert_eq!(parser.candidates(), ["admin", "guest"]);
+/// assert!(parser.push("root").is_err());
+/// parser.push("admin").unwrap();
+/// assert_eq!(parser.tokens(), ["user", "--role", "admin"]);
+/// ```
+pub struct Parser<'a> {
+    root: &'a Command,
+    cmd: &'a Command,
+    path: Vec<&'static str>,
+    args: ArgList,
+    tokens: Vec<String>,
+    expecting: Option<ArgName>,
+    positional: bool,
+    help: bool,
+    plugin: bool,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a parser of the command line of the given command, without any token.
+    pub fn new(cmd: &'a Command) -> Self {
+        let mut parser = Parser {
+            root: cmd,
+            cmd,
+            path: Vec::new(),
+            args: ArgList::new(),
+            tokens: Vec::new(),
+            expecting: None,
+            positional: false,
+            help: false,
+            plugin: false,
+        };
+        let mut args = parser.level(cmd, &ArgList::new());
+        if cmd.verbosity {
+            let flags = [
+                (arg!(-'v', --verbose), cmd.strings.verbose_flag),
+                (arg!(-'q', --quiet), cmd.strings.quiet_flag),
+            ];
+            for (argname, description) in flags {
+                let argname = cmd.prefixes.name(argname);
+                if args.try_get(argname.clone()).is_none() {
+                    args.insert(Arg::new(argname, ArgValue::Count(0), description).global(true));
+                }
+            }
+        }
+        if cmd.color_flag && args.try_get(arg!(--color)).is_none() {
+            let option = Arg::new(
+                arg!(--color),
+                ArgValue::Choice(ColorChoice::NAMES, Some("auto".into())),
+                cmd.strings.color_flag,
+            );
+            args.insert(option.value_name("WHEN").global(true));
+        }
+        parser.args = args;
+        parser
+    }
+
+    // The arguments of the command: its own, the global ones of the parent (keeping whether
+    // they were used) and the help and version flags.
+    fn level(&self, cmd: &Command, parent: &ArgList) -> ArgList {
+        let mut args = ArgList {
+            args: cmd.args.args.clone(),
+        };
+        for arg in parent.args.iter().filter(|a| a.global) {
+            if !cmd.without_globals.contains(&arg.argname)
+                && args.try_get(arg.argname.clone()).is_none()
+            {
+                args.insert(Arg {
+                    inherited: true,
+                    ..arg.clone()
+                });
+            }
+        }
+        let flags = self
+            .root
+            .help_flag
+            .iter()
+            .map(|flag| (flag, self.root.strings.help_flag))
+            .chain(
+                cmd.auto_version
+                    .then_some((&cmd.version_flag, cmd.strings.version_flag)),
+            );
+        for (flag, description) in flags {
+            let flag = self.root.prefixes.name(flag.clone());
+            if args.try_get(flag.clone()).is_none() {
+                args.insert(Arg::new(flag, ArgValue::Flag, description));
+            }
+        }
+        args
+    }
+
+    fn builtin_help(&self) -> bool {
+        self.root.builtin_help && !self.cmd.subcommands.iter().any(|s| s.name == "help")
+    }
+
+    /// Consumes the next token of the command line.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the [`ErrorKind`] of a token that is not valid where it is. The
+    /// token is then rejected and the state of the parser does not change.
+    pub fn push(&mut self, token: &str) -> Result<(), ErrorKind> {
+        if self.plugin {
+            self.tokens.push(token.into());
+            return Ok(());
+        }
+        let mut args = ArgList {
+            args: self.args.args.clone(),
+        };
+        let expecting = self.expecting.clone();
+        self.expecting = None;
+        let result = match &expecting {
+            Some(argname) => self.value(&mut args, argname.clone(), token),
+            None => self.token(&mut args, token),
+        };
+        match result {
+            Ok(()) => {
+                if let Some(subcmd) = self.subcommand(token).filter(|_| self.expecting.is_none()) {
+                    self.args = self.level(subcmd, &args);
+                    self.cmd = subcmd;
+                    self.path.push(subcmd.name);
+                } else {
+                    self.args = args;
+                }
+                self.tokens.push(token.into());
+                Ok(())
+            }
+            Err(kind) => {
+                self.expecting = expecting;
+                Err(kind)
+            }
+        }
+    }
+
+    // The subcommand the token leads to, if it is not an argument nor a value.
+    fn subcommand(&self, token: &str) -> Option<&'a Command> {
+        if self.positional || self.root.prefixes.option(token) {
+            return None;
+        }
+        self.cmd.subcommands.iter().find(|s| s.name == token)
+    }
+
+    fn value(
+        &mut self,
+        args: &mut ArgList,
+        argname: ArgName,
+        token: &str,
+    ) -> Result<(), ErrorKind> {
+        // Like in the full parse, neither `--` nor a subcommand is taken as a value.
+        if token == "--" || self.cmd.subcommands.iter().any(|s| s.name == token) {
+            return Err(ErrorKind::MissingValue(argname));
+        }
+        let mut input = VecDeque::from([token.to_string()]);
+        parser::init(args, &argname, &mut input, &mut Vec::new(), None)
+    }
+
+    fn token(&mut self, args: &mut ArgList, token: &str) -> Result<(), ErrorKind> {
+        let mut input = VecDeque::new();
+        if self.help {
+            return match self.subcommand(token) {
+                Some(_) => Ok(()),
+                None => Err(ErrorKind::UnknownSubcommand(token.into())),
+            };
+        }
+        let option = self.root.prefixes.option(token);
+        if self.positional || !option || (token == "-" && self.cmd.rest.is_some()) {
+            if self.positional || self.subcommand(token).is_some() {
+                return Ok(());
+            }
+            if token == "help" && self.builtin_help() {
+                self.help = true;
+            } else if self.cmd.plugins.iter().any(|p| p.name == token) {
+                self.plugin = true;
+            } else if self.cmd.rest.is_some() {
+                self.positional = self.cmd.posix_strict;
+            } else if self.cmd.subcommands.is_empty() {
+                return Err(ErrorKind::UnexpectedToken(token.into()));
+            } else {
+                return Err(ErrorKind::UnknownSubcommand(token.into()));
+            }
+            return Ok(());
+        }
+        if let Some(cluster) = token.strip_prefix('+') {
+            for short in cluster.chars() {
+                parser::toggle(args, short, cluster)?;
+            }
+            return Ok(());
+        }
+        if token.starts_with("---") {
+            return Err(ErrorKind::TooManyDashes(token.into()));
+        } else if token == "-" {
+            return Err(ErrorKind::LoneDash);
+        }
+        if let Some(long) = token.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (long, None),
+            };
+            let argname = args.long_name(name);
+            if args.try_get(argname.clone()).is_none() {
+                if let Some(negated) = args.negated(name).map(|arg| arg.argname.clone()) {
+                    return args.negate_arg(&negated, value.map(String::from));
+                }
+            }
+            let arg = args
+                .try_get(argname.clone())
+                .ok_or_else(|| ErrorKind::UnknownArg(argname.clone()))?;
+            match value {
+                Some(value) if !valued(args, argname.clone()) => {
+                    return Err(arg.invalid_value(value.into(), "omitted"))
+                }
+                Some(value) => input.push_back(value.into()),
+                None if valued(args, argname.clone()) => {
+                    self.expecting = Some(argname);
+                    return Ok(());
+                }
+                None => (),
+            }
+            return parser::init(args, &argname, &mut input, &mut Vec::new(), None);
+        }
+        // Short arguments can be clustered (`-abc`) and the last one can have an attached value.
+        let mut chars = token[1..].chars();
+        while let Some(short) = chars.next() {
+            let argname = ArgName::Short(short);
+            if args.try_get(argname.clone()).is_none() {
+                return Err(ErrorKind::UnknownArg(argname));
+            }
+            if valued(args, argname.clone()) {
+                let attached = chars.as_str();
+                if attached.is_empty() {
+                    self.expecting = Some(argname);
+                    return Ok(());
+                }
+                input.push_back(attached.strip_prefix('=').unwrap_or(attached).into());
+                return parser::init(args, &argname, &mut input, &mut Vec::new(), None);
+            }
+            parser::init(args, &argname, &mut input, &mut Vec::new(), None)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the tokens that were accepted, without the program's name.
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Returns the names of the subcommands that were given, e.g. `["user", "add"]`.
+    pub fn path(&self) -> &[&'static str] {
+        &self.path
+    }
+
+    /// Returns the command the next tokens belong to: the last subcommand that was given, or
+    /// the root command.
+    pub fn command(&self) -> &'a Command {
+        self.cmd
+    }
+
+    /// Returns the argument that takes the next token as its value, if any.
+    pub fn expecting(&self) -> Option<&ArgName> {
+        self.expecting.as_ref()
+    }
+
+    /// Returns the tokens that can come next: the choices of the argument that expects a value,
+    /// or the subcommands, the plugins and the arguments that can still be used (by their long
+    /// name when they have one). Other values and positional values are not listed.
+    pub fn candidates(&self) -> Vec<String> {
+        if let Some(argname) = &self.expecting {
+            return match self.args.try_get(argname.clone()).map(|arg| &arg.argvalue) {
+                Some(ArgValue::Choice(choices, _)) => {
+                    choices.iter().map(|choice| choice.to_string()).collect()
+                }
+                _ => Vec::new(),
+            };
+        }
+        if self.plugin || self.positional {
+            return Vec::new();
+        }
+        let mut candidates: Vec<String> = self
+            .cmd
+            .subcommands
+            .iter()
+            .map(|s| s.name.to_string())
+            .collect();
+        if self.help {
+            return candidates;
+        }
+        if self.builtin_help() && !self.cmd.subcommands.is_empty() {
+            candidates.push("help".into());
+        }
+        candidates.extend(
+            self.cmd
+                .plugins
+                .iter()
+                .filter(|p| !self.cmd.subcommands.iter().any(|s| s.name == p.name))
+                .map(|p| p.name.clone()),
+        );
+        let args = self.args.args.iter().filter(|arg| {
+            let used = arg.counter > 0 || arg.enabled.is_some();
+            let placed = match arg.placement {
+                Placement::Anywhere => true,
+                Placement::Before => !arg.inherited,
+                Placement::After => arg.inherited,
+            };
+            placed && !(used && arg.duplicates == DuplicatePolicy::Error)
+        });
+        candidates.extend(args.map(|arg| match &arg.argname {
+            ArgName::Short(short) => format!("-{short}"),
+            ArgName::Long(long) | ArgName::Both { long, .. } => format!("--{long}"),
+        }));
+        candidates
+    }
+}
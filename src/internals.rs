@@ -0,0 +1,100 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+//! Counters of the allocations made by the program, which the benches of the `bench` feature
+//! compare against fixed budgets.
+//!
+//! The counters are updated only if [`Counting`] is the global allocator, so they measure the
+//! whole program: the allocations of a parse are the difference between two [`stats`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use tiny_args::internals::{self, Counting};
+//! # use tiny_args::*;
+//!
+//! #[global_allocator]
+//! static GLOBAL: Counting = Counting;
+//!
+//! let (cmd, input) = (Command::create("myapp", "This is my cool app."), vec!["myapp".into()]);
+//! let before = internals::stats();
+//! let parsed = cmd.parse_from(input);
+//! let allocations = internals::stats().since(before).allocations;
+//! assert!(parsed.is_ok() && allocations > 0);
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// An allocator that counts the allocations (reallocations included) and the allocated bytes,
+/// then leaves them to the system allocator.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size.saturating_sub(layout.size()), Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// The counters of [`Counting`], since the program started.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// How many times memory was allocated or reallocated.
+    pub allocations: usize,
+
+    /// How many bytes were allocated, counting only the growth of reallocations.
+    pub bytes: usize,
+}
+
+impl Stats {
+    /// Returns what was counted after the given stats were taken.
+    pub fn since(self, before: Stats) -> Stats {
+        Stats {
+            allocations: self.allocations - before.allocations,
+            bytes: self.bytes - before.bytes,
+        }
+    }
+}
+
+/// Returns the current counters. They are zero if [`Counting`] is not the global allocator.
+pub fn stats() -> Stats {
+    Stats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    }
+}
@@ -0,0 +1,104 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use crate::{
+    schema::{key, string},
+    *,
+};
+
+fn float(value: f64) -> String {
+    // JSON has no infinity nor NaN.
+    if value.is_finite() {
+        format!("{value:?}")
+    } else {
+        string(&value.to_string())
+    }
+}
+
+fn array(values: impl IntoIterator<Item = String>) -> String {
+    format!("[{}]", values.into_iter().collect::<Vec<_>>().join(","))
+}
+
+fn path(value: &Path) -> String {
+    string(&value.display().to_string())
+}
+
+// Returns the current value of the argument, or null if it has none.
+fn value(arg: &Arg) -> String {
+    if arg.sensitive && arg.counter > 0 {
+        return string("***");
+    }
+    let value = match &arg.argvalue {
+        ArgValue::String(value) | ArgValue::Choice(_, value) => value.as_deref().map(string),
+        ArgValue::Num(value) | ArgValue::Range(_, value) => value.map(|v| v.to_string()),
+        ArgValue::Float(value) | ArgValue::Ratio(value) => value.map(float),
+        ArgValue::Path(value) | ArgValue::ExistingPath(value) => value.as_deref().map(path),
+        ArgValue::Flag => Some(arg.enabled.unwrap_or(false).to_string()),
+        ArgValue::Count(count) => Some(count.to_string()),
+        ArgValue::List(values) => Some(array(values.iter().map(|v| string(v)))),
+        ArgValue::PathList(values) => Some(array(values.iter().map(|v| path(v)))),
+        ArgValue::Bytes(value) => value.as_deref().map(|v| string(&encoding::encode(v))),
+        ArgValue::Other(value) => value.default_display().as_deref().map(string),
+    };
+    value.unwrap_or_else(|| "null".into())
+}
+
+fn source(source: ValueSource) -> String {
+    match source {
+        ValueSource::Default => r#""source":"default""#.into(),
+        ValueSource::CommandLine => r#""source":"command_line""#.into(),
+        ValueSource::Env(var) => format!(r#""source":"env","env":{}"#, string(var)),
+        ValueSource::Override => r#""source":"override""#.into(),
+    }
+}
+
+// Serializes the arguments, the subcommands and the positional values of a parsed command.
+pub(crate) fn parsed(parsed: &ParsedCommand) -> String {
+    let lists: Vec<&ArgList> = parsed.ancestors.iter().chain([&parsed.args]).collect();
+    let mut args = Vec::new();
+    for (i, list) in lists.iter().enumerate() {
+        for arg in &list.args {
+            // Global arguments carry their value to the subcommand.
+            let inherited = lists
+                .get(i + 1)
+                .is_some_and(|next| arg.global && next.try_get(arg.argname.clone()).is_some());
+            if !inherited {
+                args.push(format!(
+                    r#"{}:{{"value":{},{}}}"#,
+                    string(&key(&arg.argname)),
+                    value(arg),
+                    source(arg.source())
+                ));
+            }
+        }
+    }
+    let plugin = match &parsed.plugin {
+        Some(plugin) => string(&plugin.name),
+        None => "null".into(),
+    };
+    format!(
+        r#"{{"command":{},"path":{},"args":{{{}}},"rest":{},"plugin":{plugin}}}"#,
+        string(parsed.name),
+        array(parsed.path().iter().map(|name| string(name))),
+        args.join(","),
+        array(parsed.rest.iter().map(|v| string(v))),
+    )
+}
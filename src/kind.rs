@@ -0,0 +1,174 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{any::Any, fmt};
+
+/// A value type defined outside of this crate. See [`ArgValue::Other`](crate::ArgValue::Other).
+///
+/// The implementor carries the value itself (or its default before parsing), which can be
+/// retrieved with [`Arg::other`](crate::Arg::other).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// #[derive(Clone, Debug)]
+/// struct Version(Option<(u64, u64)>);
+///
+/// impl ArgValueKind for Version {
+///     fn parse(&mut self, input: &str) -> Result<(), String> {
+///         let parse = |s: &str| s.parse::<u64>().ok();
+///         match input.split_once('.') {
+///             Some((major, minor)) => {
+///                 self.0 = Some((
+///                     parse(major).ok_or("a valid version")?,
+///                     parse(minor).ok_or("a valid version")?,
+///                 ));
+///                 Ok(())
+///             }
+///             None => Err("a version like 1.2".into()),
+///         }
+///     }
+///
+///     fn type_name(&self) -> &str {
+///         "version"
+///     }
+///
+///     fn default_display(&self) -> Option<String> {
+///         self.0.map(|(major, minor)| format!("{major}.{minor}"))
+///     }
+///
+///     fn clone_box(&self) -> Box<dyn ArgValueKind> {
+///         Box::new(self.clone())
+///     }
+/// }
+///
+/// let cmd = || {
+///     Command::create("myapp", "This is my cool app.")
+///         .arg(arg!(--min), ArgValue::Other(Box::new(Version(None))), "Minimum version.")
+/// };
+/// let parsed = cmd().parse_from_str("--min 1.2").unwrap();
+/// let min = parsed.args.get(arg!(--min)).other::<Version>().unwrap();
+/// assert_eq!(min.0, Some((1, 2)));
+/// assert!(cmd().parse_from_str("--min 1").is_err());
+/// ```
+pub trait ArgValueKind: Any + fmt::Debug + Send + Sync {
+    /// Parses the value given by the user and stores it, replacing the previous one.
+    ///
+    /// On failure it returns what the value must be (e.g. `"a valid UUID"`), which is shown
+    /// to the user like the reasons of the built-in values.
+    fn parse(&mut self, input: &str) -> Result<(), String>;
+
+    /// Returns the name of the type, shown in the configuration template
+    /// (see [`Command::config_template`](crate::Command::config_template)).
+    fn type_name(&self) -> &str;
+
+    /// Returns the current value as it should be displayed, or [`None`] if there is none.
+    /// Before parsing it is the default value shown in the help page.
+    fn default_display(&self) -> Option<String>;
+
+    /// Returns a copy of this value. It is usually `Box::new(self.clone())`.
+    fn clone_box(&self) -> Box<dyn ArgValueKind>;
+}
+
+impl Clone for Box<dyn ArgValueKind> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// Values defined elsewhere are compared by what they display, since that is all that is known
+// about them.
+impl PartialEq for dyn ArgValueKind {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id() == other.type_id()
+            && self.type_name() == other.type_name()
+            && self.default_display() == other.default_display()
+    }
+}
+
+/// The kind of an [`ArgValue`](crate::ArgValue), without the value it carries. See
+/// [`Arg::value_kind`](crate::Arg::value_kind).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    /// See [`ArgValue::String`](crate::ArgValue::String).
+    String,
+
+    /// See [`ArgValue::Num`](crate::ArgValue::Num).
+    Num,
+
+    /// See [`ArgValue::Float`](crate::ArgValue::Float).
+    Float,
+
+    /// See [`ArgValue::Path`](crate::ArgValue::Path).
+    Path,
+
+    /// See [`ArgValue::Flag`](crate::ArgValue::Flag).
+    Flag,
+
+    /// See [`ArgValue::Choice`](crate::ArgValue::Choice).
+    Choice,
+
+    /// See [`ArgValue::Range`](crate::ArgValue::Range).
+    Range,
+
+    /// See [`ArgValue::List`](crate::ArgValue::List).
+    List,
+
+    /// See [`ArgValue::Count`](crate::ArgValue::Count).
+    Count,
+
+    /// See [`ArgValue::PathList`](crate::ArgValue::PathList).
+    PathList,
+
+    /// See [`ArgValue::Bytes`](crate::ArgValue::Bytes).
+    Bytes,
+
+    /// See [`ArgValue::ExistingPath`](crate::ArgValue::ExistingPath).
+    ExistingPath,
+
+    /// See [`ArgValue::Ratio`](crate::ArgValue::Ratio).
+    Ratio,
+
+    /// See [`ArgValue::Other`](crate::ArgValue::Other).
+    Other,
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::String => "string",
+            Self::Num => "number",
+            Self::Float => "float",
+            Self::Path => "path",
+            Self::Flag => "flag",
+            Self::Choice => "choice",
+            Self::Range => "range",
+            Self::List => "list",
+            Self::Count => "count",
+            Self::PathList => "path list",
+            Self::Bytes => "bytes",
+            Self::ExistingPath => "existing path",
+            Self::Ratio => "ratio",
+            Self::Other => "custom value",
+        })
+    }
+}
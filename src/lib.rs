@@ -47,33 +47,144 @@
 //!         .parse()
 //!         .unwrap(); // Show the error to the user instead of panicking!!!
 //!
-//! if parsed.args.count(arg!(-'h')) > 0 {
+//! if parsed.wants_help() {
 //!     println!("{}", parsed.help);
 //!     return;
 //! }
 //!
-//! // Safe to unwrap since it has a default value.
-//! let path = parsed.args.get(arg!(--path)).path().unwrap();
+//! // Safe to unwrap since the argument exists and has a default value.
+//! let path = parsed.args.try_get(arg!(--path)).and_then(Arg::path).unwrap();
 //! println!("Path to something: {}", path.display());
 //!
-//! if let Some(words) = parsed.args.get(arg!(-'s')).string() {
+//! if let Some(words) = parsed.args.try_get(arg!(-'s')).and_then(Arg::string) {
 //!     println!("Your words: {words}");
 //! }
 //! ```
 
 #![warn(missing_docs)]
 
-use std::{env, fmt, path::PathBuf};
+#[cfg(not(feature = "smol_str"))]
+use std::borrow::Cow;
+use std::{
+    any::Any,
+    collections::VecDeque,
+    env,
+    ffi::OsString,
+    fmt,
+    io::{self, Write},
+    iter, mem,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
+#[cfg(feature = "smol_str")]
 use smol_str::SmolStr;
 
-mod help;
+mod autocorrect;
+mod completion;
+#[cfg(feature = "definitions")]
+mod definition;
+mod encoding;
+mod error;
+mod exit;
+#[cfg(feature = "glob")]
+mod glob;
+pub mod help;
+mod incremental;
+#[cfg(feature = "bench")]
+pub mod internals;
+mod json;
+mod kind;
+#[cfg(feature = "pager")]
+mod pager;
 mod parser;
+mod plugin;
+mod schema;
+#[cfg(feature = "semver")]
+mod semver;
+mod split;
+mod strings;
+mod terminal;
+#[cfg(feature = "testing")]
+mod testing;
+mod trace;
+mod utf8;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod width;
 #[macro_use]
 mod macros;
 #[cfg(test)]
 mod tests;
 
+pub use autocorrect::Autocorrect;
+pub use completion::{generate, generate_to, Shell, ValueHint};
+#[cfg(feature = "definitions")]
+pub use definition::DefinitionError;
+pub use error::{ArgNameError, ErrorKind, ParseError, WrongKind};
+pub use exit::{
+    exit_usage, EX_CONFIG, EX_DATAERR, EX_IOERR, EX_NOINPUT, EX_NOPERM, EX_OK, EX_SOFTWARE,
+    EX_TEMPFAIL, EX_UNAVAILABLE, EX_USAGE,
+};
+#[cfg(feature = "glob")]
+pub use glob::Glob;
+pub use incremental::Parser;
+pub use kind::{ArgValueKind, ValueKind};
+#[cfg(feature = "pager")]
+pub use pager::print_paged;
+pub use parser::{Prefixes, Token, Tokenizer};
+pub use plugin::Plugin;
+#[cfg(feature = "semver")]
+pub use semver::{Version, VersionReq};
+pub use split::{quote, split, SplitError};
+pub use strings::Strings;
+pub use terminal::{ColorChoice, TerminalInfo};
+#[cfg(feature = "testing")]
+pub use testing::{Generator, Input};
+#[cfg(feature = "tracing")]
+pub use trace::TraceEvent;
+pub use utf8::Utf8Policy;
+#[cfg(feature = "uuid")]
+pub use uuid::Uuid;
+
+/// The string holding a long name (see [`ArgName::Long`]).
+///
+/// It is a [`SmolStr`] with the `smol_str` feature, otherwise a
+/// [`Cow`](std::borrow::Cow) that borrows static names and owns the others.
+#[cfg(feature = "smol_str")]
+pub type LongName = SmolStr;
+
+/// The string holding a long name (see [`ArgName::Long`]).
+///
+/// It is a `SmolStr` with the `smol_str` feature, otherwise a [`Cow`] that borrows static names
+/// and owns the others.
+#[cfg(not(feature = "smol_str"))]
+pub type LongName = Cow<'static, str>;
+
+#[cfg(feature = "smol_str")]
+const fn static_name(name: &'static str) -> LongName {
+    SmolStr::new_static(name)
+}
+
+#[cfg(not(feature = "smol_str"))]
+const fn static_name(name: &'static str) -> LongName {
+    Cow::Borrowed(name)
+}
+
+#[cfg(feature = "smol_str")]
+fn owned_name(name: &str) -> LongName {
+    SmolStr::new(name)
+}
+
+#[cfg(not(feature = "smol_str"))]
+fn owned_name(name: &str) -> LongName {
+    Cow::Owned(name.into())
+}
+
 /// The argument's values.
 ///
 /// This enum is used during the initialization of the command to specify the argument's value type
@@ -96,6 +207,179 @@ pub enum ArgValue {
 
     /// Flags do not carry any value.
     Flag,
+
+    /// Carries a [`String`] that must be one of the given possible values.
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let format = ArgValue::Choice(&["json", "yaml"], Some("json".into()));
+    /// ```
+    Choice(&'static [&'static str], Option<String>),
+
+    /// Carries an [`i64`] that must be in the given range.
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let port = ArgValue::Range(1..=65535, Some(8080));
+    /// ```
+    Range(RangeInclusive<i64>, Option<i64>),
+
+    /// Carries a list of [`String`]s, one for each time the argument was used.
+    ///
+    /// The default values are replaced by the first value inserted by the user.
+    /// See [`DuplicatePolicy::Append`].
+    List(Vec<String>),
+
+    /// Counted flags carry how many times they were used, starting from the given value.
+    ///
+    /// Unlike [`Arg::counter`], this is the value of the argument itself (e.g. a verbosity
+    /// level given with `-v -v -v`).
+    Count(usize),
+
+    /// Carries a list of [`PathBuf`]s, one for each time the argument was used.
+    ///
+    /// Like [`ArgValue::List`], the default values are replaced by the first value inserted by
+    /// the user. Each value can also contain multiple paths, see [`Arg::split_paths`].
+    PathList(Vec<PathBuf>),
+
+    /// Carries raw bytes (e.g. keys and tokens).
+    ///
+    /// They are given as base64 (standard or URL-safe, padding is optional) or as hex digits
+    /// prefixed with `hex:`.
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--key), ArgValue::Bytes(None), "Insert a key.")
+    ///     .arg(arg!(--token), ArgValue::Bytes(None), "Insert a token.")
+    ///     .parse_from_str("--key aGkh --token hex:686921")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.get(arg!(--key)).bytes_raw(), Some(&b"hi!"[..]));
+    /// assert_eq!(parsed.args.get(arg!(--token)).bytes_raw(), Some(&b"hi!"[..]));
+    /// ```
+    Bytes(Option<Vec<u8>>),
+
+    /// Carries a [`PathBuf`] that must exist when it is given by the user.
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = || {
+    ///     Command::create("myapp", "This is my cool app.")
+    ///         .arg(arg!(--config), ArgValue::ExistingPath(None), "Insert a config file.")
+    /// };
+    /// assert!(cmd().parse_from_str("--config /").is_ok());
+    /// assert!(cmd().parse_from_str("--config /does/not/exist").is_err());
+    /// ```
+    ExistingPath(Option<PathBuf>),
+
+    /// Carries an [`f64`] between `0` and `1` (e.g. a quota), given either as a percentage
+    /// (`75%`) or as a ratio (`0.75`).
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--quota), value!(ratio), "Insert a quota.")
+    ///     .arg(arg!(--throttle), value!(ratio, 1.0), "Insert a throttle.")
+    ///     .parse_from_str("--quota 75% --throttle 0.5")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.get(arg!(--quota)).ratio(), Some(0.75));
+    /// assert_eq!(parsed.args.get(arg!(--throttle)).ratio(), Some(0.5));
+    /// ```
+    Ratio(Option<f64>),
+
+    /// Carries a value of a type defined outside of this crate (e.g. a UUID).
+    /// See [`ArgValueKind`].
+    Other(Box<dyn ArgValueKind>),
+}
+
+impl ArgValue {
+    /// Returns the value as it should be shown to the user (e.g. in the help page or in the
+    /// output of a `config show` subcommand), or [`None`] if there is none.
+    ///
+    /// Paths are converted lossily, floats are trimmed to at most nine decimals, ratios are
+    /// shown as percentages, bytes as `hex:` digits and lists are joined with commas. Flags do
+    /// not carry any value, see [`Arg::display`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// assert_eq!(value!(ratio, 0.07).display().unwrap(), "7%");
+    /// assert_eq!(value!(float, 0.1 + 0.2).display().unwrap(), "0.3");
+    /// let paths = ArgValue::PathList(vec!["/tmp".into(), "/srv".into()]);
+    /// assert_eq!(paths.display().unwrap(), "/tmp, /srv");
+    /// assert_eq!(value!(string).display(), None);
+    /// ```
+    pub fn display(&self) -> Option<String> {
+        match self {
+            ArgValue::List(values) => (!values.is_empty()).then(|| values.join(", ")),
+            ArgValue::PathList(_) => {
+                let items = self.items();
+                (!items.is_empty()).then(|| items.join(", "))
+            }
+            _ => self.item(),
+        }
+    }
+
+    // Returns each value as it should be shown to the user.
+    fn items(&self) -> Vec<String> {
+        match self {
+            ArgValue::List(values) => values.clone(),
+            ArgValue::PathList(paths) => paths
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect(),
+            _ => self.item().into_iter().collect(),
+        }
+    }
+
+    // Returns the value of the kinds that carry at most one.
+    fn item(&self) -> Option<String> {
+        match self {
+            ArgValue::Flag | ArgValue::List(_) | ArgValue::PathList(_) => None,
+            ArgValue::Count(count) => Some(count.to_string()),
+            ArgValue::String(value) | ArgValue::Choice(_, value) => value.clone(),
+            ArgValue::Num(value) | ArgValue::Range(_, value) => value.map(|v| v.to_string()),
+            ArgValue::Float(value) => value.map(trimmed),
+            ArgValue::Ratio(value) => value.map(|v| trimmed(v * 100.0) + "%"),
+            ArgValue::Path(value) | ArgValue::ExistingPath(value) => {
+                value.as_ref().map(|p| p.to_string_lossy().into_owned())
+            }
+            ArgValue::Bytes(value) => value.as_deref().map(encoding::encode),
+            ArgValue::Other(value) => value.default_display(),
+        }
+    }
+}
+
+// Formats a float without the noise of its binary representation (e.g. `0.3` instead of
+// `0.30000000000000004`), unless it is too small to be shown with nine decimals.
+fn trimmed(value: f64) -> String {
+    let mut fixed = format!("{value:.9}");
+    let len = fixed.trim_end_matches('0').trim_end_matches('.').len();
+    fixed.truncate(len);
+    if !value.is_finite() || (value != 0.0 && fixed.trim_start_matches('-') == "0") {
+        value.to_string()
+    } else {
+        fixed
+    }
+}
+
+// Parses a percentage (e.g. `75%`) or a ratio (e.g. `0.75`) between 0 and 1. On failure it
+// returns what the value must be.
+fn ratio(input: &str) -> Result<f64, &'static str> {
+    let (number, scale) = match input.strip_suffix('%') {
+        Some(number) => (number, 100.0),
+        None => (input, 1.0),
+    };
+    let value = match number.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() => value / scale,
+        _ => return Err("a percentage like 75% or a ratio like 0.75"),
+    };
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err("between 0% and 100%")
+    }
 }
 
 /// Name of an argument. It contains both short and/or long names of the argument.
@@ -111,11 +395,18 @@ pub enum ArgValue {
 /// assert_eq!(arg! { -'h', --help }, arg!(-'h'));
 /// assert_eq!(arg! { -'h', --help }, arg!(--help));
 /// ```
+///
+/// Since a single shared name is enough, a program must not pair a short name with two different
+/// long names (or the other way around), even in different subcommands: `-'o', --out` and
+/// `-'o', --output` make building the command panic.
 #[derive(Eq, Clone, Debug)]
 pub enum ArgName {
     /// Represents a short argument.
     ///
     /// It is formed by a dash and a character on the command line (e.g. `-h`).
+    /// Short arguments can be clustered (`-abc` is `-a -b -c`) and the last one can have its
+    /// value attached, optionally after an `=` (`-n5` and `-n=5` are `-n 5`). Only the first
+    /// `=` is a separator, so `-n==5` gives `=5`.
     /// The dash is omitted in the enum's value.
     /// When turned into a string this enum recreates the argument.
     ///
@@ -130,6 +421,9 @@ pub enum ArgName {
     /// Represents a long argument.
     ///
     /// It is formed by two dashes and a string on the command line (e.g. `--help`).
+    /// Its value can be attached after an `=` (`--key=value`): only the first `=` is a
+    /// separator, and the value is taken verbatim even if it starts with a dash
+    /// (`--message=-value`).
     /// The dashes are omitted in the enum's value.
     /// When turned into a string this enum recreates the argument.
     ///
@@ -139,7 +433,7 @@ pub enum ArgName {
     /// # use tiny_args::ArgName;
     /// assert_eq!(ArgName::long("help").to_string(), "--help");
     /// ```
-    Long(SmolStr),
+    Long(LongName),
 
     /// Represents both a long and a short argument.
     ///
@@ -156,48 +450,187 @@ pub enum ArgName {
         short: char,
 
         /// Long argument's name.
-        long: SmolStr,
+        long: LongName,
     },
 }
 
 impl ArgName {
-    /// Creates a new short [`ArgName`] from a char.
+    /// Creates a new short [`ArgName`] from a char. It can be used in constants and statics.
+    ///
+    /// In debug builds it panics if the name is not valid, see [`ArgName::try_short`].
     #[inline(always)]
-    pub fn short(name: char) -> Self {
+    #[track_caller]
+    pub const fn short(name: char) -> Self {
+        if cfg!(debug_assertions) && !Self::is_valid_short(name) {
+            panic!("A short name cannot be a dash nor whitespace");
+        }
         Self::Short(name)
     }
 
     /// Creates a new long [`ArgName`].
+    ///
+    /// In debug builds it panics if the name is not valid, see [`ArgName::try_long`].
     #[inline(always)]
+    #[track_caller]
     pub fn long(name: &str) -> Self {
-        Self::Long(SmolStr::from(name))
+        assert_valid(Self::check_long(name));
+        Self::Long(owned_name(name))
     }
 
-    /// Creates a new long [`ArgName`] from a static string.
+    /// Creates a new long [`ArgName`] from a static string. It can be used in constants and
+    /// statics.
     ///
     /// Consider using the [`arg`] macro instead of this function.
     #[inline(always)]
-    pub fn long_static(name: &'static str) -> Self {
-        Self::Long(SmolStr::new_static(name))
+    #[track_caller]
+    pub const fn long_static(name: &'static str) -> Self {
+        if cfg!(debug_assertions) && !Self::is_valid_long(name) {
+            panic!("A long name cannot be empty, contain whitespace or start with a dash");
+        }
+        Self::Long(static_name(name))
     }
 
     /// Creates a new [`ArgName`] with short and long options.
+    ///
+    /// In debug builds it panics if the names are not valid, see [`ArgName::try_both`].
     #[inline(always)]
+    #[track_caller]
     pub fn both(short: char, long: &str) -> Self {
+        assert_valid(Self::check_short(short).and(Self::check_long(long)));
         Self::Both {
             short,
-            long: SmolStr::new(long),
+            long: owned_name(long),
         }
     }
 
-    /// Creates a new [`ArgName`] with short and long options from a static strings.
+    /// Creates a new [`ArgName`] with short and long options from a static strings. It can be
+    /// used in constants and statics.
     ///
     /// Consider using the [`arg`] macro instead of this function.
     #[inline(always)]
-    pub fn both_static(short: char, long: &'static str) -> Self {
+    #[track_caller]
+    pub const fn both_static(short: char, long: &'static str) -> Self {
+        if cfg!(debug_assertions) && !(Self::is_valid_short(short) && Self::is_valid_long(long)) {
+            panic!(
+                "A short name cannot be a dash nor whitespace and a long name cannot be empty, \
+                 contain whitespace or start with a dash"
+            );
+        }
         Self::Both {
             short,
-            long: SmolStr::new_static(long),
+            long: static_name(long),
+        }
+    }
+
+    /// Creates a new short [`ArgName`] from a char, checking that it is valid: it cannot be a
+    /// dash nor whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// assert_eq!(ArgName::try_short('h'), Ok(arg!(-'h')));
+    /// assert_eq!(ArgName::try_short('-'), Err(ArgNameError::InvalidShort('-')));
+    /// ```
+    pub fn try_short(name: char) -> Result<Self, ArgNameError> {
+        Self::check_short(name)?;
+        Ok(Self::Short(name))
+    }
+
+    /// Creates a new long [`ArgName`], checking that it is valid: it cannot be empty, contain
+    /// whitespace or start with a dash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// assert_eq!(ArgName::try_long("help"), Ok(arg!(--help)));
+    /// assert_eq!(
+    ///     ArgName::try_long("--help"),
+    ///     Err(ArgNameError::LeadingDash("--help".into()))
+    /// );
+    /// ```
+    pub fn try_long(name: &str) -> Result<Self, ArgNameError> {
+        Self::check_long(name)?;
+        Ok(Self::Long(owned_name(name)))
+    }
+
+    /// Creates a new [`ArgName`] with short and long options, checking that they are valid.
+    /// See [`ArgName::try_short`] and [`ArgName::try_long`].
+    pub fn try_both(short: char, long: &str) -> Result<Self, ArgNameError> {
+        Self::check_short(short)?;
+        Self::check_long(long)?;
+        Ok(Self::Both {
+            short,
+            long: owned_name(long),
+        })
+    }
+
+    // Checks a short name at compile time, see the `arg` macro.
+    #[doc(hidden)]
+    pub const fn is_valid_short(name: char) -> bool {
+        name != '-' && !name.is_whitespace()
+    }
+
+    // Checks a long name at compile time, see the `arg` macro.
+    #[doc(hidden)]
+    pub const fn is_valid_long(name: &str) -> bool {
+        let bytes = name.as_bytes();
+        if bytes.is_empty() || bytes[0] == b'-' {
+            return false;
+        }
+        let mut i = 0;
+        while i < bytes.len() {
+            // Iterators cannot be used in constants, so each character is decoded by hand.
+            let (len, mut code) = match bytes[i] {
+                b if b < 0x80 => (1, b as u32),
+                b if b >= 0xF0 => (4, b as u32 & 0x07),
+                b if b >= 0xE0 => (3, b as u32 & 0x0F),
+                b => (2, b as u32 & 0x1F),
+            };
+            let mut j = 1;
+            while j < len {
+                code = (code << 6) | (bytes[i + j] as u32 & 0x3F);
+                j += 1;
+            }
+            if let Some(c) = char::from_u32(code) {
+                if c.is_whitespace() {
+                    return false;
+                }
+            }
+            i += len;
+        }
+        true
+    }
+
+    fn check_short(name: char) -> Result<(), ArgNameError> {
+        if !Self::is_valid_short(name) {
+            return Err(ArgNameError::InvalidShort(name));
+        }
+        Ok(())
+    }
+
+    fn check_long(name: &str) -> Result<(), ArgNameError> {
+        if name.is_empty() {
+            Err(ArgNameError::Empty)
+        } else if name.contains(char::is_whitespace) {
+            Err(ArgNameError::Whitespace(name.into()))
+        } else if name.starts_with('-') {
+            Err(ArgNameError::LeadingDash(name.into()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Names of arguments are checked only in debug builds when they are not created by fallible
+// functions, since they are usually written in the code.
+#[inline(always)]
+#[track_caller]
+fn assert_valid(result: Result<(), ArgNameError>) {
+    if cfg!(debug_assertions) {
+        if let Err(e) = result {
+            panic!("{e}");
         }
     }
 }
@@ -224,6 +657,29 @@ impl PartialEq for ArgName {
     }
 }
 
+impl ArgName {
+    // Whether or not both names have a short and a long name, but only one of them is shared.
+    pub(crate) fn conflicts(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Both { short, long }, Self::Both { short: s, long: l }) => {
+                (short == s) != (long == l)
+            }
+            _ => false,
+        }
+    }
+
+    // Whether or not every name of this one is also a name of the other (e.g. `--help` of
+    // `-h, --help`, but not `-h, --host`).
+    pub(crate) fn within(&self, other: &Self) -> bool {
+        match self {
+            Self::Short(_) | Self::Long(_) => self == other,
+            Self::Both { short, long } => {
+                matches!(other, Self::Both { short: s, long: l } if s == short && l == long)
+            }
+        }
+    }
+}
+
 impl fmt::Display for ArgName {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self {
@@ -236,9 +692,224 @@ impl fmt::Display for ArgName {
     }
 }
 
+/// Where the value of an argument comes from.
+///
+/// See [`Arg::source`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueSource {
+    /// The argument was not used, so it has its default value.
+    Default,
+
+    /// The argument was given in the environment variable with extra arguments. It carries the
+    /// name of the variable. See [`Command::args_env`].
+    Env(&'static str),
+
+    /// The argument was given on the command line.
+    CommandLine,
+
+    /// The value was set by the application after parsing (e.g. from a configuration file).
+    /// See [`ArgList::set`].
+    Override,
+}
+
+/// What using an argument more than once means.
+///
+/// See [`Arg::duplicates`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Only the last value is kept. This is the default for every value except
+    /// [`ArgValue::List`] and [`ArgValue::PathList`].
+    Overwrite,
+
+    /// Using the argument more than once is an error.
+    Error,
+
+    /// Every value is appended to the list. This is the default for [`ArgValue::List`] and
+    /// [`ArgValue::PathList`], and it can only be used with them.
+    Append,
+}
+
+/// What giving the same value more than once to a list means, e.g. `--tag a,b --tag a`.
+///
+/// See [`Arg::merge`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Every value is kept, even if it was already given. This is the default.
+    Append,
+
+    /// Values that were already given are discarded, so the list keeps only the first time each
+    /// value was given.
+    Dedupe,
+
+    /// Giving a value that was already given is an error.
+    Error,
+}
+
+impl MergePolicy {
+    // Adds the new values to the list, returning how many were added or the first value that
+    // was already given if it is an error.
+    fn merge<T: PartialEq>(
+        self,
+        values: &mut Vec<T>,
+        new: impl IntoIterator<Item = T>,
+    ) -> Result<usize, T> {
+        let len = values.len();
+        for value in new {
+            if self != MergePolicy::Append && values.contains(&value) {
+                if self == MergePolicy::Error {
+                    return Err(value);
+                }
+                continue;
+            }
+            values.push(value);
+        }
+        Ok(values.len() - len)
+    }
+}
+
+/// Where a global argument can be used, relative to the name of a subcommand.
+///
+/// See [`Arg::placement`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    /// Both in the command that defines the argument and in its subcommands. This is the
+    /// default.
+    Anywhere,
+
+    /// Only in the command that defines the argument, before the name of a subcommand.
+    Before,
+
+    /// Only in the subcommands, after their name.
+    After,
+}
+
+/// Where an argument or a subcommand is documented. It is always parsed, whatever its
+/// visibility.
+///
+/// See [`Arg::visibility`] and [`Command::visibility`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = Command::create("tinycloud", "This is my cool app.")
+///     .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+///     .add_arg(
+///         Arg::new(arg!(--reset-admin), value!(), "Resets the admin account.")
+///             .visibility(Visibility::Internal),
+///     );
+/// // Published documents, like this page, leave out the internal arguments.
+/// let page = help::plain(&cmd);
+/// assert!(page.contains("--port") && !page.contains("--reset-admin"));
+/// let mut buf = Vec::new();
+/// cmd.color(false).print_help(&mut buf).unwrap();
+/// assert!(String::from_utf8(buf).unwrap().contains("--reset-admin"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Visibility {
+    /// Documented everywhere. This is the default.
+    #[default]
+    Public,
+
+    /// Documented only by the program itself: its help pages and the usage of its errors show
+    /// it, while the documents generated from the command for publishing do not (help pages
+    /// rendered with [`help::render`] and [`help::plain`] by default, completion scripts and
+    /// configuration templates).
+    Internal,
+
+    /// Never documented.
+    Hidden,
+}
+
+/// Which values an [`ArgValue::Float`] accepts.
+///
+/// See [`Arg::float_policy`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let policy = FloatPolicy {
+///     max_magnitude: Some(1000.0),
+///     decimal_comma: true,
+///     ..FloatPolicy::FINITE
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatPolicy {
+    /// Whether or not `NaN` is accepted.
+    pub nan: bool,
+
+    /// Whether or not infinite values (e.g. `inf`) are accepted.
+    pub infinite: bool,
+
+    /// The highest absolute value that is accepted, if any.
+    pub max_magnitude: Option<f64>,
+
+    /// Whether or not a comma can be used as decimal separator (e.g. `3,14`).
+    pub decimal_comma: bool,
+}
+
+impl FloatPolicy {
+    /// Accepts every value, using only the dot as decimal separator. This is the default.
+    pub const ANY: FloatPolicy = FloatPolicy {
+        nan: true,
+        infinite: true,
+        max_magnitude: None,
+        decimal_comma: false,
+    };
+
+    /// Accepts only finite values, using only the dot as decimal separator.
+    pub const FINITE: FloatPolicy = FloatPolicy {
+        nan: false,
+        infinite: false,
+        max_magnitude: None,
+        decimal_comma: false,
+    };
+
+    // Parses the value, returning what it must be if it is not accepted.
+    fn parse(&self, input: &str) -> Result<f64, String> {
+        let value: f64 = if self.decimal_comma && !input.contains('.') {
+            input.replacen(',', ".", 1).parse()
+        } else {
+            input.parse()
+        }
+        .map_err(|e| format!("a valid float number: {e}"))?;
+        if value.is_nan() && !self.nan {
+            return Err("a number, not NaN".into());
+        }
+        if value.is_infinite() && !self.infinite {
+            return Err("a finite number".into());
+        }
+        if let Some(max) = self.max_magnitude {
+            if value.abs() > max {
+                return Err(format!("between -{max} and {max}"));
+            }
+        }
+        Ok(value)
+    }
+}
+
+impl Default for FloatPolicy {
+    fn default() -> Self {
+        Self::ANY
+    }
+}
+
 /// A struct containing all the information of an argument.
 ///
-/// This struct is not available until [`Command`] has been parsed.
+/// Arguments are usually specified with [`Command::arg`]. Create one with [`Arg::new`] and add it
+/// with [`Command::add_arg`] to change its other settings.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = Command::create("myapp", "This is my cool app.").add_arg(
+///     Arg::new(arg!(--path), value!(path), "Insert a path.").duplicates(DuplicatePolicy::Error),
+/// );
+/// ```
 #[non_exhaustive]
 #[derive(Clone)]
 pub struct Arg {
@@ -253,53 +924,646 @@ pub struct Arg {
     /// Description of this argument
     pub description: &'static str,
 
+    /// Longer description of this argument, shown only in [`ParsedCommand::long_help`].
+    pub long_description: Option<&'static str>,
+
     /// How many time this argument was called in the command line. (`0` if none)
     ///
-    /// Note: arguments can be called multiple times, but if they carry a value only the last one
-    /// is saved. A counter is usually useful for some types of flags, or to check if the
-    /// argument was called in command line, instead of containing just the default value.
+    /// Note: arguments can be called multiple times, but if they carry a value only the last one is
+    /// saved, unless stated otherwise by [`Arg::duplicates`]. A counter is usually useful for some
+    /// types of flags, or to check if the argument was called in command line, instead of
+    /// containing just the default value.
     pub counter: usize,
+
+    /// What using this argument more than once means.
+    pub duplicates: DuplicatePolicy,
+
+    /// Name of this argument's value shown in the help page (e.g. `--timeout <SECONDS>`).
+    pub value_name: Option<&'static str>,
+
+    /// An example of how this argument is used, shown in the help page. See [`Arg::example`].
+    pub example: Option<&'static str>,
+
+    /// Name of the component that offers this argument. See [`Arg::provider`].
+    pub provider: Option<&'static str>,
+
+    /// If this argument is deprecated, a note telling the user what to use instead.
+    pub deprecated: Option<&'static str>,
+
+    /// Whether or not this argument is inherited by every subcommand.
+    pub global: bool,
+
+    /// Where this global argument can be used. See [`Arg::placement`].
+    pub placement: Placement,
+
+    /// The subcommands this argument is valid with. See [`Arg::only_with`].
+    pub only_with: Vec<&'static str>,
+
+    /// Where this argument is documented. See [`Arg::visibility`].
+    pub visibility: Visibility,
+
+    /// Whether or not each value of an [`ArgValue::PathList`] is split like the `PATH`
+    /// environment variable.
+    pub split_paths: bool,
+
+    /// Which values an [`ArgValue::Float`] accepts.
+    pub float_policy: FloatPolicy,
+
+    /// Whether or not the value of this argument is secret (e.g. a password or a token).
+    /// See [`Arg::sensitive`].
+    pub sensitive: bool,
+
+    /// Whether or not the whitespace around each value is removed. See [`Arg::trim`].
+    pub trim: bool,
+
+    /// Whether or not the empty string is an acceptable value. See [`Arg::allow_empty`].
+    pub allow_empty: bool,
+
+    /// What kind of value this argument takes, used by completion scripts. See
+    /// [`Arg::value_hint`].
+    pub value_hint: Option<ValueHint>,
+
+    /// Whether or not this flag can be switched off with `--no-<name>`. See
+    /// [`Arg::negatable`].
+    pub negatable: bool,
+
+    /// How many values this list takes each time it is used. See [`Arg::num_values`].
+    pub num_values: Option<RangeInclusive<usize>>,
+
+    /// The character each value of an [`ArgValue::List`] is split on. See [`Arg::delimiter`].
+    pub delimiter: Option<char>,
+
+    /// What giving the same value more than once to a list means.
+    pub merge: MergePolicy,
+
+    // The value this argument was created with.
+    default: ArgValue,
+
+    // How many values were given each time the list was used.
+    sizes: Vec<usize>,
+
+    // The values that were replaced by a later use of the argument.
+    overwritten: Vec<String>,
+
+    // Whether the flag was last switched on or off.
+    enabled: Option<bool>,
+
+    // Where the value comes from.
+    source: ValueSource,
+
+    // Whether this is the copy of a global argument of a parent command.
+    inherited: bool,
+
+    // Whether the application read the value. See `Arg::mark_used`.
+    used: Used,
+}
+
+// A flag set through a shared reference, so that the accessors can record the reads.
+#[derive(Default)]
+struct Used(AtomicBool);
+
+impl Clone for Used {
+    fn clone(&self) -> Self {
+        Used(AtomicBool::new(self.0.load(Ordering::Relaxed)))
+    }
 }
 
 impl Arg {
-    fn new(argname: ArgName, argvalue: ArgValue, description: &'static str) -> Self {
+    /// Creates a new [`Arg`].
+    pub fn new(argname: ArgName, argvalue: ArgValue, description: &'static str) -> Self {
+        let duplicates = if let ArgValue::List(_) | ArgValue::PathList(_) = argvalue {
+            DuplicatePolicy::Append
+        } else {
+            DuplicatePolicy::Overwrite
+        };
         Self {
             argname,
+            default: argvalue.clone(),
             argvalue,
             description,
+            long_description: None,
             counter: 0, // Counts how many times the argument has been called.
+            duplicates,
+            value_name: None,
+            example: None,
+            provider: None,
+            deprecated: None,
+            global: false,
+            placement: Placement::Anywhere,
+            visibility: Visibility::Public,
+            only_with: Vec::new(),
+            split_paths: false,
+            float_policy: FloatPolicy::ANY,
+            sensitive: false,
+            trim: false,
+            allow_empty: true,
+            value_hint: None,
+            negatable: false,
+            num_values: None,
+            delimiter: None,
+            merge: MergePolicy::Append,
+            sizes: Vec::new(),
+            overwritten: Vec::new(),
+            enabled: None,
+            source: ValueSource::Default,
+            inherited: false,
+            used: Used::default(),
         }
     }
 
-    /// Returns the [`String`] value of the argument.
+    /// Specifies whether or not this argument is inherited by every subcommand.
+    /// By default it is not.
     ///
-    /// If no value (not even default) was specified or if it is not an [`ArgValue::String`]
-    /// it returns [`None`].
-    pub fn string(&self) -> Option<&str> {
-        if let ArgValue::String(Some(value)) = &self.argvalue {
-            Some(value)
-        } else {
-            None
-        }
+    /// Subcommands that should not inherit it can opt out with [`Command::without_global`].
+    #[inline]
+    pub fn global(mut self, global: bool) -> Self {
+        self.global = global;
+        self
     }
 
-    /// Returns the [`i64`] value of the argument.
+    /// Specifies where this global argument can be used: before the name of a subcommand, after
+    /// it, or both. By default it is [`Placement::Anywhere`].
     ///
-    /// If no value (not even default) was specified or if it is not an [`ArgValue::Num`]
-    /// it returns [`None`].
-    pub fn num(&self) -> Option<i64> {
-        if let ArgValue::Num(Some(value)) = self.argvalue {
-            Some(value)
-        } else {
-            None
-        }
-    }
-
-    /// Returns the [`f64`] value of the argument.
+    /// Using it elsewhere is an error ([`ErrorKind::MisplacedArg`]), e.g. an option that only
+    /// the root command reads is not silently accepted after a subcommand.
     ///
-    /// If no value (not even default) was specified or if it is not an [`ArgValue::Float`]
-    /// it returns [`None`].
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = || {
+    ///     Command::create("myapp", "This is my cool app.")
+    ///         .add_arg(
+    ///             Arg::new(arg!(--config), value!(path), "Insert a configuration file.")
+    ///                 .global(true)
+    ///                 .placement(Placement::Before),
+    ///         )
+    ///         .subcommand(Command::create("run", "Runs."))
+    /// };
+    /// assert!(cmd().parse_from_str("--config app.toml run").is_ok());
+    /// let err = cmd().parse_from_str("run --config app.toml").err().unwrap();
+    /// assert_eq!(
+    ///     *err.kind(),
+    ///     ErrorKind::MisplacedArg {
+    ///         arg: arg!(--config),
+    ///         placement: Placement::Before
+    ///     }
+    /// );
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Parsing panics if an argument that is not global is placed.
+    #[inline]
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Specifies a subcommand of the command this argument belongs to that must be selected for
+    /// this argument to be used. It can be called more than once, in which case any of the
+    /// subcommands will do. By default the argument is valid with every subcommand.
+    ///
+    /// It is checked once the subcommands are resolved, so it also catches the argument being
+    /// used before the subcommand (e.g. `myapp --follow logs`). Using it without the subcommand
+    /// is an error ([`ErrorKind::RequiresSubcommand`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = || {
+    ///     Command::create("myapp", "This is my cool app.")
+    ///         .add_arg(
+    ///             Arg::new(arg!(--follow), value!(), "Keep printing new lines.")
+    ///                 .only_with("logs"),
+    ///         )
+    ///         .subcommand(Command::create("logs", "Prints the logs."))
+    ///         .subcommand(Command::create("status", "Prints the status."))
+    /// };
+    /// assert!(cmd().parse_from_str("--follow logs").is_ok());
+    /// let err = cmd().parse_from_str("--follow status").err().unwrap();
+    /// assert_eq!(
+    ///     *err.kind(),
+    ///     ErrorKind::RequiresSubcommand {
+    ///         arg: arg!(--follow),
+    ///         subcommands: vec!["logs"]
+    ///     }
+    /// );
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Parsing panics if the subcommand does not exist in the command of the argument.
+    #[inline]
+    pub fn only_with(mut self, subcommand: &'static str) -> Self {
+        self.only_with.push(subcommand);
+        self
+    }
+
+    /// Specifies a longer description of this argument.
+    ///
+    /// It replaces the description in the long help page ([`ParsedCommand::long_help`]), which is
+    /// usually shown for `--help` while `-h` shows the compact one ([`ParsedCommand::help`]).
+    #[inline]
+    pub fn long_description(mut self, long_description: &'static str) -> Self {
+        self.long_description = Some(long_description);
+        self
+    }
+
+    /// Specifies the name of this argument's value.
+    ///
+    /// It will appear on the help page next to the argument's name (e.g. `--timeout <SECONDS>`).
+    #[inline]
+    pub fn value_name(mut self, value_name: &'static str) -> Self {
+        self.value_name = Some(value_name);
+        self
+    }
+
+    /// Specifies an example of how this argument is used (e.g. `--bind 0.0.0.0:8080`).
+    ///
+    /// It will appear on the help page beneath the argument's description, which helps with
+    /// values in formats that are not obvious (e.g. durations).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .add_arg(
+    ///         Arg::new(arg!(--bind), value!(string), "Address to listen on.")
+    ///             .example("--bind 0.0.0.0:8080"),
+    ///     )
+    ///     .color(false);
+    /// let mut buf = Vec::new();
+    /// cmd.print_help(&mut buf).unwrap();
+    /// let help = String::from_utf8(buf).unwrap();
+    /// assert!(help.contains("\t--bind  Address to listen on.\n\t        example: --bind 0.0.0.0:8080\n"));
+    /// ```
+    #[inline]
+    pub fn example(mut self, example: &'static str) -> Self {
+        self.example = Some(example);
+        self
+    }
+
+    /// Specifies the name of the component that offers this argument (e.g. `Backup plugin`).
+    /// By default there is none.
+    ///
+    /// See [`Command::provider`].
+    #[inline]
+    pub fn provider(mut self, provider: &'static str) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Specifies where this argument is documented, e.g. to keep administrative flags out of
+    /// published documents. By default it is [`Visibility::Public`].
+    ///
+    /// The argument is parsed whatever its visibility.
+    #[inline]
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Specifies what kind of value this argument takes, so that completion scripts (see
+    /// [`generate`]) can suggest values. By default paths complete files, choices complete
+    /// their possible values and any other value is not completed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .add_arg(
+    ///         Arg::new(arg!(--host), value!(string), "Insert a host.")
+    ///             .value_hint(ValueHint::Hostname),
+    ///     )
+    ///     .add_arg(
+    ///         Arg::new(arg!(--data), value!(path), "Insert a directory.")
+    ///             .value_hint(ValueHint::DirPath),
+    ///     );
+    /// assert!(generate(Shell::Bash, &cmd).contains("compgen -A hostname"));
+    /// ```
+    #[inline]
+    pub fn value_hint(mut self, value_hint: ValueHint) -> Self {
+        self.value_hint = Some(value_hint);
+        self
+    }
+
+    /// Marks this argument as deprecated.
+    ///
+    /// The argument is still parsed, but using it adds a warning containing the given note
+    /// (e.g. `"use --new-flag instead"`) to [`ParsedCommand::warnings`].
+    #[inline]
+    pub fn deprecated(mut self, note: &'static str) -> Self {
+        self.deprecated = Some(note);
+        self
+    }
+
+    /// Marks the value of this argument as secret (e.g. a password or a token).
+    ///
+    /// Its default value is not shown in the help page nor in the configuration template, and
+    /// its value is replaced with `***` in [`ParsedCommand::raw`], in errors and in the
+    /// [`Debug`](fmt::Debug) output of [`Arg`] and [`ArgList`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .add_arg(Arg::new(arg!(--token), value!(string), "Insert a token.").sensitive())
+    ///     .parse_from_str("--token hunter2")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.get(arg!(--token)).string(), Some("hunter2"));
+    /// assert_eq!(parsed.raw, ["--token", "***"]);
+    /// ```
+    #[inline]
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+
+    /// Specifies whether or not the whitespace around each value of this argument is removed
+    /// before it is parsed. By default it is not.
+    ///
+    /// Each value of a list with a delimiter is trimmed on its own (e.g. `--tag "a, b"`).
+    #[inline]
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Specifies whether or not the empty string is an acceptable value (e.g. `--name ""`).
+    /// By default it is.
+    ///
+    /// An empty value is an [`ErrorKind::InvalidValue`] error otherwise. With [`Arg::trim`] a
+    /// value made of whitespace is empty too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = || {
+    ///     Command::create("myapp", "This is my cool app.").add_arg(
+    ///         Arg::new(arg!(--user), value!(string), "Insert a username.")
+    ///             .trim(true)
+    ///             .allow_empty(false),
+    ///     )
+    /// };
+    /// let parsed = cmd().parse_from_str("--user ' root '").unwrap();
+    /// assert_eq!(parsed.args.try_get(arg!(--user)).unwrap().string(), Some("root"));
+    /// assert!(cmd().parse_from_str("--user ' '").is_err());
+    /// ```
+    #[inline]
+    pub fn allow_empty(mut self, allow_empty: bool) -> Self {
+        self.allow_empty = allow_empty;
+        self
+    }
+
+    /// Specifies whether or not each value of an [`ArgValue::PathList`] is split into multiple
+    /// paths like the `PATH` environment variable (on `:`, or `;` on Windows).
+    /// By default it is not.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// # #[cfg(unix)]
+    /// # {
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .add_arg(
+    ///         Arg::new(arg!(-'I', --include), value!(paths), "Include a directory.")
+    ///             .split_paths(true),
+    ///     )
+    ///     .parse_from_str("-I /usr/include:/opt/include -I /home/me/include")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     parsed.args.get(arg!(-'I')).paths().unwrap(),
+    ///     ["/usr/include", "/opt/include", "/home/me/include"].map(std::path::PathBuf::from)
+    /// );
+    /// # }
+    /// ```
+    #[inline]
+    pub fn split_paths(mut self, split_paths: bool) -> Self {
+        self.split_paths = split_paths;
+        self
+    }
+
+    /// Specifies which values an [`ArgValue::Float`] accepts.
+    /// By default every value is accepted ([`FloatPolicy::ANY`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = || {
+    ///     Command::create("myapp", "This is my cool app.").add_arg(
+    ///         Arg::new(arg!(--ratio), value!(float), "Insert a ratio.")
+    ///             .float_policy(FloatPolicy::FINITE),
+    ///     )
+    /// };
+    /// assert!(cmd().parse_from_str("--ratio NaN").is_err());
+    /// assert!(cmd().parse_from_str("--ratio 0.5").is_ok());
+    /// ```
+    #[inline]
+    pub fn float_policy(mut self, float_policy: FloatPolicy) -> Self {
+        self.float_policy = float_policy;
+        self
+    }
+
+    /// Makes this flag negatable: `--no-<name>` is accepted too and switches it off (e.g.
+    /// `--no-color` for `--color`), so that a flag enabled by default (e.g. in a configuration
+    /// file) can be disabled from the command line. The last one that is used wins, see
+    /// [`Arg::enabled`].
+    ///
+    /// Switching the flag off resets its counter to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = || {
+    ///     Command::create("myapp", "This is my cool app.")
+    ///         .add_arg(Arg::new(arg!(--color), value!(), "Colors the output.").negatable())
+    /// };
+    /// let parsed = cmd().parse_from_str("--color --no-color").unwrap();
+    /// assert!(!parsed.args.enabled_or(arg!(--color), true));
+    /// let parsed = cmd().parse_from_str("").unwrap();
+    /// assert!(parsed.args.enabled_or(arg!(--color), true));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the argument is not an [`ArgValue::Flag`] or if it has no long name.
+    pub fn negatable(mut self) -> Self {
+        if self.argvalue != ArgValue::Flag || matches!(self.argname, ArgName::Short(_)) {
+            panic!(
+                "The argument '{}' can only be negatable if it is a flag with a long name",
+                self.argname
+            );
+        }
+        self.negatable = true;
+        self
+    }
+
+    /// Specifies how many values this list takes each time it is used (e.g. `2..=2` for
+    /// `--map SRC DST`). By default it takes one.
+    ///
+    /// The values after the first one are taken greedily until the maximum is reached or until
+    /// a token starts with a dash. Giving fewer values than the minimum is an error. The values
+    /// given each time are read together with [`Arg::groups`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .add_arg(Arg::new(arg!(--map), value!(list), "Maps a path.").num_values(2..=2))
+    ///     .add_arg(Arg::new(arg!(--points), value!(list), "Some points.").num_values(2..=4))
+    ///     .parse_from_str("--map a b --map c d --points 1 2 3")
+    ///     .unwrap();
+    /// let map = parsed.args.try_get(arg!(--map)).unwrap();
+    /// assert_eq!(map.groups(), [["a", "b"], ["c", "d"]]);
+    /// assert_eq!(map.list().unwrap(), ["a", "b", "c", "d"]);
+    /// let points = parsed.args.try_get(arg!(--points)).unwrap();
+    /// assert_eq!(points.groups(), [["1", "2", "3"]]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the argument is not an [`ArgValue::List`], if the range is empty or starts
+    /// from `0`, or if the list has a delimiter (see [`Arg::delimiter`]).
+    pub fn num_values(mut self, num_values: RangeInclusive<usize>) -> Self {
+        if !matches!(self.argvalue, ArgValue::List(_))
+            || num_values.is_empty()
+            || *num_values.start() == 0
+            || self.delimiter.is_some()
+        {
+            panic!(
+                "The argument '{}' can only take a non-empty range of values if it is a list",
+                self.argname
+            );
+        }
+        self.num_values = Some(num_values);
+        self
+    }
+
+    /// Specifies the character each value of this list is split on (e.g. `,` to accept
+    /// `--tag a,b`). By default values are not split.
+    ///
+    /// The values are merged with the ones given the other times the argument was used, so
+    /// [`Arg::list`] returns all of them (see [`Arg::merge`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .add_arg(Arg::new(arg!(--tag), value!(list), "Adds a tag.").delimiter(','))
+    ///     .parse_from_str("--tag a,b --tag c")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.try_get(arg!(--tag)).unwrap().list().unwrap(), ["a", "b", "c"]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the argument is not an [`ArgValue::List`] or if it takes more values each
+    /// time (see [`Arg::num_values`]).
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        if !matches!(self.argvalue, ArgValue::List(_)) || self.num_values.is_some() {
+            panic!(
+                "The argument '{}' can only split its values if it is a list that takes one value",
+                self.argname
+            );
+        }
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Specifies what giving the same value more than once to this list means, both in a single
+    /// value split by [`Arg::delimiter`] or [`Arg::split_paths`] and across the times the
+    /// argument was used. By default every value is kept ([`MergePolicy::Append`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = |merge| {
+    ///     Command::create("myapp", "This is my cool app.").add_arg(
+    ///         Arg::new(arg!(--tag), value!(list), "Adds a tag.")
+    ///             .delimiter(',')
+    ///             .merge(merge),
+    ///     )
+    /// };
+    /// let parsed = cmd(MergePolicy::Dedupe).parse_from_str("--tag a,b --tag b,c").unwrap();
+    /// assert_eq!(parsed.args.try_get(arg!(--tag)).unwrap().list().unwrap(), ["a", "b", "c"]);
+    /// assert!(cmd(MergePolicy::Error).parse_from_str("--tag a,b --tag b,c").is_err());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the argument is not an [`ArgValue::List`] or an [`ArgValue::PathList`].
+    pub fn merge(mut self, merge: MergePolicy) -> Self {
+        if !matches!(self.argvalue, ArgValue::List(_) | ArgValue::PathList(_)) {
+            panic!(
+                "The argument '{}' can only merge values if it is a list",
+                self.argname
+            );
+        }
+        self.merge = merge;
+        self
+    }
+
+    /// Specifies what using this argument more than once means.
+    ///
+    /// # Panic
+    ///
+    /// Panics if [`DuplicatePolicy::Append`] is used on a value that is not an
+    /// [`ArgValue::List`] or an [`ArgValue::PathList`].
+    pub fn duplicates(mut self, duplicates: DuplicatePolicy) -> Self {
+        if duplicates == DuplicatePolicy::Append
+            && !matches!(self.argvalue, ArgValue::List(_) | ArgValue::PathList(_))
+        {
+            panic!(
+                "The argument '{}' can only append values if it is a list",
+                self.argname
+            );
+        }
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Returns the [`String`] value of the argument.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::String`]
+    /// or an [`ArgValue::Choice`] it returns [`None`].
+    pub fn string(&self) -> Option<&str> {
+        self.mark_used();
+        match &self.argvalue {
+            ArgValue::String(Some(value)) | ArgValue::Choice(_, Some(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`i64`] value of the argument.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Num`]
+    /// or an [`ArgValue::Range`] it returns [`None`].
+    pub fn num(&self) -> Option<i64> {
+        self.mark_used();
+        match self.argvalue {
+            ArgValue::Num(Some(value)) | ArgValue::Range(_, Some(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`f64`] value of the argument.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Float`]
+    /// it returns [`None`].
     pub fn float(&self) -> Option<f64> {
+        self.mark_used();
         if let ArgValue::Float(Some(value)) = self.argvalue {
             Some(value)
         } else {
@@ -307,291 +1571,3320 @@ impl Arg {
         }
     }
 
-    /// Returns the [`PathBuf`] value of the argument.
+    /// Returns the ratio (between `0` and `1`) of the argument.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Ratio`]
+    /// it returns [`None`].
+    pub fn ratio(&self) -> Option<f64> {
+        self.mark_used();
+        if let ArgValue::Ratio(Some(value)) = self.argvalue {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`PathBuf`] value of the argument.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Path`]
+    /// or an [`ArgValue::ExistingPath`] it returns [`None`].
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.mark_used();
+        match &self.argvalue {
+            ArgValue::Path(Some(value)) | ArgValue::ExistingPath(Some(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the list of [`String`]s of the argument.
+    ///
+    /// If it is not an [`ArgValue::List`] it returns [`None`].
+    pub fn list(&self) -> Option<&[String]> {
+        self.mark_used();
+        if let ArgValue::List(values) = &self.argvalue {
+            Some(values)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the values of an [`ArgValue::List`] grouped by the time they were given (see
+    /// [`Arg::num_values`]). The default values are a single group.
+    ///
+    /// If it is not an [`ArgValue::List`] it returns an empty [`Vec`].
+    pub fn groups(&self) -> Vec<&[String]> {
+        self.mark_used();
+        self.grouped()
+    }
+
+    // Like `Arg::groups`, but it does not mark the argument as read.
+    fn grouped(&self) -> Vec<&[String]> {
+        let ArgValue::List(values) = &self.argvalue else {
+            return Vec::new();
+        };
+        if self.sizes.is_empty() {
+            return [&values[..]]
+                .into_iter()
+                .filter(|v| !v.is_empty())
+                .collect();
+        }
+        let mut groups = Vec::new();
+        let mut rest = &values[..];
+        for &size in &self.sizes {
+            let (group, next) = rest.split_at(size.min(rest.len()));
+            groups.push(group);
+            rest = next;
+        }
+        groups
+    }
+
+    /// Returns the list of [`PathBuf`]s of the argument.
+    ///
+    /// If it is not an [`ArgValue::PathList`] it returns [`None`].
+    pub fn paths(&self) -> Option<&[PathBuf]> {
+        self.mark_used();
+        if let ArgValue::PathList(values) = &self.argvalue {
+            Some(values)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the raw bytes of the argument.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Bytes`]
+    /// it returns [`None`].
+    pub fn bytes_raw(&self) -> Option<&[u8]> {
+        self.mark_used();
+        if let ArgValue::Bytes(Some(value)) = &self.argvalue {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`Version`] value of the argument. Requires the `semver` feature.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Other`]
+    /// holding an `Option<Version>` it returns [`None`].
+    #[cfg(feature = "semver")]
+    pub fn version(&self) -> Option<&Version> {
+        self.other::<Option<Version>>()?.as_ref()
+    }
+
+    /// Returns the [`VersionReq`] value of the argument. Requires the `semver` feature.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Other`]
+    /// holding an `Option<VersionReq>` it returns [`None`].
+    #[cfg(feature = "semver")]
+    pub fn version_req(&self) -> Option<&VersionReq> {
+        self.other::<Option<VersionReq>>()?.as_ref()
+    }
+
+    /// Returns the [`Uuid`] value of the argument. Requires the `uuid` feature.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Other`]
+    /// holding an `Option<Uuid>` it returns [`None`].
+    #[cfg(feature = "uuid")]
+    pub fn uuid(&self) -> Option<Uuid> {
+        *self.other::<Option<Uuid>>()?
+    }
+
+    /// Returns the [`Glob`] pattern of the argument. Requires the `glob` feature.
+    ///
+    /// If no value (not even default) was specified or if it is not an [`ArgValue::Other`]
+    /// holding an `Option<Glob>` it returns [`None`].
+    #[cfg(feature = "glob")]
+    pub fn glob(&self) -> Option<&Glob> {
+        self.other::<Option<Glob>>()?.as_ref()
+    }
+
+    /// Returns the value of the argument if it is an [`ArgValue::Other`] of the given type,
+    /// otherwise it returns [`None`].
+    pub fn other<T: ArgValueKind>(&self) -> Option<&T> {
+        self.mark_used();
+        if let ArgValue::Other(value) = &self.argvalue {
+            (&**value as &dyn Any).downcast_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`usize`] value of the argument.
+    ///
+    /// If it is not an [`ArgValue::Count`] it returns [`None`].
+    pub fn count_value(&self) -> Option<usize> {
+        self.mark_used();
+        if let ArgValue::Count(value) = self.argvalue {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the kind of value this argument carries.
+    pub fn value_kind(&self) -> ValueKind {
+        match self.argvalue {
+            ArgValue::String(_) => ValueKind::String,
+            ArgValue::Num(_) => ValueKind::Num,
+            ArgValue::Float(_) => ValueKind::Float,
+            ArgValue::Path(_) => ValueKind::Path,
+            ArgValue::Flag => ValueKind::Flag,
+            ArgValue::Choice(..) => ValueKind::Choice,
+            ArgValue::Range(..) => ValueKind::Range,
+            ArgValue::List(_) => ValueKind::List,
+            ArgValue::Count(_) => ValueKind::Count,
+            ArgValue::PathList(_) => ValueKind::PathList,
+            ArgValue::Bytes(_) => ValueKind::Bytes,
+            ArgValue::ExistingPath(_) => ValueKind::ExistingPath,
+            ArgValue::Ratio(_) => ValueKind::Ratio,
+            ArgValue::Other(_) => ValueKind::Other,
+        }
+    }
+
+    // Checks that the argument carries one of the kinds of value read by an accessor.
+    fn expect(&self, expected: &'static [ValueKind]) -> Result<(), WrongKind> {
+        let found = self.value_kind();
+        if expected.contains(&found) {
+            Ok(())
+        } else {
+            Err(WrongKind {
+                arg: self.argname.clone(),
+                expected,
+                found,
+            })
+        }
+    }
+
+    /// Like [`Arg::string`], but it returns an error if the argument is not an
+    /// [`ArgValue::String`] nor an [`ArgValue::Choice`], so that using the wrong accessor is not
+    /// mistaken for a missing value.
+    pub fn expect_string(&self) -> Result<Option<&str>, WrongKind> {
+        self.expect(&[ValueKind::String, ValueKind::Choice])?;
+        Ok(self.string())
+    }
+
+    /// Like [`Arg::num`], but it returns an error if the argument is not an [`ArgValue::Num`]
+    /// nor an [`ArgValue::Range`], so that using the wrong accessor is not mistaken for a
+    /// missing value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--num), value!(num), "Insert a number.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .parse_from_str("--name me")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.try_get(arg!(--num)).unwrap().expect_num(), Ok(None));
+    /// let err = parsed.args.try_get(arg!(--name)).unwrap().expect_num().unwrap_err();
+    /// assert_eq!(err.found, ValueKind::String);
+    /// assert_eq!(err.to_string(), "'--name' carries a string, not a number nor a range");
+    /// ```
+    pub fn expect_num(&self) -> Result<Option<i64>, WrongKind> {
+        self.expect(&[ValueKind::Num, ValueKind::Range])?;
+        Ok(self.num())
+    }
+
+    /// Like [`Arg::float`], but it returns an error if the argument is not an
+    /// [`ArgValue::Float`], so that using the wrong accessor is not mistaken for a missing
+    /// value.
+    pub fn expect_float(&self) -> Result<Option<f64>, WrongKind> {
+        self.expect(&[ValueKind::Float])?;
+        Ok(self.float())
+    }
+
+    /// Like [`Arg::ratio`], but it returns an error if the argument is not an
+    /// [`ArgValue::Ratio`], so that using the wrong accessor is not mistaken for a missing
+    /// value.
+    pub fn expect_ratio(&self) -> Result<Option<f64>, WrongKind> {
+        self.expect(&[ValueKind::Ratio])?;
+        Ok(self.ratio())
+    }
+
+    /// Like [`Arg::path`], but it returns an error if the argument is not an
+    /// [`ArgValue::Path`] nor an [`ArgValue::ExistingPath`], so that using the wrong accessor is
+    /// not mistaken for a missing value.
+    pub fn expect_path(&self) -> Result<Option<&PathBuf>, WrongKind> {
+        self.expect(&[ValueKind::Path, ValueKind::ExistingPath])?;
+        Ok(self.path())
+    }
+
+    /// Like [`Arg::list`], but it returns an error if the argument is not an
+    /// [`ArgValue::List`].
+    pub fn expect_list(&self) -> Result<&[String], WrongKind> {
+        self.expect(&[ValueKind::List])?;
+        Ok(self.list().unwrap_or_default())
+    }
+
+    /// Like [`Arg::paths`], but it returns an error if the argument is not an
+    /// [`ArgValue::PathList`].
+    pub fn expect_paths(&self) -> Result<&[PathBuf], WrongKind> {
+        self.expect(&[ValueKind::PathList])?;
+        Ok(self.paths().unwrap_or_default())
+    }
+
+    /// Like [`Arg::bytes_raw`], but it returns an error if the argument is not an
+    /// [`ArgValue::Bytes`], so that using the wrong accessor is not mistaken for a missing
+    /// value.
+    pub fn expect_bytes(&self) -> Result<Option<&[u8]>, WrongKind> {
+        self.expect(&[ValueKind::Bytes])?;
+        Ok(self.bytes_raw())
+    }
+
+    /// Like [`Arg::count_value`], but it returns an error if the argument is not an
+    /// [`ArgValue::Count`].
+    pub fn expect_count(&self) -> Result<usize, WrongKind> {
+        self.expect(&[ValueKind::Count])?;
+        Ok(self.count_value().unwrap_or_default())
+    }
+
+    /// Returns whether this flag was switched on (`Some(true)`) or off with its negation
+    /// (`Some(false)`) by the last one of them that was used, or [`None`] if it was not used.
+    /// See [`Arg::negatable`].
+    ///
+    /// If it is not an [`ArgValue::Flag`] it returns [`None`].
+    pub fn enabled(&self) -> Option<bool> {
+        self.mark_used();
+        self.enabled
+    }
+
+    /// Marks the argument as read by the application, so that [`ParsedCommand::finish`] does not
+    /// report it.
+    ///
+    /// The accessors (e.g. [`Arg::string`] or [`ArgList::try_count`]) mark the argument on their
+    /// own, but reading a public field like [`Arg::counter`] does not.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--dry-run), value!(), "Does nothing.")
+    ///     .parse_from_str("--dry-run")
+    ///     .unwrap();
+    /// let dry_run = parsed.args.get(arg!(--dry-run));
+    /// if dry_run.counter > 0 {
+    ///     dry_run.mark_used();
+    /// }
+    /// assert!(dry_run.is_used());
+    /// ```
+    #[inline]
+    pub fn mark_used(&self) {
+        self.used.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the application read the argument. See [`Arg::mark_used`].
+    #[inline]
+    pub fn is_used(&self) -> bool {
+        self.used.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns where the value of this argument comes from. If the argument was used more than
+    /// once it is where the last value was given.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// std::env::set_var("MYAPP_OPTS", "--port 9090");
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--host), value!(string, "localhost"), "Insert a host.")
+    ///     .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+    ///     .arg(arg!(--verbose), value!(), "Be verbose.")
+    ///     .args_env("MYAPP_OPTS")
+    ///     .parse_from_str("--verbose")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.get(arg!(--host)).source(), ValueSource::Default);
+    /// assert_eq!(parsed.args.get(arg!(--port)).source(), ValueSource::Env("MYAPP_OPTS"));
+    /// assert_eq!(parsed.args.get(arg!(--verbose)).source(), ValueSource::CommandLine);
+    /// ```
+    #[inline]
+    pub fn source(&self) -> ValueSource {
+        self.source
+    }
+
+    /// Returns the value this argument was created with, before parsing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+    ///     .parse_from_str("--port 9090")
+    ///     .unwrap();
+    /// let port = parsed.args.get(arg!(--port));
+    /// assert_eq!(port.default_value(), &ArgValue::Num(Some(8080)));
+    /// assert_eq!(port.num(), Some(9090));
+    /// ```
+    #[inline]
+    pub fn default_value(&self) -> &ArgValue {
+        &self.default
+    }
+
+    /// Returns the current value as it should be shown to the user (see [`ArgValue::display`]),
+    /// or [`None`] if there is none. Flags are `set` or `unset` and the values of sensitive
+    /// arguments given by the user are replaced with `***` (see [`Arg::sensitive`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--quota), value!(ratio, 0.5), "Insert a quota.")
+    ///     .arg(arg!(--dry-run), value!(), "Does nothing.")
+    ///     .parse_from_str("--dry-run")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.get(arg!(--quota)).display().unwrap(), "50%");
+    /// assert_eq!(parsed.args.get(arg!(--dry-run)).display().unwrap(), "set");
+    /// ```
+    pub fn display(&self) -> Option<String> {
+        match self.argvalue {
+            ArgValue::Flag if self.enabled == Some(true) => Some("set".into()),
+            ArgValue::Flag => Some("unset".into()),
+            _ if self.sensitive && self.counter > 0 => Some("***".into()),
+            _ => self.argvalue.display(),
+        }
+    }
+
+    // Returns the tokens that give this argument its current value, or none if it was not used.
+    fn tokens(&self) -> Vec<String> {
+        let name = match &self.argname {
+            ArgName::Short(short) => format!("-{short}"),
+            ArgName::Long(long) | ArgName::Both { long, .. } => format!("--{long}"),
+        };
+        let values: Vec<String> = match &self.argvalue {
+            // Only negatable flags have `--no-<name>`, the others were switched off with `+`.
+            ArgValue::Flag if self.enabled == Some(false) => match &self.argname {
+                ArgName::Short(short) | ArgName::Both { short, .. } if !self.negatable => {
+                    return vec![format!("+{short}")]
+                }
+                _ => return vec![format!("--no-{}", &name[2..])],
+            },
+            ArgValue::Flag | ArgValue::Count(_) => return vec![name; self.counter],
+            _ if self.counter == 0 => return Vec::new(),
+            ArgValue::List(_) if self.num_values.is_some() => {
+                return self
+                    .grouped()
+                    .into_iter()
+                    .flat_map(|group| iter::once(name.clone()).chain(group.iter().cloned()))
+                    .collect()
+            }
+            _ => self.values(),
+        };
+        values
+            .into_iter()
+            .flat_map(|value| [name.clone(), value])
+            .collect()
+    }
+
+    // Returns the current value as it would be given on the command line, or every value of a
+    // list. Flags have none.
+    fn values(&self) -> Vec<String> {
+        match &self.argvalue {
+            ArgValue::Flag | ArgValue::Count(_) => Vec::new(),
+            ArgValue::List(values) => values.clone(),
+            ArgValue::PathList(paths) => paths
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect(),
+            ArgValue::String(value) | ArgValue::Choice(_, value) => value.iter().cloned().collect(),
+            ArgValue::Num(value) | ArgValue::Range(_, value) => {
+                value.iter().map(i64::to_string).collect()
+            }
+            ArgValue::Float(value) | ArgValue::Ratio(value) => {
+                value.iter().map(f64::to_string).collect()
+            }
+            ArgValue::Path(value) | ArgValue::ExistingPath(value) => value
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect(),
+            ArgValue::Bytes(value) => value.iter().map(|b| encoding::encode(b)).collect(),
+            ArgValue::Other(value) => value.default_display().into_iter().collect(),
+        }
+    }
+
+    fn invalid_value(&self, value: String, reason: impl fmt::Display) -> ErrorKind {
+        ErrorKind::InvalidValue {
+            arg: self.argname.clone(),
+            value: if self.sensitive { "***".into() } else { value },
+            reason: reason.to_string(),
+        }
+    }
+
+    // Trims the value if asked, returning whether or not it is acceptable.
+    fn normalize(value: &mut String, trim: bool, allow_empty: bool) -> bool {
+        if trim {
+            let trimmed = value.trim();
+            if trimmed.len() != value.len() {
+                *value = trimmed.to_string();
+            }
+        }
+        allow_empty || !value.is_empty()
+    }
+
+    fn init(&mut self, input: &mut VecDeque<String>) -> Result<(), ErrorKind> {
+        let used = self.counter > 0 || self.enabled.is_some();
+        if used && self.duplicates == DuplicatePolicy::Error {
+            return Err(ErrorKind::Duplicate(self.argname.clone()));
+        }
+        if input.is_empty() && !matches!(self.argvalue, ArgValue::Flag | ArgValue::Count(_)) {
+            return Err(ErrorKind::MissingValue(self.argname.clone()));
+        }
+        // Lists check each of their values once they are split.
+        if !matches!(
+            self.argvalue,
+            ArgValue::Flag | ArgValue::Count(_) | ArgValue::List(_)
+        ) {
+            let value = input.front_mut().expect("The argument has a value");
+            if !Self::normalize(value, self.trim, self.allow_empty) {
+                let value = input.pop_front().expect("The argument has a value");
+                return Err(self.invalid_value(value, "non-empty"));
+            }
+        }
+        // Counters add up rather than discard their value.
+        let counted = matches!(self.argvalue, ArgValue::Count(_));
+        if self.counter > 0 && self.duplicates == DuplicatePolicy::Overwrite && !counted {
+            let values = self.argvalue.items();
+            self.overwritten.extend(values);
+        }
+        let mut next = || input.pop_front().expect("The argument has a value");
+        match &mut self.argvalue {
+            ArgValue::String(_) => self.argvalue = ArgValue::String(Some(next())),
+            ArgValue::Num(_) => {
+                let input = next();
+                match input.parse() {
+                    Ok(value) => self.argvalue = ArgValue::Num(Some(value)),
+                    Err(e) => return Err(self.invalid_value(input, format!("a valid number: {e}"))),
+                }
+            }
+            ArgValue::Float(_) => {
+                let input = next();
+                match self.float_policy.parse(&input) {
+                    Ok(value) => self.argvalue = ArgValue::Float(Some(value)),
+                    Err(reason) => return Err(self.invalid_value(input, reason)),
+                }
+            }
+            ArgValue::Ratio(value) => {
+                let input = next();
+                match ratio(&input) {
+                    Ok(ratio) => *value = Some(ratio),
+                    Err(reason) => return Err(self.invalid_value(input, reason)),
+                }
+            }
+            ArgValue::Path(_) => self.argvalue = ArgValue::Path(Some(PathBuf::from(next()))),
+            ArgValue::ExistingPath(value) => {
+                let input = next();
+                let path = PathBuf::from(&input);
+                if !path.exists() {
+                    return Err(self.invalid_value(input, "an existing path"));
+                }
+                value.replace(path);
+            }
+            ArgValue::Choice(choices, value) => {
+                let input = next();
+                if !choices.contains(&input.as_str()) {
+                    let reason = format!("one of: {}", choices.join(", "));
+                    return Err(self.invalid_value(input, reason));
+                }
+                value.replace(input);
+            }
+            ArgValue::Range(range, value) => {
+                let input = next();
+                let parsed: i64 = match input.parse() {
+                    Ok(parsed) => parsed,
+                    Err(e) => return Err(self.invalid_value(input, format!("a valid number: {e}"))),
+                };
+                if !range.contains(&parsed) {
+                    let reason = format!("between {} and {}", range.start(), range.end());
+                    return Err(self.invalid_value(input, reason));
+                }
+                value.replace(parsed);
+            }
+            ArgValue::List(values) => {
+                // Default values and overwritten ones are discarded.
+                if self.counter == 0 || self.duplicates != DuplicatePolicy::Append {
+                    values.clear();
+                    self.sizes.clear();
+                }
+                // The values after the first one are taken until the next argument.
+                let (min, max) = self
+                    .num_values
+                    .as_ref()
+                    .map_or((1, 1), |range| (*range.start(), *range.end()));
+                let mut group = vec![input.pop_front().expect("The argument has a value")];
+                while group.len() < max && input.front().is_some_and(|v| !v.starts_with('-')) {
+                    group.extend(input.pop_front());
+                }
+                if group.len() < min {
+                    let reason = if min == max {
+                        format!("exactly {min} values")
+                    } else {
+                        format!("at least {min} values")
+                    };
+                    return Err(self.invalid_value(group.join(" "), reason));
+                }
+                let mut group: Vec<String> = match self.delimiter {
+                    Some(delimiter) => group[0].split(delimiter).map(String::from).collect(),
+                    None => group,
+                };
+                let (trim, allow_empty) = (self.trim, self.allow_empty);
+                for value in &mut group {
+                    if !Self::normalize(value, trim, allow_empty) {
+                        let value = mem::take(value);
+                        return Err(self.invalid_value(value, "non-empty"));
+                    }
+                }
+                match self.merge.merge(values, group) {
+                    Ok(added) => self.sizes.push(added),
+                    Err(value) => return Err(self.invalid_value(value, "a value not given yet")),
+                }
+            }
+            ArgValue::PathList(values) => {
+                if self.counter == 0 || self.duplicates != DuplicatePolicy::Append {
+                    values.clear();
+                }
+                let input = next();
+                let paths = if self.split_paths {
+                    env::split_paths(&input).collect()
+                } else {
+                    vec![PathBuf::from(input)]
+                };
+                if let Err(path) = self.merge.merge(values, paths) {
+                    let value = path.to_string_lossy().into_owned();
+                    return Err(self.invalid_value(value, "a value not given yet"));
+                }
+            }
+            ArgValue::Bytes(value) => {
+                let input = next();
+                match encoding::decode(&input) {
+                    Ok(bytes) => *value = Some(bytes),
+                    Err(e) => return Err(self.invalid_value(input, format!("base64 or hex: {e}"))),
+                }
+            }
+            ArgValue::Other(value) => {
+                let input = next();
+                if let Err(reason) = value.parse(&input) {
+                    return Err(self.invalid_value(input, reason));
+                }
+            }
+            ArgValue::Flag => self.enabled = Some(true),
+            ArgValue::Count(value) => *value += 1,
+        }
+        self.counter += 1;
+        Ok(())
+    }
+
+    // Switches the flag off, see `Arg::negatable`. A negation cannot have a value.
+    fn negate(&mut self, value: Option<String>) -> Result<(), ErrorKind> {
+        if let Some(value) = value {
+            return Err(self.invalid_value(value, "omitted"));
+        }
+        let used = self.counter > 0 || self.enabled.is_some();
+        if used && self.duplicates == DuplicatePolicy::Error {
+            return Err(ErrorKind::Duplicate(self.argname.clone()));
+        }
+        self.enabled = Some(false);
+        self.counter = 0;
+        Ok(())
+    }
+}
+
+// The value of sensitive arguments is printed as `***`, so parsed arguments can be logged.
+impl fmt::Debug for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("Arg");
+        debug.field("argname", &self.argname);
+        if self.sensitive {
+            debug.field("argvalue", &format_args!("***"));
+        } else {
+            debug.field("argvalue", &self.argvalue);
+        }
+        debug
+            .field("description", &self.description)
+            .field("long_description", &self.long_description)
+            .field("counter", &self.counter)
+            .field("duplicates", &self.duplicates)
+            .field("value_name", &self.value_name)
+            .field("example", &self.example)
+            .field("provider", &self.provider)
+            .field("deprecated", &self.deprecated)
+            .field("global", &self.global)
+            .field("placement", &self.placement)
+            .field("split_paths", &self.split_paths)
+            .field("float_policy", &self.float_policy)
+            .field("sensitive", &self.sensitive)
+            .field("trim", &self.trim)
+            .field("allow_empty", &self.allow_empty)
+            .field("value_hint", &self.value_hint)
+            .field("negatable", &self.negatable)
+            .field("num_values", &self.num_values)
+            .field("delimiter", &self.delimiter)
+            .field("merge", &self.merge)
+            .finish()
+    }
+}
+
+/// A list of arguments.
+///
+/// This list is accessible only after the command line arguments have been parsed.
+/// You can access specific arguments with [`ArgList::get`].
+#[repr(transparent)]
+pub struct ArgList {
+    args: Vec<Arg>,
+}
+
+impl fmt::Debug for ArgList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.args).finish()
+    }
+}
+
+impl ArgList {
+    fn new() -> Self {
+        ArgList { args: Vec::new() }
+    }
+
+    fn insert(&mut self, arg: Arg) {
+        if let Some(a) = self.args.iter().find(|a| a.argname.conflicts(&arg.argname)) {
+            panic!(
+                "The arguments '{}' and '{}' share only one name",
+                a.argname, arg.argname
+            );
+        }
+        if self.args.iter().any(|a| a.argname == arg.argname) {
+            panic!(
+                "The argument '{}' already exists in this command",
+                arg.argname
+            );
+        }
+        self.args.push(arg);
+    }
+
+    /// Returns the arguments whose value was given on the command line, even if it is equal to
+    /// the default. Values that come from the environment (see [`Command::args_env`]) or from
+    /// the defaults are not included.
+    ///
+    /// The previous value of each argument can be read with [`Arg::default_value`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--host), value!(string, "localhost"), "Insert a host.")
+    ///     .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+    ///     .parse_from_str("--port 9090")
+    ///     .unwrap();
+    /// for arg in parsed.args.overridden() {
+    ///     println!("{}: {:?} -> {:?}", arg.argname, arg.default_value(), arg.argvalue);
+    /// }
+    /// let overridden: Vec<&ArgName> = parsed.args.overridden().map(|arg| &arg.argname).collect();
+    /// assert_eq!(overridden, [&arg!(--port)]);
+    /// ```
+    pub fn overridden(&self) -> impl Iterator<Item = &Arg> {
+        self.args
+            .iter()
+            .filter(|arg| arg.source == ValueSource::CommandLine)
+    }
+
+    /// Returns the arguments that take no value: [`ArgValue::Flag`] and [`ArgValue::Count`].
+    ///
+    /// See [`ArgList::valued`] for the other ones.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--debug), value!(), "Enables debugging.")
+    ///     .arg(arg!(-'v'), value!(count), "Increases verbosity.")
+    ///     .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+    ///     .parse_from_str("-vv")
+    ///     .unwrap();
+    /// for arg in parsed.args.flags() {
+    ///     println!("{}: {}", arg.argname, arg.counter);
+    /// }
+    /// let flags: Vec<&ArgName> = parsed.args.flags().map(|arg| &arg.argname).collect();
+    /// assert_eq!(flags, [&arg!(--debug), &arg!(-'v')]);
+    /// let valued: Vec<&ArgName> = parsed.args.valued().map(|arg| &arg.argname).collect();
+    /// assert_eq!(valued, [&arg!(--port)]);
+    /// ```
+    pub fn flags(&self) -> impl Iterator<Item = &Arg> {
+        self.args
+            .iter()
+            .filter(|arg| matches!(arg.argvalue, ArgValue::Flag | ArgValue::Count(_)))
+    }
+
+    /// Returns the arguments that take a value, i.e. every argument that is not returned by
+    /// [`ArgList::flags`].
+    pub fn valued(&self) -> impl Iterator<Item = &Arg> {
+        self.args
+            .iter()
+            .filter(|arg| !matches!(arg.argvalue, ArgValue::Flag | ArgValue::Count(_)))
+    }
+
+    /// Returns the arguments whose long name (without the dashes) starts with the given prefix,
+    /// e.g. every `--db-*` argument for `"db-"`. Arguments with only a short name never match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--db-host), value!(string, "localhost"), "Insert the database host.")
+    ///     .arg(arg!(--db-port), value!(num, 5432), "Insert the database port.")
+    ///     .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+    ///     .parse_from_str("")
+    ///     .unwrap();
+    /// let db: Vec<&ArgName> = parsed.args.matching("db-").map(|arg| &arg.argname).collect();
+    /// assert_eq!(db, [&arg!(--db-host), &arg!(--db-port)]);
+    /// ```
+    pub fn matching<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a Arg> {
+        self.args.iter().filter(move |arg| match &arg.argname {
+            ArgName::Long(long) | ArgName::Both { long, .. } => long.starts_with(prefix),
+            ArgName::Short(_) => false,
+        })
+    }
+
+    /// Returns the inner [`Vec`] with parsed [`Arg`]s.
+    pub fn inner(&self) -> &Vec<Arg> {
+        &self.args
+    }
+
+    /// Returns a given argument [`Arg`] by its [`ArgName`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given argument does not exist in the [`Command`]. Use [`ArgList::try_get`]
+    /// when the name may not exist (e.g. when it comes from another crate).
+    #[cfg_attr(
+        feature = "strict",
+        deprecated(note = "panics on unknown names, use `try_get` instead")
+    )]
+    pub fn get(&self, argname: ArgName) -> &Arg {
+        self.try_get(argname.clone())
+            .unwrap_or_else(|| panic!("Argument '{argname}' does not exist"))
+    }
+
+    /// Returns a given argument [`Arg`] by its [`ArgName`], or [`None`] if the argument does not
+    /// exist in the [`Command`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string, "nobody"), "Insert a name.")
+    ///     .parse_from(vec!["myapp".into()])
+    ///     .unwrap();
+    /// let name = parsed.args.try_get(arg!(--name)).and_then(Arg::string);
+    /// assert_eq!(name, Some("nobody"));
+    /// assert!(parsed.args.try_get(arg!(--other)).is_none());
+    /// ```
+    pub fn try_get(&self, argname: ArgName) -> Option<&Arg> {
+        self.args.iter().find(|&arg| arg.argname == argname)
+    }
+
+    /// Returns an argument by its name given as a string, with or without dashes (e.g. `"h"`,
+    /// `"-h"`, `"help"` or `"--help"`). A name of one character without dashes is looked up as
+    /// a short name first and then as a long one.
+    ///
+    /// Returns [`None`] if the argument does not exist or if the name is not valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'n', --num), value!(num), "Insert a number.")
+    ///     .parse_from_str("-n 5")
+    ///     .unwrap();
+    /// for name in ["n", "-n", "num", "--num"] {
+    ///     assert_eq!(parsed.args.get_by_name(name).unwrap().num(), Some(5));
+    /// }
+    /// assert!(parsed.args.get_by_name("-num").is_none());
+    /// ```
+    pub fn get_by_name(&self, name: &str) -> Option<&Arg> {
+        let short = |name: &str| {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(short), None) => ArgName::try_short(short).ok(),
+                _ => None,
+            }
+        };
+        let argname = if let Some(long) = name.strip_prefix("--") {
+            ArgName::try_long(long).ok()
+        } else if let Some(name) = name.strip_prefix('-') {
+            short(name)
+        } else {
+            let found = short(name).and_then(|argname| self.try_get(argname));
+            if found.is_some() {
+                return found;
+            }
+            ArgName::try_long(name).ok()
+        };
+        self.try_get(argname?)
+    }
+
+    /// Returns the [`String`] value of the given argument, or `fallback` if it has no value or
+    /// if it does not exist in the [`Command`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .parse_from(vec!["myapp".into()])
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.string_or(arg!(--name), "nobody"), "nobody");
+    /// assert_eq!(parsed.args.num_or(arg!(--num), 42), 42);
+    /// ```
+    pub fn string_or<'a>(&'a self, argname: ArgName, fallback: &'a str) -> &'a str {
+        self.try_get(argname)
+            .and_then(Arg::string)
+            .unwrap_or(fallback)
+    }
+
+    /// Returns the [`i64`] value of the given argument, or `fallback` if it has no value or if it
+    /// does not exist in the [`Command`].
+    pub fn num_or(&self, argname: ArgName, fallback: i64) -> i64 {
+        self.try_get(argname).and_then(Arg::num).unwrap_or(fallback)
+    }
+
+    /// Returns the [`f64`] value of the given argument, or `fallback` if it has no value or if it
+    /// does not exist in the [`Command`].
+    pub fn float_or(&self, argname: ArgName, fallback: f64) -> f64 {
+        self.try_get(argname)
+            .and_then(Arg::float)
+            .unwrap_or(fallback)
+    }
+
+    /// Returns the [`Path`] value of the given argument, or `fallback` if it has no value or if it
+    /// does not exist in the [`Command`].
+    pub fn path_or<'a>(&'a self, argname: ArgName, fallback: &'a Path) -> &'a Path {
+        self.try_get(argname)
+            .and_then(Arg::path)
+            .map_or(fallback, PathBuf::as_path)
+    }
+
+    /// Returns whether the given flag was switched on or off (see [`Arg::enabled`]), or
+    /// `fallback` if it was not used or if it does not exist in the [`Command`].
+    pub fn enabled_or(&self, argname: ArgName, fallback: bool) -> bool {
+        self.try_get(argname)
+            .and_then(Arg::enabled)
+            .unwrap_or(fallback)
+    }
+
+    /// Replaces the value of the given argument with a value computed elsewhere (e.g. from a
+    /// configuration file), so that it can be read like any other parsed value.
+    ///
+    /// The argument's counter is not changed, while its source becomes
+    /// [`ValueSource::Override`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given argument does not exist in the [`Command`] or if the new value is not
+    /// of the same kind of the argument's value. See [`ArgList::try_set`].
+    #[cfg_attr(
+        feature = "strict",
+        deprecated(note = "panics on unknown names, use `try_set` instead")
+    )]
+    pub fn set(&mut self, argname: ArgName, argvalue: ArgValue) {
+        if let Err(e) = self.try_set(argname, argvalue) {
+            panic!("{e}");
+        }
+    }
+
+    /// Replaces the value of the given argument like [`ArgList::set`], but returns an error
+    /// message instead of panicking if the argument does not exist in the [`Command`] or if the
+    /// new value is not of the same kind of the argument's value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let mut parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+    ///     .parse_from(vec!["myapp".into()])
+    ///     .unwrap();
+    /// assert!(parsed.args.try_set(arg!(--port), value!(num, 9090)).is_ok());
+    /// assert!(parsed.args.try_set(arg!(--port), value!(string, "9090")).is_err());
+    /// assert!(parsed.args.try_set(arg!(--host), value!(string, "localhost")).is_err());
+    /// assert_eq!(parsed.args.num_or(arg!(--port), 0), 9090);
+    /// ```
+    pub fn try_set(&mut self, argname: ArgName, argvalue: ArgValue) -> Result<(), String> {
+        let arg = self
+            .args
+            .iter_mut()
+            .find(|arg| arg.argname == argname)
+            .ok_or_else(|| format!("Argument '{argname}' does not exist"))?;
+        if mem::discriminant(&arg.argvalue) != mem::discriminant(&argvalue) {
+            return Err(format!(
+                "Argument '{argname}' cannot be set to a different kind of value"
+            ));
+        }
+        arg.argvalue = argvalue;
+        arg.source = ValueSource::Override;
+        Ok(())
+    }
+
+    /// Removes the given argument from the list and returns its value, or [`None`] if the
+    /// argument does not exist in the [`Command`] (or was already taken).
+    ///
+    /// Unlike the accessors of [`Arg`], which borrow the value, it moves it out of the list, so
+    /// big values (e.g. an embedded JSON payload) are not copied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let mut parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--payload), value!(string), "Insert a JSON payload.")
+    ///     .parse_from_str("--payload '{\"id\": 1}'")
+    ///     .unwrap();
+    /// let payload = parsed.args.take(arg!(--payload));
+    /// assert_eq!(payload, Some(value!(string, "{\"id\": 1}")));
+    /// assert!(parsed.args.try_get(arg!(--payload)).is_none());
+    /// assert!(parsed.args.take(arg!(--payload)).is_none());
+    /// ```
+    pub fn take(&mut self, argname: ArgName) -> Option<ArgValue> {
+        let i = self.args.iter().position(|arg| arg.argname == argname)?;
+        Some(self.args.remove(i).argvalue)
+    }
+
+    /// Consumes the list and returns the name and the value of each argument, in the order they
+    /// were added to the [`Command`]. Like [`ArgList::take`], the values are moved, not copied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .arg(arg!(--num), value!(num, 3), "Insert a number.")
+    ///     .parse_from_str("--name alice")
+    ///     .unwrap();
+    /// let values: Vec<(ArgName, ArgValue)> = parsed.args.into_values().collect();
+    /// assert_eq!(
+    ///     values,
+    ///     [
+    ///         (arg!(--name), value!(string, "alice")),
+    ///         (arg!(--num), value!(num, 3))
+    ///     ]
+    /// );
+    /// ```
+    pub fn into_values(self) -> impl Iterator<Item = (ArgName, ArgValue)> {
+        self.args.into_iter().map(|arg| (arg.argname, arg.argvalue))
+    }
+
+    /// Counts how many arguments were inserted by the user.
+    pub fn total_count(&self) -> usize {
+        let mut count: usize = 0;
+        for arg in &self.args {
+            count += arg.counter;
+        }
+        count
+    }
+
+    /// Checks how many times the argument has been inserted (`0` if none). Usually useful for flags.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given `argname` does not exist in the [`Command`]. See
+    /// [`ArgList::try_count`].
+    #[cfg_attr(
+        feature = "strict",
+        deprecated(note = "panics on unknown names, use `try_count` instead")
+    )]
+    pub fn count(&self, argname: ArgName) -> usize {
+        self.try_count(argname.clone())
+            .unwrap_or_else(|| panic!("Flag '{argname}' does not exist"))
+    }
+
+    /// Checks how many times the argument has been inserted (`0` if none), or returns [`None`] if
+    /// the argument does not exist in the [`Command`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'v'), value!(count), "Increases verbosity.")
+    ///     .parse_from_str("-vvv")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.try_count(arg!(-'v')), Some(3));
+    /// assert_eq!(parsed.args.try_count(arg!(-'q')), None);
+    /// ```
+    pub fn try_count(&self, argname: ArgName) -> Option<usize> {
+        let arg = self.try_get(argname)?;
+        arg.mark_used();
+        Some(arg.counter)
+    }
+
+    // Returns the given long name, sharing the argument's own name when it has it, so that
+    // parsing does not copy the names that are known.
+    fn long_name(&self, name: &str) -> ArgName {
+        let known = self.args.iter().find_map(|arg| match &arg.argname {
+            ArgName::Long(long) | ArgName::Both { long, .. } if &**long == name => Some(long),
+            _ => None,
+        });
+        ArgName::Long(known.cloned().unwrap_or_else(|| owned_name(name)))
+    }
+
+    // Returns the negatable flag switched off by the given long name (`no-<name>`), if any.
+    fn negated(&self, long: &str) -> Option<&Arg> {
+        let long = long.strip_prefix("no-")?;
+        self.try_get(self.long_name(long))
+            .filter(|arg| arg.negatable)
+    }
+
+    fn negate_arg(&mut self, argname: &ArgName, value: Option<String>) -> Result<(), ErrorKind> {
+        match self.args.iter_mut().find(|arg| arg.argname == *argname) {
+            Some(arg) => arg.negate(value),
+            None => Err(ErrorKind::UnknownArg(argname.clone())),
+        }
+    }
+
+    fn init_arg(
+        &mut self,
+        argname: &ArgName,
+        input: &mut VecDeque<String>,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        for arg in &mut self.args {
+            if arg.argname == *argname {
+                arg.init(input)?;
+                if let Some(note) = arg.deprecated {
+                    warnings.push(format!("'{argname}' is deprecated: {note}"));
+                }
+                return Ok(());
+            }
+        }
+        Err(ErrorKind::UnknownArg(argname.clone()))
+    }
+}
+
+/// Build metadata shown in the version page.
+///
+/// Every field is optional and supplied by the caller, usually from environment variables set
+/// by a build script. See [`Command::build_info`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = Command::create("myapp", "This is my cool app.")
+///     .version("0.1.0")
+///     .build_info(BuildInfo {
+///         git_hash: Some("1a2b3c4"),
+///         target: Some("x86_64-unknown-linux-gnu"),
+///         ..Default::default()
+///     })
+///     .auto_version(true);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildInfo {
+    /// Hash of the commit the program was built from.
+    pub git_hash: Option<&'static str>,
+
+    /// Date of the build.
+    pub build_date: Option<&'static str>,
+
+    /// Target triple the program was built for.
+    pub target: Option<&'static str>,
+}
+
+/// Builds the command line.
+///
+/// It can be then used to parse the command line to get the arguments inserted by the user.
+/// See [`Command::parse`] or [`Command::parse_from`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let parsed = Command::create("myapp", "This is my cool app.")
+///     .author("Me!")
+///     .license("MY-LICENSE")
+///     .version("0.1.0")
+///     .arg(arg!(-'h', --help), value!(), "Shows this help.")
+///     .subcommand(
+///         Command::create("subcmd", "This is a subcommand.")
+///             .arg(arg!(-'p', --path), value!(path), "Insert a path.")
+///     )
+///     .parse();
+/// ```
+pub struct Command {
+    name: &'static str,
+    description: &'static str,
+    author: Option<&'static str>,
+    version: Option<&'static str>,
+    long_version: Option<&'static str>,
+    build_info: BuildInfo,
+    license: Option<&'static str>,
+    homepage: Option<&'static str>,
+    repository: Option<&'static str>,
+    examples: Vec<(&'static str, &'static str)>,
+    epilogue: Option<help::Epilogue>,
+    provider: Option<&'static str>,
+    visibility: Visibility,
+    terminal: Option<TerminalInfo>,
+    auto_version: bool,
+    verbosity: bool,
+    color_flag: bool,
+    subcommand_required: bool,
+    multicall: bool,
+    posix_strict: bool,
+    prefixes: Prefixes,
+    args_env: Option<&'static str>,
+    env_prefix: Option<&'static str>,
+    collect_errors: bool,
+    autocorrect: Autocorrect,
+    utf8_policy: Utf8Policy,
+    args: ArgList,
+    subcommands: Vec<Command>,
+    plugins: Vec<Plugin>,
+    parents: Vec<&'static str>,
+    without_globals: Vec<ArgName>,
+    required_groups: Vec<Vec<ArgName>>,
+    rest: Option<Rest>,
+    strings: Strings,
+    error_formatter: Option<error::ErrorFormatter>,
+    validator: Option<error::Validator>,
+    tracer: Option<trace::Tracer>,
+    help_flag: Option<ArgName>,
+    builtin_help: bool,
+    version_flag: ArgName,
+}
+
+// The positional arguments that capture every remaining value. See `Command::rest`.
+#[derive(Clone, Copy)]
+struct Rest {
+    name: &'static str,
+    description: &'static str,
+    min: usize,
+}
+
+impl Command {
+    /// This function creates a new [`Command`].
+    pub fn create(name: &'static str, description: &'static str) -> Self {
+        Command {
+            name,
+            description,
+            version: None,
+            long_version: None,
+            build_info: BuildInfo::default(),
+            author: None,
+            license: None,
+            homepage: None,
+            repository: None,
+            examples: Vec::new(),
+            epilogue: None,
+            provider: None,
+            args: ArgList::new(),
+            subcommands: Vec::new(),
+            plugins: Vec::new(),
+            parents: Vec::new(),
+            without_globals: Vec::new(),
+            required_groups: Vec::new(),
+            rest: None,
+            error_formatter: None,
+            validator: None,
+            tracer: None,
+            help_flag: None,
+            builtin_help: true,
+            version_flag: arg!(-'V', --version),
+            strings: Strings::ENGLISH,
+            terminal: None,
+            auto_version: false,
+            verbosity: false,
+            color_flag: false,
+            subcommand_required: false,
+            multicall: false,
+            posix_strict: false,
+            prefixes: Prefixes::DEFAULT,
+            args_env: None,
+            env_prefix: None,
+            collect_errors: false,
+            visibility: Visibility::Public,
+            autocorrect: Autocorrect::Off,
+            utf8_policy: Utf8Policy::Strict,
+        }
+    }
+
+    /// Specifies a new argument.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'h', --help), value!(), "Shows this help.");
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if an argument with the same name was already inputted, or if the name and the one
+    /// of an argument of this command or of its subcommands share only one of their short and long
+    /// names (e.g. `-'o', --out` and `-'o', --output`).
+    #[inline]
+    pub fn arg(mut self, argname: ArgName, argtype: ArgValue, description: &'static str) -> Self {
+        self.insert_arg(Arg::new(argname, argtype, description));
+        self
+    }
+
+    /// Specifies many arguments at once from a table of names, values and descriptions, like
+    /// [`Command::arg`]. The table can be a `static`, since [`arg`] and the [`value`]s without a
+    /// default can be used in constants: it is built once, its names are checked at compile
+    /// time and the long names are never copied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// static ARGS: &[(ArgName, ArgValue, &str)] = &[
+    ///     (arg!(-'v', --verbose), value!(count), "Increases verbosity."),
+    ///     (arg!(--path), value!(path), "Insert a path."),
+    ///     (arg!(--format), value!(choice ["json", "yaml"]), "Choose a format."),
+    /// ];
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg_table(ARGS)
+    ///     .parse_from_str("-vv --format json")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.try_count(arg!(-'v')), Some(2));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics like [`Command::arg`].
+    pub fn arg_table(mut self, table: &'static [(ArgName, ArgValue, &'static str)]) -> Self {
+        for (argname, argvalue, description) in table {
+            self.insert_arg(Arg::new(argname.clone(), argvalue.clone(), description));
+        }
+        self
+    }
+
+    /// Specifies a new argument created with [`Arg::new`].
+    ///
+    /// # Panic
+    ///
+    /// Panics like [`Command::arg`].
+    #[inline]
+    pub fn add_arg(mut self, arg: Arg) -> Self {
+        self.insert_arg(arg);
+        self
+    }
+
+    /// Specifies that this command accepts a variable number of positional values, which are
+    /// every token that is neither an argument nor the value of one (e.g. `myapp upload FILE...`).
+    ///
+    /// The name is shown in the usage (e.g. `[FILE]...`) and the values are collected in
+    /// [`ParsedCommand::rest`]. If this command has subcommands, a value that is not the name of
+    /// a subcommand is collected too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'v', --verbose), value!(), "Shows more details.")
+    ///     .rest("FILE", "Files to upload.")
+    ///     .rest_min(1)
+    ///     .parse_from_str("a.txt -v b.txt")
+    ///     .unwrap();
+    /// assert_eq!(parsed.rest, ["a.txt", "b.txt"]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the positional values were already specified.
+    pub fn rest(mut self, name: &'static str, description: &'static str) -> Self {
+        if self.rest.is_some() {
+            panic!("The positional values of '{}' already exist", self.name);
+        }
+        self.rest = Some(Rest {
+            name,
+            description,
+            min: 0,
+        });
+        self
+    }
+
+    /// Specifies the minimum number of positional values (see [`Command::rest`]).
+    /// By default it is `0`.
+    ///
+    /// Fewer values return an [`ErrorKind::TooFewValues`] error.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the positional values were not specified.
+    pub fn rest_min(mut self, min: usize) -> Self {
+        let Some(rest) = &mut self.rest else {
+            panic!("The command '{}' has no positional values", self.name);
+        };
+        rest.min = min;
+        self
+    }
+
+    /// Requires at least one of the given arguments to be used (e.g. either `--file` or `--url`).
+    ///
+    /// If none of them is used, parsing returns an [`ErrorKind::MissingOneOf`] error naming the
+    /// whole group. The group is shown in the usage as a single alternative, e.g.
+    /// `(--file <FILE> | --url <URL>)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = || {
+    ///     Command::create("myapp", "This is my cool app.")
+    ///         .arg(arg!(--file), value!(path), "Insert a file.")
+    ///         .arg(arg!(--url), value!(string), "Insert a URL.")
+    ///         .require_one_of([arg!(--file), arg!(--url)])
+    ///         .color(false)
+    /// };
+    /// assert!(cmd().parse_from_str("--url https://example.com").is_ok());
+    /// let err = cmd().parse_from_str("").err().unwrap();
+    /// assert_eq!(err.to_string(), "one of '--file', '--url' is required.");
+    /// assert!(err
+    ///     .render(false)
+    ///     .contains("myapp (--file <FILE> | --url <URL>)"));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if fewer than two arguments are given, and during parsing if one of them is not an
+    /// argument of this command.
+    pub fn require_one_of(mut self, argnames: impl IntoIterator<Item = ArgName>) -> Self {
+        let group: Vec<ArgName> = argnames.into_iter().collect();
+        if group.len() < 2 {
+            panic!("A group of '{}' needs at least two arguments", self.name);
+        }
+        self.required_groups.push(group);
+        self
+    }
+
+    /// Excludes a global argument inherited from the parent commands (see [`Arg::global`]).
+    ///
+    /// The excluded argument is not inherited by this command nor by its subcommands.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the name and the one of an argument of this command or of its subcommands share
+    /// only one of their short and long names, and during parsing if none of the parent commands
+    /// has the given global argument.
+    #[inline]
+    pub fn without_global(mut self, argname: ArgName) -> Self {
+        if let Some((cmd, name)) = self
+            .names()
+            .into_iter()
+            .find(|(_, n)| n.conflicts(&argname))
+        {
+            panic!(
+                "The argument '{argname}' of '{}' and '{name}' of '{cmd}' share only one name",
+                self.name
+            );
+        }
+        self.without_globals.push(argname);
+        self
+    }
+
+    /// Specifies a new subcommand [`Command`].
+    ///
+    /// # Panic
+    ///
+    /// Panics if a subcommand with the same name was already inputted. Since the subcommands
+    /// often come from different components (see [`Command::extend`]), names that differ only in
+    /// case (e.g. `sync` and `Sync`) are rejected too, and the panic names the providers of both
+    /// when they have one. Every level of the tree is checked, since each one is built with this
+    /// method.
+    ///
+    /// It also panics if an argument of the subcommand (or of its own subcommands) and one of this
+    /// command (or of its other subcommands) share only one of their short and long names (e.g.
+    /// `-'o', --out` and `-'o', --output`), since they would match each other.
+    pub fn subcommand(mut self, subcmd: Command) -> Self {
+        if let Some([(cmd, name), (other_cmd, other)]) = self.name_conflict(&subcmd) {
+            panic!(
+                "The argument '{other}' of '{other_cmd}' and '{name}' of '{cmd}' share only one name"
+            );
+        }
+        if let Some(existing) = self.sibling(subcmd.name) {
+            if existing.name == subcmd.name && existing.provider.or(subcmd.provider).is_none() {
+                panic!("Subcommand '{}' already exists.", subcmd.name);
+            }
+            let provider = |s: &Command| s.provider.unwrap_or(self.name);
+            let reason = if existing.name == subcmd.name {
+                "have the same name"
+            } else {
+                "differ only in case"
+            };
+            panic!(
+                "Subcommand '{}' of '{}' and subcommand '{}' of '{}' {reason}.",
+                existing.name,
+                provider(existing),
+                subcmd.name,
+                provider(&subcmd)
+            );
+        }
+        let mut subcmd = subcmd;
+        subcmd.add_parents(self.parents.clone(), self.name);
+        self.subcommands.push(subcmd);
+        self
+    }
+
+    /// Builds a command from a TOML document, so that simple programs can define their command
+    /// line as data. Requires the `definitions` feature.
+    ///
+    /// The root table is the command, with the fields `name` and `description` (required),
+    /// `version`, `long_version`, `author`, `license`, `homepage`, `repository`, `epilogue`,
+    /// `provider`, `visibility` (`public`, `internal` or `hidden`), `args_env`, `env_prefix`,
+    /// `color`, `auto_version`, `verbosity_flags`, `color_flag`, `subcommand_required`,
+    /// `multicall`, `posix_strict`, `collect_errors`, `autocorrect` (`off`, `run` or `prompt`) and
+    /// `rest` (a table with `name`, `description` and `min`). Arguments are listed in the `args`
+    /// array of tables, subcommands, which are defined like the root, in the `subcommands` one and
+    /// examples, with a `line` and a `description`, in the `examples` one.
+    ///
+    /// Each argument has the fields `short` and/or `long`, `description` (required), `type`
+    /// (`string`, `num`, `float`, `path` with an optional `exists = true`, `flag`, `choice` with
+    /// `choices`, `range` with `range = [min, max]`, `list`, `count`, `paths`, `bytes` or `ratio`;
+    /// by default `flag`), `default`, `long_description`, `value_name`, `example`, `provider`,
+    /// `visibility`, `deprecated`, `global`, `placement` (`anywhere`, `before` or `after`),
+    /// `only_with` (an array of subcommands), `split_paths`, `sensitive`, `trim`, `allow_empty`,
+    /// `negatable`, `num_values` (`[min, max]`), `delimiter` (a single character), `merge`
+    /// (`append`, `dedupe` or `error`), `value_hint` (`file`, `dir`, `hostname`, `username`,
+    /// `command` or `other`) and `duplicates` (`overwrite`, `error` or `append`).
+    ///
+    /// Only single-line strings are supported. Since commands hold static strings, the strings
+    /// of the document are leaked: it is meant to be loaded once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::from_toml_str(
+    ///     r#"
+    ///     name = "myplugin"
+    ///     description = "This is my cool plugin."
+    ///
+    ///     [[args]]
+    ///     short = "p"
+    ///     long = "port"
+    ///     type = "range"
+    ///     range = [1, 65535]
+    ///     default = 8080
+    ///     description = "Port to listen on."
+    ///
+    ///     [[subcommands]]
+    ///     name = "clean"
+    ///     description = "Removes the cache."
+    ///     "#,
+    /// )
+    /// .unwrap();
+    /// let parsed = cmd.parse_from_str("-p 80 clean").unwrap();
+    /// assert!(parsed.is(["clean"]));
+    /// assert_eq!(parsed.ancestors[0].get(arg!(--port)).num(), Some(80));
+    /// ```
+    #[cfg(feature = "definitions")]
+    pub fn from_toml_str(document: &str) -> Result<Self, DefinitionError> {
+        definition::load(document)
+    }
+
+    /// Merges the arguments and subcommands of another command into this one.
+    ///
+    /// This is useful to define a set of shared arguments once (e.g. logging flags) and mix it
+    /// into many commands. Only arguments, subcommands and excluded globals are merged: the other
+    /// command's name, description and metadata are discarded, but its provider (see
+    /// [`Command::provider`]) is given to the merged arguments and subcommands that have none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// fn logging() -> Command {
+    ///     Command::create("logging", "Logging arguments.")
+    ///         .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity.")
+    ///         .arg(arg!(--log), value!(path), "Writes logs to a file.")
+    /// }
+    ///
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .subcommand(Command::create("serve", "Starts the server.").extend(logging()))
+    ///     .subcommand(Command::create("backup", "Makes a backup.").extend(logging()));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if an argument, a subcommand or the positional values of the other command already
+    /// exist in this one. Subcommands whose names differ only in case (e.g. `sync` and `Sync`)
+    /// are rejected too, and the panic names the providers of both (see
+    /// [`Command::subcommand`]).
+    pub fn extend(mut self, other: Command) -> Self {
+        for mut arg in other.args.args {
+            arg.provider = arg.provider.or(other.provider);
+            self.insert_arg(arg);
+        }
+        for mut subcmd in other.subcommands {
+            subcmd.provider = subcmd.provider.or(other.provider);
+            self = self.subcommand(subcmd);
+        }
+        self.without_globals.extend(other.without_globals);
+        self.required_groups.extend(other.required_groups);
+        if let Some(rest) = other.rest {
+            self = self.rest(rest.name, rest.description).rest_min(rest.min);
+        }
+        self
+    }
+
+    /// Specifies the version of the program.
+    ///
+    /// It will appear on the help page ([`ParsedCommand::help`]). Subcommands that do not
+    /// specify their own version use the one of their parent command.
+    #[inline]
+    pub fn version(mut self, version: &'static str) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Specifies a longer version text of the program.
+    ///
+    /// It will appear on the version page, below the version.
+    /// See [`Command::auto_version`].
+    #[inline]
+    pub fn long_version(mut self, long_version: &'static str) -> Self {
+        self.long_version = Some(long_version);
+        self
+    }
+
+    /// Specifies the build metadata of the program.
+    ///
+    /// It will appear on the version page. See [`Command::auto_version`].
+    #[inline]
+    pub fn build_info(mut self, build_info: BuildInfo) -> Self {
+        self.build_info = build_info;
+        self
+    }
+
+    /// Specifies whether or not the version page should be handled automatically.
+    /// By default it is not.
+    ///
+    /// When enabled, a `-V, --version` flag is added to the command (unless an argument
+    /// with one of those names already exists) and, when it is used, parsing returns an
+    /// [`ErrorKind::DisplayVersion`] error that carries the version page, which
+    /// [`Command::parse_or_exit`] prints before exiting. The flag can be changed with
+    /// [`Command::version_flag`].
+    #[inline]
+    pub fn auto_version(mut self, auto_version: bool) -> Self {
+        self.auto_version = auto_version;
+        self
+    }
+
+    /// Adds the global `-v, --verbose` and `-q, --quiet` flags to the program, which can be used
+    /// many times to raise or lower the verbosity (see [`ParsedCommand::verbosity`]).
+    /// A flag is not added if an argument with one of its names already exists.
+    ///
+    /// It has effect only on the root command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .verbosity_flags()
+    ///     .subcommand(Command::create("serve", "Starts the server."))
+    ///     .parse_from_str("-vvv serve -q")
+    ///     .unwrap();
+    /// assert_eq!(parsed.verbosity(), 2);
+    /// ```
+    #[inline]
+    pub fn verbosity_flags(mut self) -> Self {
+        self.verbosity = true;
+        self
+    }
+
+    /// Adds the global `--color <WHEN>` option to the program, which takes `auto` (the
+    /// default), `always` or `never` (see [`ColorChoice`]). The option is not added if an
+    /// argument with its name already exists.
+    ///
+    /// The choice is read before the rest of the command line, which is then collected, so that the
+    /// help pages and the errors follow it: `always` and `never` override [`Command::color`] and
+    /// [`Command::terminal`], while `auto` keeps them. The program can color its own output the
+    /// same way with [`ParsedCommand::color_choice`].
+    ///
+    /// It has effect only on the root command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .color_flag()
+    ///     .subcommand(Command::create("serve", "Starts the server."));
+    /// let err = cmd.parse_from_str("serve --idk --color always").err().unwrap();
+    /// assert!(err.terminal().color);
+    /// ```
+    #[inline]
+    pub fn color_flag(mut self) -> Self {
+        self.color_flag = true;
+        self
+    }
+
+    /// Specifies the name of the automatic version flag (see [`Command::auto_version`]).
+    /// By default it is `-V, --version`.
+    #[inline]
+    pub fn version_flag(mut self, argname: ArgName) -> Self {
+        self.version_flag = argname;
+        self
+    }
+
+    /// Specifies a flag that requests the long help page of the command: parsing returns an
+    /// [`ErrorKind::DisplayHelp`] error that carries it. By default there is none.
+    ///
+    /// The flag is added to every command of the program (unless it already has an argument
+    /// with one of its names, which is then left to the command) and it is used by the whole
+    /// program when set on the root command. If it is used with a parent command (e.g.
+    /// `myapp --help user`) the page of the subcommand is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tiny_args::*;
+    /// // `-h` is taken by `--host`.
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'h', --host), value!(string), "Insert a host.")
+    ///     .help_flag(arg!(-'?', --help))
+    ///     .parse();
+    /// ```
+    #[inline]
+    pub fn help_flag(mut self, argname: ArgName) -> Self {
+        self.help_flag = Some(argname);
+        self
+    }
+
+    /// Disables the built-in help handling: the `help` subcommand and the help flag (see
+    /// [`Command::help_flag`]).
+    ///
+    /// It is used by the whole program when set on the root command.
+    #[inline]
+    pub fn disable_help(mut self) -> Self {
+        self.help_flag = None;
+        self.builtin_help = false;
+        self
+    }
+
+    /// Specifies whether or not a subcommand must be given when this command has any.
+    /// By default it is not required.
+    ///
+    /// When required, invoking this command with no subcommand and no arguments returns an
+    /// [`ErrorKind::MissingSubcommand`] error listing the available subcommands.
+    #[inline]
+    pub fn subcommand_required(mut self, subcommand_required: bool) -> Self {
+        self.subcommand_required = subcommand_required;
+        self
+    }
+
+    /// Specifies external executables that are used as subcommands (cargo-style), usually
+    /// found with [`Plugin::discover`]. By default there are none.
+    ///
+    /// The plugins are listed with the subcommands in the help page, unless a subcommand has
+    /// the same name. When one is given, every token after its name is left untouched in
+    /// [`ParsedCommand::rest`] and the plugin is returned in [`ParsedCommand::plugin`], so
+    /// that it can be run with [`Plugin::run`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let backup = Plugin {
+    ///     name: "backup".into(),
+    ///     path: "/usr/bin/tinycloud-backup".into(),
+    /// };
+    /// let parsed = Command::create("tinycloud", "This is my cool app.")
+    ///     .arg(arg!(--verbose), value!(), "Prints more.")
+    ///     .plugins(vec![backup.clone()])
+    ///     .parse_from_str("--verbose backup --all --verbose")
+    ///     .unwrap();
+    /// assert_eq!(parsed.plugin, Some(backup));
+    /// assert_eq!(parsed.rest, ["--all", "--verbose"]);
+    /// assert_eq!(parsed.args.try_count(arg!(--verbose)), Some(1));
+    /// ```
+    #[inline]
+    pub fn plugins(mut self, plugins: Vec<Plugin>) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Adds a plugin (see [`Command::plugins`]) that declares which versions of this command
+    /// it works with, checking them against the version of this command (see
+    /// [`Command::version`]). Requires the `semver` feature.
+    ///
+    /// Incompatible plugins are rejected here, while the command is built, rather than failing
+    /// when they are run. Check [`VersionReq::matches`] first to skip them instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let backup = Plugin {
+    ///     name: "backup".into(),
+    ///     path: "/usr/bin/tinycloud-backup".into(),
+    /// };
+    /// let parsed = Command::create("tinycloud", "This is my cool app.")
+    ///     .version("1.4.0")
+    ///     .compatible_plugin(backup.clone(), &"^1.2".parse().unwrap())
+    ///     .parse_from_str("backup --all")
+    ///     .unwrap();
+    /// assert_eq!(parsed.plugin, Some(backup));
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if this command has no valid version or if it does not meet the requirement.
+    #[cfg(feature = "semver")]
+    pub fn compatible_plugin(mut self, plugin: Plugin, requires: &VersionReq) -> Self {
+        let version: Version = match self.version.map(str::parse) {
+            Some(Ok(version)) => version,
+            _ => panic!(
+                "The command '{}' needs a valid version to check the plugin '{}'",
+                self.name, plugin.name
+            ),
+        };
+        if !requires.matches(&version) {
+            panic!(
+                "The plugin '{}' requires version {requires} of '{}', but it is {version}",
+                plugin.name, self.name
+            );
+        }
+        self.plugins.push(plugin);
+        self
+    }
+
+    /// Specifies whether or not the name of the binary selects a subcommand (busybox-style).
+    /// By default it does not.
+    ///
+    /// When enabled, a binary named either like a subcommand (`backup`) or like the command
+    /// followed by a dash and a subcommand (`myapp-backup`) is dispatched straight to that
+    /// subcommand. This is usually done with symlinks to the same binary. Any other binary
+    /// name is parsed normally.
+    #[inline]
+    pub fn multicall(mut self, multicall: bool) -> Self {
+        self.multicall = multicall;
+        self
+    }
+
+    /// Specifies whether or not the arguments of this command end at the first positional value
+    /// (POSIX-style). By default they do not, so arguments and values can be mixed.
+    ///
+    /// When enabled, the first token that is neither an argument, the value of one nor a
+    /// subcommand is collected in [`ParsedCommand::rest`] together with every token that
+    /// follows it, even if they start with a dash. This is useful for wrappers whose positional
+    /// values are another command line. Tokens that are not known by
+    /// [`Command::parse_known_from`] are left over in the same way.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'v', --verbose), value!(), "Prints more.")
+    ///     .rest("COMMAND", "The command to run.")
+    ///     .posix_strict(true)
+    ///     .parse_from_str("-v ls -v --all")
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.try_count(arg!(--verbose)), Some(1));
+    /// assert_eq!(parsed.rest, ["ls", "-v", "--all"]);
+    /// ```
+    #[inline]
+    pub fn posix_strict(mut self, posix_strict: bool) -> Self {
+        self.posix_strict = posix_strict;
+        self
+    }
+
+    /// Specifies which prefixes introduce the arguments (see [`Prefixes`]), e.g. to migrate a
+    /// tool that switches its flags off with `+`. By default they are [`Prefixes::DEFAULT`].
+    ///
+    /// Without the short arguments, the automatic flags (e.g. `-V, --version`) have only their
+    /// long name.
+    ///
+    /// It has effect only on the root command.
+    ///
+    /// # Panic
+    ///
+    /// Parsing panics if the short arguments are disabled and an argument or the help flag has a
+    /// short name.
+    #[inline]
+    pub fn prefixes(mut self, prefixes: Prefixes) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Specifies an environment variable containing extra arguments (e.g. `MYAPP_OPTS`).
+    /// By default there is none.
+    ///
+    /// The variable is split into arguments with [`split()`], and they are
+    /// parsed before the ones in the command line, after the subcommands. Since only the last
+    /// value of an argument is usually kept, the command line takes precedence.
+    /// It is used by the whole program when set on the root command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// std::env::set_var("MYAPP_OPTS", "--name 'Some One'");
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .args_env("MYAPP_OPTS")
+    ///     .parse_from(vec!["myapp".into()])
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.get(arg!(--name)).string(), Some("Some One"));
+    /// ```
+    #[inline]
+    pub fn args_env(mut self, var: &'static str) -> Self {
+        self.args_env = Some(var);
+        self
+    }
+
+    /// Specifies the prefix of the program's environment variables (e.g. `MYAPP_`), so that
+    /// the unknown ones are reported. By default there is none.
+    ///
+    /// A variable with the prefix is known if it is the one given to [`Command::args_env`] or
+    /// if it is named after a long argument of the parsed command or of its parents, with
+    /// uppercase letters and underscores instead of dashes and dots (e.g. `MYAPP_DATA_DIR` for
+    /// `--data-dir`). The others are probably misspelled, so a warning is added to
+    /// [`ParsedCommand::warnings`] for each of them.
+    /// It is used by the whole program when set on the root command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// std::env::set_var("MYAPP_DATA_DIR", "/srv/myapp");
+    /// std::env::set_var("MYAPP_DATADIR", "/srv/myapp");
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--data-dir), value!(path), "Insert a directory.")
+    ///     .env_prefix("MYAPP_")
+    ///     .parse_from(vec!["myapp".into()])
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     parsed.warnings,
+    ///     ["'MYAPP_DATADIR' is not a known environment variable"]
+    /// );
+    /// ```
+    #[inline]
+    pub fn env_prefix(mut self, prefix: &'static str) -> Self {
+        self.env_prefix = Some(prefix);
+        self
+    }
+
+    /// Specifies the author of the program.
+    ///
+    /// It will appear on the help page ([`ParsedCommand::help`]). Subcommands that do not
+    /// specify their own author use the one of their parent command.
+    #[inline]
+    pub fn author(mut self, author: &'static str) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    /// Specifies the license of the program.
+    ///
+    /// It will appear on the help page ([`ParsedCommand::help`]). Subcommands that do not
+    /// specify their own license use the one of their parent command.
+    #[inline]
+    pub fn license(mut self, license: &'static str) -> Self {
+        self.license = Some(license);
+        self
+    }
+
+    /// Specifies the URL of the program's homepage.
+    ///
+    /// It will appear on the help page ([`ParsedCommand::help`]). Subcommands that do not
+    /// specify their own homepage use the one of their parent command.
+    #[inline]
+    pub fn homepage(mut self, homepage: &'static str) -> Self {
+        self.homepage = Some(homepage);
+        self
+    }
+
+    /// Specifies the URL of the program's source code repository.
+    ///
+    /// It will appear on the help page ([`ParsedCommand::help`]). Subcommands that do not
+    /// specify their own repository use the one of their parent command.
+    #[inline]
+    pub fn repository(mut self, repository: &'static str) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Specifies the name of the component that offers this command (e.g. `Backup plugin`).
+    /// By default there is none.
+    ///
+    /// When some arguments (see [`Arg::provider`]) or subcommands of a command have a provider,
+    /// its help page lists them grouped by provider, beginning with those that have none
+    /// (see [`Strings::core`]). Commands merged with [`Command::extend`] give their provider to
+    /// what they add, so a component can tag everything it registers at once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// fn backup() -> Command {
+    ///     Command::create("backup", "Backup arguments.")
+    ///         .provider("Backup plugin")
+    ///         .arg(arg!(--snapshot), value!(), "Takes a snapshot first.")
+    ///         .subcommand(Command::create("restore", "Restores a backup."))
+    /// }
+    ///
+    /// let cmd = Command::create("tinycloud", "This is my cool app.")
+    ///     .arg(arg!(--config), value!(path), "Uses another configuration.")
+    ///     .subcommand(Command::create("serve", "Starts the server."))
+    ///     .extend(backup())
+    ///     .color(false);
+    /// let mut buf = Vec::new();
+    /// cmd.print_help(&mut buf).unwrap();
+    /// let help = String::from_utf8(buf).unwrap();
+    /// assert!(help.contains("\tBackup plugin:\n\t--snapshot  Takes a snapshot first.\n"));
+    /// assert!(help.contains("\tBackup plugin:\n\trestore     Restores a backup.\n"));
+    /// ```
+    #[inline]
+    pub fn provider(mut self, provider: &'static str) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Specifies where this subcommand is documented (see [`Visibility`]). By default it is
+    /// [`Visibility::Public`].
+    ///
+    /// It hides the subcommand from the lists of its parent, and the completion scripts and
+    /// configuration templates leave out its whole subtree. Its own help page still shows it like
+    /// any other. The subcommand is parsed whatever its visibility.
+    #[inline]
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Adds an example of how the command is used: a command line and what it does.
+    ///
+    /// The examples are listed in their own section of the help page
+    /// ([`ParsedCommand::help`]), in the order they were added. They belong only to this
+    /// command, not to its subcommands.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .homepage("https://example.com/myapp")
+    ///     .example("myapp --name me", "Greets me.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .color(false);
+    /// let mut buf = Vec::new();
+    /// cmd.print_help(&mut buf).unwrap();
+    /// let help = String::from_utf8(buf).unwrap();
+    /// assert!(help.contains("EXAMPLES:\n\tmyapp --name me\n\t\tGreets me.\n"));
+    /// assert!(help.contains("Homepage: https://example.com/myapp\n"));
+    /// ```
+    #[inline]
+    pub fn example(mut self, line: &'static str, description: &'static str) -> Self {
+        self.examples.push((line, description));
+        self
+    }
+
+    /// Specifies a text shown at the end of the help page, after the examples. By default there
+    /// is none.
+    ///
+    /// See [`Command::epilogue_with`] for a text that is known only when the page is shown.
+    #[inline]
+    pub fn epilogue(self, epilogue: &'static str) -> Self {
+        self.epilogue_with(move || epilogue.into())
+    }
+
+    /// Specifies a function that returns the text shown at the end of the help page, after the
+    /// examples. It is called every time the page is created, so the text can show the current
+    /// state of the program (e.g. the enabled plugins or the configuration file in use). An empty
+    /// text is not shown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .epilogue_with(|| format!("Configuration: {}", "/etc/myapp.toml"))
+    ///     .color(false);
+    /// let mut buf = Vec::new();
+    /// cmd.print_help(&mut buf).unwrap();
+    /// let help = String::from_utf8(buf).unwrap();
+    /// assert!(help.contains("Insert a name.\n\nConfiguration: /etc/myapp.toml\n"));
+    /// ```
+    #[inline]
+    pub fn epilogue_with(mut self, epilogue: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        self.epilogue = Some(help::Epilogue(Arc::new(epilogue)));
+        self
+    }
+
+    /// Specifies the text used in help pages, version pages and errors, to localize them.
+    ///
+    /// It is used by the whole program when set on the root command: the subcommands' own text
+    /// is replaced with it during parsing. See [`Strings`].
+    #[inline]
+    pub fn strings(mut self, strings: Strings) -> Self {
+        self.strings = strings;
+        self
+    }
+
+    /// Specifies a function that renders errors in place of [`ParseError::render`], to reword
+    /// them. The structured error is still returned by parsing.
+    ///
+    /// It is used by the whole program when set on the root command, like [`Command::strings`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .error_formatter(|err| format!("myapp: {err}\nSee 'myapp help'."))
+    ///     .parse_from_str("--what")
+    ///     .err()
+    ///     .unwrap();
+    /// assert_eq!(err.kind(), &ErrorKind::UnknownArg(arg!(--what)));
+    /// assert_eq!(
+    ///     err.render(false),
+    ///     "myapp: '--what' is not a valid argument.\nSee 'myapp help'."
+    /// );
+    /// ```
+    #[inline]
+    pub fn error_formatter(
+        mut self,
+        formatter: impl Fn(&ParseError) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.error_formatter = Some(error::ErrorFormatter(Arc::new(formatter)));
+        self
+    }
+
+    /// Specifies whether or not the parser keeps going after an error, so that every problem
+    /// of the command line is reported at once (see [`ParseError::errors`]). Errors that leave
+    /// nothing to parse, like an unknown subcommand, still stop it.
+    /// By default it stops at the first error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--num), value!(num), "Insert a number.")
+    ///     .arg(arg!(--verbose), value!(), "Be verbose.")
+    ///     .collect_errors(true)
+    ///     .color(false)
+    ///     .parse_from_str("--num 6x --verbose=yes")
+    ///     .err()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     err.render(false),
+    ///     "error: invalid value '6x' for '--num': must be a valid number: \
+    ///      invalid digit found in string
+    /// error: invalid value 'yes' for '--verbose': must be omitted
+    ///
+    /// USAGE:
+    /// \tmyapp [--num <NUM>] [--verbose]
+    ///
+    /// For more information, try 'myapp help'."
+    /// );
+    /// ```
+    #[inline]
+    pub fn collect_errors(mut self, collect_errors: bool) -> Self {
+        self.collect_errors = collect_errors;
+        self
+    }
+
+    /// Specifies what to do with a subcommand that does not exist but is close to one that does
+    /// (e.g. `stauts` instead of `status`), like git's `help.autocorrect`. By default it is an
+    /// error ([`Autocorrect::Off`]).
+    ///
+    /// Only the setting of the root command is used, and it applies to every subcommand.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .subcommand(Command::create("status", "Shows the status."))
+    ///     .subcommand(Command::create("stash", "Stashes the changes."))
+    ///     .autocorrect(Autocorrect::Run)
+    ///     .parse_from_str("stauts")
+    ///     .unwrap();
+    /// assert_eq!(parsed.name, "status");
+    /// assert_eq!(parsed.warnings, ["'stauts' was corrected to 'status'"]);
+    /// ```
+    #[inline]
+    pub fn autocorrect(mut self, autocorrect: Autocorrect) -> Self {
+        self.autocorrect = autocorrect;
+        self
+    }
+
+    /// Specifies what [`Command::parse`] and [`Command::parse_from_os`] do with arguments that
+    /// are not valid UTF-8. By default they are an error ([`Utf8Policy::Strict`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::ffi::OsString;
+    /// # use tiny_args::*;
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::os::unix::ffi::OsStringExt;
+    ///
+    /// let cmd = |policy| {
+    ///     Command::create("myapp", "This is my cool app.")
+    ///         .arg(arg!(--file), value!(path), "Insert a file.")
+    ///         .utf8_policy(policy)
+    /// };
+    /// let file = OsString::from_vec(b"caf\xe9.txt".to_vec());
+    /// let args = || ["myapp".into(), "--file".into(), file.clone()];
+    /// let err = cmd(Utf8Policy::Strict).parse_from_os(args()).err().unwrap();
+    /// assert_eq!(*err.kind(), ErrorKind::NonUtf8Arg("caf\u{FFFD}.txt".into()));
+    /// let parsed = cmd(Utf8Policy::Paths).parse_from_os(args()).unwrap();
+    /// assert_eq!(parsed.args.try_get(arg!(--file)).unwrap().path().unwrap(), &file);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn utf8_policy(mut self, policy: Utf8Policy) -> Self {
+        self.utf8_policy = policy;
+        self
+    }
+
+    /// Specifies a function that checks the parsed command, for rules that involve more than
+    /// one argument. It runs at the end of the parsing and the reason it returns is reported
+    /// as an [`ErrorKind::Validation`] error, like the other parse errors.
+    ///
+    /// The validators of every command that was traversed run, the root first, and each one
+    /// receives the same [`ParsedCommand`] (the arguments of the parents are in
+    /// [`ParsedCommand::ancestors`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--data-dir), value!(path), "Insert the data directory.")
+    ///     .arg(arg!(--backup-dir), value!(path), "Insert the backup directory.")
+    ///     .validator(|parsed| {
+    ///         let data = parsed.args.get(arg!(--data-dir)).path();
+    ///         if data.is_some() && data == parsed.args.get(arg!(--backup-dir)).path() {
+    ///             return Err("'--backup-dir' must differ from '--data-dir'".into());
+    ///         }
+    ///         Ok(())
+    ///     })
+    ///     .parse_from_str("--data-dir /srv --backup-dir /srv")
+    ///     .err()
+    ///     .unwrap();
+    /// assert_eq!(err.to_string(), "'--backup-dir' must differ from '--data-dir'.");
+    /// ```
+    #[inline]
+    pub fn validator(
+        mut self,
+        validator: impl Fn(&ParsedCommand) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validator = Some(error::Validator(Arc::new(validator)));
+        self
+    }
+
+    /// Specifies a function that receives every step of the parsing: the subcommands that are
+    /// entered, the tokens that are read, which argument they match and the converted values.
+    /// It is useful to find out why the command line was not parsed as expected, and the events
+    /// can be forwarded to a logger. Subcommands use the tracer of their parent.
+    ///
+    /// Available with the `tracing` feature. See [`TraceEvent`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'n', --num), value!(num), "Insert a number.")
+    ///     .tracer(|event| {
+    ///         if let TraceEvent::Value { arg, token } = event {
+    ///             eprintln!("'{token}' is the value of '{arg}'");
+    ///         }
+    ///     })
+    ///     .parse_from_str("-n 5");
+    /// ```
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn tracer(mut self, tracer: impl Fn(&TraceEvent) + Send + Sync + 'static) -> Self {
+        self.tracer = Some(trace::Tracer(Arc::new(tracer)));
+        self
+    }
+
+    /// Specifies whether or not the help page and the errors should be colored. By default it
+    /// is detected (see [`TerminalInfo::detect`]) and they are never colored without the `color`
+    /// feature.
+    ///
+    /// It overrides only the color of [`Command::terminal`]. To let the users choose, add the
+    /// `--color` option with [`Command::color_flag`].
+    #[inline]
+    pub fn color(mut self, color: bool) -> Self {
+        self.terminal = Some(TerminalInfo {
+            color,
+            ..self.terminal_info()
+        });
+        self
+    }
+
+    /// Specifies the terminal the help page and the errors are rendered for: the help page is
+    /// wrapped at its width and both are colored if it supports it. By default it is detected
+    /// once per process (see [`TerminalInfo::detect`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .arg(
+    ///         arg!(--name),
+    ///         value!(string),
+    ///         "Insert the name that will be shown to the other users of the service.",
+    ///     )
+    ///     .terminal(TerminalInfo {
+    ///         width: Some(50),
+    ///         ..TerminalInfo::PLAIN
+    ///     });
+    /// let mut buf = Vec::new();
+    /// cmd.print_help(&mut buf).unwrap();
+    /// let help = String::from_utf8(buf).unwrap();
+    /// assert!(help.contains("Insert the name that will be shown\n\t        to the other users"));
+    /// ```
+    #[inline]
+    pub fn terminal(mut self, terminal: TerminalInfo) -> Self {
+        self.terminal = Some(terminal);
+        self
+    }
+
+    // The terminal given with `Command::terminal`, or the detected one.
+    pub(crate) fn terminal_info(&self) -> TerminalInfo {
+        self.terminal.unwrap_or_else(TerminalInfo::detect)
+    }
+
+    /// Returns the name of this command.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the description of this command.
+    #[inline]
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Returns the arguments of this command, in the order they were added, so that other
+    /// interfaces (e.g. a menu) can be built from the same definition without parsing.
+    ///
+    /// Like [`Command::print_help`], they do not include the arguments inherited from the parent
+    /// commands nor the automatic help and version flags.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .subcommand(Command::create("serve", "Starts the server."));
+    /// assert_eq!(cmd.name(), "myapp");
+    /// let names: Vec<&ArgName> = cmd.args().iter().map(|arg| &arg.argname).collect();
+    /// assert_eq!(names, [&arg!(--name)]);
+    /// let subcommands: Vec<&str> = cmd.subcommands().iter().map(Command::name).collect();
+    /// assert_eq!(subcommands, ["serve"]);
+    /// ```
+    #[inline]
+    pub fn args(&self) -> &[Arg] {
+        &self.args.args
+    }
+
+    /// Returns the subcommands of this command, in the order they were added. See
+    /// [`Command::args`].
+    #[inline]
+    pub fn subcommands(&self) -> &[Command] {
+        &self.subcommands
+    }
+
+    /// Returns the subcommand at the given path of names, or [`None`] if there is none. An empty
+    /// path is this command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.").subcommand(
+    ///     Command::create("user", "Manages the users.")
+    ///         .subcommand(Command::create("add", "Adds a user.")),
+    /// );
+    /// assert_eq!(cmd.find(["user", "add"]).unwrap().description(), "Adds a user.");
+    /// assert!(cmd.find(["user", "remove"]).is_none());
+    /// ```
+    pub fn find<'p>(&self, path: impl IntoIterator<Item = &'p str>) -> Option<&Command> {
+        path.into_iter().try_fold(self, |cmd, name| {
+            cmd.subcommands.iter().find(|s| s.name == name)
+        })
+    }
+
+    /// Detaches the subcommand at the given path of names (see [`Command::find`]) from the rest
+    /// of the tree, so that it can be parsed on its own, e.g. to test it in isolation.
+    ///
+    /// The subcommand keeps what it would get from its parents if the whole tree was parsed: their
+    /// global arguments, the automatic flags, the strings, the error formatter and their metadata.
+    /// Its help page and its errors still show its full name, while the parsed command has no
+    /// [`ParsedCommand::ancestors`]: the global arguments can be read from its own
+    /// [`ParsedCommand::args`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .verbosity_flags()
+    ///     .subcommand(
+    ///         Command::create("user", "Manages the users.").subcommand(
+    ///             Command::create("add", "Adds a user.").arg(
+    ///                 arg!(--name),
+    ///                 value!(string),
+    ///                 "Insert the name.",
+    ///             ),
+    ///         ),
+    ///     );
+    /// let parsed = cmd
+    ///     .into_subcommand(["user", "add"])
+    ///     .unwrap()
+    ///     .parse_from_str("--name me -v")
+    ///     .unwrap();
+    /// assert_eq!(parsed.path(), ["user", "add"]);
+    /// assert_eq!(parsed.verbosity(), 1);
+    /// ```
+    pub fn into_subcommand<'p>(self, path: impl IntoIterator<Item = &'p str>) -> Option<Command> {
+        parser::detach(self, path)
+    }
+
+    /// Writes the help page of this command to the given writer, usually [`io::stdout`].
+    ///
+    /// Unlike [`ParsedCommand::help`], the page is created before parsing, so it does not
+    /// contain the arguments inherited from the parent commands nor the automatic version flag.
+    /// See [`help::render`] to render it with other options (e.g. wrapped at a given width).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let cmd = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'h', --help), value!(), "Shows this help.")
+    ///     .color(false);
+    /// let mut buf = Vec::new();
+    /// cmd.print_help(&mut buf).unwrap();
+    /// assert!(String::from_utf8(buf).unwrap().starts_with("myapp"));
+    ///
+    /// cmd.print_help(&mut std::io::stdout()).unwrap();
+    /// ```
+    pub fn print_help(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "{}", help::create(self, false))
+    }
+
+    /// Creates a commented TOML template of a configuration file with every argument of this
+    /// command and of its subcommands.
+    ///
+    /// Each argument is listed with its description, type and default value, using its long name
+    /// as key. Arguments without a default value are commented out, and subcommands get their own
+    /// tables (e.g. `[user.add]`). This keeps the example configuration aligned with the command
+    /// line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let template = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'p', --port), value!(num, 8080), "Insert a port.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .config_template();
+    /// assert!(template.contains("port = 8080\n"));
+    /// assert!(template.contains("#name =\n"));
+    /// ```
+    pub fn config_template(&self) -> String {
+        schema::toml(self)
+    }
+
+    // Adds an argument, checking its name against the ones of the subcommands (the arguments of
+    // this command are checked by the list itself).
+    fn insert_arg(&mut self, arg: Arg) {
+        let names = self.subcommands.iter().flat_map(Command::names);
+        if let Some((cmd, name)) = names.into_iter().find(|(_, n)| n.conflicts(&arg.argname)) {
+            panic!(
+                "The argument '{}' of '{}' and '{name}' of '{cmd}' share only one name",
+                arg.argname, self.name
+            );
+        }
+        self.args.insert(arg);
+    }
+
+    // Returns the names of the arguments (and of the excluded globals) of this command and of its
+    // subcommands, with the name of the command they belong to.
+    fn names(&self) -> Vec<(&'static str, &ArgName)> {
+        let argnames = self.args.args.iter().map(|a| &a.argname);
+        let mut names: Vec<_> = argnames
+            .chain(&self.without_globals)
+            .map(|n| (self.name, n))
+            .collect();
+        for subcmd in &self.subcommands {
+            names.extend(subcmd.names());
+        }
+        names
+    }
+
+    // Returns a name of this command (or of its subcommands) and a name of the other one (or of
+    // its subcommands) that share only one of their short and long names.
+    pub(crate) fn name_conflict<'a>(
+        &'a self,
+        other: &'a Command,
+    ) -> Option<[(&'static str, &'a ArgName); 2]> {
+        let names = self.names();
+        other.names().into_iter().find_map(|(cmd, name)| {
+            let found = names.iter().find(|(_, n)| n.conflicts(name))?;
+            Some([*found, (cmd, name)])
+        })
+    }
+
+    // Returns the subcommand whose name is the given one, ignoring case.
+    pub(crate) fn sibling(&self, name: &str) -> Option<&Command> {
+        let name = name.to_lowercase();
+        self.subcommands
+            .iter()
+            .find(|s| s.name.to_lowercase() == name)
+    }
+
+    fn add_parents(&mut self, grandparents: Vec<&'static str>, parent: &'static str) {
+        let mut parents = grandparents;
+        parents.push(parent);
+        // Subcommands of this command were added before it had any parents.
+        for subcmd in &mut self.subcommands {
+            subcmd.add_parents(parents.clone(), self.name);
+        }
+        self.parents = parents;
+    }
+
+    /// Parses the command line arguments given by [`env::args_os`]. Arguments that are not
+    /// valid UTF-8 are handled as specified by [`Command::utf8_policy`].
+    ///
+    /// # Returns
+    ///
+    /// This function returns a [`Result`] that contains the [`ParsedCommand`].
+    /// In case of error, a [`ParseError`] will be returned, which can be displayed to the user
+    /// with [`ParseError::render`].
+    #[inline]
+    pub fn parse(self) -> Result<ParsedCommand, ParseError> {
+        self.parse_from_os(env::args_os())
+    }
+
+    /// Parses the command line arguments like [`Command::parse`], but never returns an error:
+    /// the requested pages (see [`ParseError::is_page`]) are printed to [`io::stdout`] and the
+    /// program exits successfully, while the other errors are handled by [`exit_usage`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .version("1.0.0")
+    ///     .auto_version(true)
+    ///     .parse_or_exit();
+    /// ```
+    #[inline]
+    pub fn parse_or_exit(self) -> ParsedCommand {
+        self.parse().unwrap_or_else(|err| exit_usage(err))
+    }
+
+    /// Parses command line arguments that might not be valid UTF-8, as specified by
+    /// [`Command::utf8_policy`]. The first item must be the program's name. See
+    /// [`Command::parse_from`].
+    ///
+    /// # Returns
+    ///
+    /// This function returns a [`Result`] that contains the [`ParsedCommand`].
+    /// In case of error, a [`ParseError`] will be returned, which can be displayed to the user
+    /// with [`ParseError::render`]. Arguments that cannot be converted return
+    /// [`ErrorKind::NonUtf8Arg`].
+    pub fn parse_from_os(
+        self,
+        args: impl IntoIterator<Item = OsString>,
+    ) -> Result<ParsedCommand, ParseError> {
+        let utf8::Converted { args, invalid } = utf8::convert(args);
+        let policy = self.utf8_policy;
+        // The errors are created now, since the command is consumed by the parsing.
+        let errors: Vec<ParseError> = invalid
+            .iter()
+            .filter(|_| policy != Utf8Policy::Lossy)
+            .map(|(lossy, _)| ParseError::new(ErrorKind::NonUtf8Arg(lossy.clone()), &self))
+            .collect();
+        if policy == Utf8Policy::Strict {
+            if let Some(err) = errors.into_iter().next() {
+                return Err(err);
+            }
+            return self.parse_from_iter(args);
+        }
+        let mut parsed = self.parse_from_iter(args)?;
+        for ((_, original), err) in invalid.iter().zip(errors) {
+            let restored = utf8::restore(&mut parsed.args, original);
+            let restored = parsed.ancestors.iter_mut().fold(restored, |found, args| {
+                utf8::restore(args, original) || found
+            });
+            if !restored {
+                return Err(err);
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Parses command line arguments from a line typed by the user (e.g. in an admin console).
+    ///
+    /// The line is split with [`split()`] and it must not contain the program's name: the name
+    /// of this command is used instead. See [`Command::parse_from`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .subcommand(
+    ///         Command::create("user", "Manages users.")
+    ///             .arg(arg!(--name), value!(string), "Insert a name."),
+    ///     )
+    ///     .parse_from_str(r#"user --name "Some One""#)
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.get(arg!(--name)).string(), Some("Some One"));
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// This function returns a [`Result`] that contains the [`ParsedCommand`].
+    /// In case of error, a [`ParseError`] will be returned, which can be displayed to the user
+    /// with [`ParseError::render`]. Badly quoted lines return [`ErrorKind::InvalidLine`].
+    pub fn parse_from_str(self, line: &str) -> Result<ParsedCommand, ParseError> {
+        let args = split(line).map_err(|e| ParseError::new(ErrorKind::InvalidLine(e), &self))?;
+        self.parse_from_args_only(args)
+    }
+
+    /// Parses command line arguments that do not start with the program's name (e.g. when the
+    /// command is embedded in another program): the name of this command is used instead. See
+    /// [`Command::parse_from`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .parse_from_args_only(["--name".to_string(), "me".to_string()])
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.string_or(arg!(--name), ""), "me");
+    /// ```
+    pub fn parse_from_args_only(
+        self,
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<ParsedCommand, ParseError> {
+        let name = self.name.into();
+        self.parse_from_iter(iter::once(name).chain(args))
+    }
+
+    /// Parses command line arguments from a custom [`Vec<String>`] list of arguments.
+    ///
+    /// The first item must be the program's name. An empty list, which a program receives if it
+    /// is executed without even its name, is parsed as a command line without arguments. See
+    /// [`Command::parse_from_args_only`] for arguments without the program's name.
+    ///
+    /// If [`Command::auto_version`] is enabled and the version flag is used, an
+    /// [`ErrorKind::DisplayVersion`] error carrying the version page is returned.
+    ///
+    /// Every command also has a built-in `help` subcommand (unless it defines its own or
+    /// [`Command::disable_help`] is used): with `myapp help [SUBCOMMAND]...` an
+    /// [`ErrorKind::DisplayHelp`] error carrying the help page of the given subcommand is
+    /// returned. The same happens with the help flag (see [`Command::help_flag`]). Neither of
+    /// them exits the program, unless [`Command::parse_or_exit`] is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let err = Command::create("myapp", "This is my cool app.")
+    ///     .subcommand(Command::create("serve", "Starts the server."))
+    ///     .color(false)
+    ///     .parse_from_str("help serve")
+    ///     .err()
+    ///     .unwrap();
+    /// assert!(err.is_page());
+    /// assert!(err.render(false).starts_with("myapp serve"));
+    /// ```
+    ///
+    /// # Order of evaluation
+    ///
+    /// The help flag, then the version flag, win over every error: `myapp --port abc --help`
+    /// prints the help page, even though the port is invalid and required groups (see
+    /// [`Command::require_one_of`]) or positional values may be missing, in this command or in
+    /// its parents. [`Command::validator`]s run only after that, on successful parses. Without
+    /// the flags, the errors of the parent commands are reported before those of the subcommand,
+    /// in the order of the command line.
+    ///
+    /// # Allocations
+    ///
+    /// Successful parses allocate only for the values and the help page of the parsed command
+    /// ([`ParsedCommand::help`], which is shared by [`ParsedCommand::long_help`] unless an
+    /// argument has a long description). Known long names are never copied and the version page
+    /// is created only when it is printed. A command with 16 arguments allocates at most 200
+    /// times, mostly for its help page, while a command line of 15 tokens that reaches a
+    /// subcommand with one argument allocates at most 80 times, and a pathological one of 2001
+    /// tokens at most 3500 times. These budgets are checked by the benches of the `bench`
+    /// feature, which count the allocations with `internals::stats`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a [`Result`] that contains the [`ParsedCommand`].
+    /// In case of error, a [`ParseError`] will be returned, which can be displayed to the user
+    /// with [`ParseError::render`].
+    #[inline]
+    pub fn parse_from(self, args: Vec<String>) -> Result<ParsedCommand, ParseError> {
+        self.parse_from_iter(args)
+    }
+
+    /// Parses command line arguments from an iterator (e.g. the lines of a response file),
+    /// without collecting them first, unless the `--color` option is looked up ahead of them
+    /// (see [`Command::color_flag`]). The first item must be the program's name. See
+    /// [`Command::parse_from`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::iter;
+    /// # use tiny_args::*;
+    /// let file = "--name\nSome One\n--verbose\n";
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .arg(arg!(--verbose), value!(), "Prints more.")
+    ///     .parse_from_iter(iter::once("myapp").chain(file.lines()).map(String::from))
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.string_or(arg!(--name), ""), "Some One");
+    /// assert_eq!(parsed.args.try_count(arg!(--verbose)), Some(1));
+    /// ```
+    pub fn parse_from_iter(
+        self,
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<ParsedCommand, ParseError> {
+        parser::parse(self, args, None)
+    }
+
+    /// Parses the arguments this command knows and returns the others untouched, in their
+    /// original order, for programs that only handle a few arguments and pass the rest to
+    /// another one.
+    ///
+    /// Unknown arguments (a cluster of short flags is unknown if any of them is), values that
+    /// are not taken by an argument and unknown subcommands are left over. Since it is not
+    /// known whether an unknown argument takes a value, its value is left over only if it is
+    /// not a positional value of the command (see [`Command::rest`]).
+    /// Help and version are handled like in [`Command::parse_from`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let args = ["wrapper", "--verbose", "--inner", "value", "-v", "-x"];
+    /// let (parsed, leftovers) = Command::create("wrapper", "Wraps another program.")
+    ///     .arg(arg!(-'v', --verbose), value!(), "Be verbose.")
+    ///     .parse_known_from(args.iter().map(|s| s.to_string()).collect())
+    ///     .unwrap();
+    /// assert_eq!(parsed.args.count(arg!(--verbose)), 2);
+    /// assert_eq!(leftovers, ["--inner", "value", "-x"]);
+    /// ```
+    pub fn parse_known_from(
+        self,
+        args: Vec<String>,
+    ) -> Result<(ParsedCommand, Vec<String>), ParseError> {
+        let mut leftovers = Vec::new();
+        let parsed = parser::parse(self, args, Some(&mut leftovers))?;
+        Ok((parsed, leftovers))
+    }
+}
+
+/// A struct representing a parsed command.
+#[non_exhaustive]
+pub struct ParsedCommand {
+    /// Name of the command or subcommand.
+    pub name: &'static str,
+
+    /// Description of the command or subcommand. See [`Command::create`].
+    pub description: &'static str,
+
+    /// Version of the command, or of its closest parent that has one. See [`Command::version`].
+    pub version: Option<&'static str>,
+
+    /// Author of the command, or of its closest parent that has one. See [`Command::author`].
+    pub author: Option<&'static str>,
+
+    /// License of the command, or of its closest parent that has one. See [`Command::license`].
+    pub license: Option<&'static str>,
+
+    /// The help page of the parsed command.
+    ///
+    /// It can be displayed to the user, for example when the `--help` flag is used.
+    pub help: String,
+
+    /// The long help page of the parsed command.
+    ///
+    /// It is like [`ParsedCommand::help`], but it uses the arguments' long descriptions when they
+    /// have one (see [`Arg::long_description`]). Conventionally, it is displayed for `--help`
+    /// while `-h` displays the compact page.
+    pub long_help: String,
+
+    /// The list of parsed arguments.
+    ///
+    /// You can access the values of each argument value inputted by the user.
+    pub args: ArgList,
+
+    /// The parent commands if this is a subcommand.
     ///
-    /// If no value (not even default) was specified or if it is not an [`ArgValue::String`]
-    /// it returns [`None`].
-    pub fn path(&self) -> Option<&PathBuf> {
-        if let ArgValue::Path(Some(value)) = &self.argvalue {
-            Some(value)
-        } else {
-            None
-        }
-    }
+    /// If this is the root of the program the [`Vec`] is empty.
+    pub parents: Vec<&'static str>,
 
-    fn init(&mut self, input: &mut Vec<String>) -> Result<(), String> {
-        match self.argvalue {
-            ArgValue::String(_) => self.argvalue = ArgValue::String(Some(input.remove(0))),
-            ArgValue::Num(_) => {
-                self.argvalue = ArgValue::Num(Some(input.remove(0).parse().map_err(|e| {
-                    format!("'{}' value's must be a valid number: {e}", self.argname)
-                })?))
-            }
-            ArgValue::Float(_) => {
-                self.argvalue = ArgValue::Float(Some(input.remove(0).parse().map_err(|e| {
-                    format!(
-                        "'{}' value's must be a valid float number: {e}",
-                        self.argname
-                    )
-                })?))
-            }
-            ArgValue::Path(_) => {
-                self.argvalue = ArgValue::Path(Some(PathBuf::from(input.remove(0))))
-            }
-            ArgValue::Flag => (),
-        }
-        self.counter += 1;
-        Ok(())
-    }
+    /// The parsed arguments of the parent commands, in the same order as
+    /// [`ParsedCommand::parents`].
+    ///
+    /// Each command gets the arguments that precede the name of its subcommand (e.g. in
+    /// `myapp --verbose user --name me add` `--verbose` belongs to `myapp`, `--name` to `user`).
+    /// Global arguments (see [`Arg::global`]) used with a parent are also given to its
+    /// subcommands, so they can be read from [`ParsedCommand::args`].
+    pub ancestors: Vec<ArgList>,
+
+    /// Warnings found while parsing (e.g. deprecated arguments being used).
+    ///
+    /// They are not printed, show them to the user as you see fit. The arguments that were never
+    /// read are reported later by [`ParsedCommand::finish`].
+    pub warnings: Vec<String>,
+
+    /// The arguments as they were given, without the program's name.
+    ///
+    /// It contains every token, including subcommands and values, which is useful for audit logs.
+    /// The values of sensitive arguments are replaced with `***` (see [`Arg::sensitive`]).
+    pub raw: Vec<String>,
+
+    /// The positional values given to the command. See [`Command::rest`].
+    ///
+    /// They can also be read converted to other types with [`ParsedCommand::positional`].
+    /// If a plugin was given, they are the tokens that follow its name.
+    pub rest: Vec<String>,
+
+    /// The plugin given as subcommand, if any. See [`Command::plugins`].
+    pub plugin: Option<Plugin>,
+
+    path: Vec<&'static str>,
+    help_flag: ArgName,
+    rest_name: Option<&'static str>,
+    subcommands: Vec<(&'static str, &'static str)>,
 }
 
-/// A list of arguments.
+/// A positional value of a [`ParsedCommand`] (see [`Command::rest`]).
 ///
-/// This list is accessible only after the command line arguments have been parsed.
-/// You can access specific arguments with [`ArgList::get`].
-#[repr(transparent)]
-pub struct ArgList {
-    args: Vec<Arg>,
+/// Its value is converted into an [`Arg`] of the needed type with [`Positional::arg`], so the
+/// same rules of the arguments apply (e.g. [`ArgValue::Range`] checks the bounds). The other
+/// getters are shorthands for the common types: each one returns [`None`] if the value cannot
+/// be converted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Positional<'a> {
+    name: &'static str,
+    index: usize,
+    value: &'a str,
 }
 
-impl ArgList {
-    fn new() -> Self {
-        ArgList { args: Vec::new() }
-    }
-
-    fn insert(&mut self, arg: Arg) {
-        if self.args.iter().any(|a| a.argname == arg.argname) {
-            panic!(
-                "The argument '{}' already exists in this command",
-                arg.argname
-            );
-        }
-        self.args.push(arg);
+impl<'a> Positional<'a> {
+    /// Returns the name of the positional values (e.g. `FILE`).
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
     }
 
-    /// Returns the inner [`Vec`] with parsed [`Arg`]s.
-    pub fn inner(&self) -> &Vec<Arg> {
-        &self.args
+    /// Returns the position of the value among the positional values, starting from `0`.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
     }
 
-    /// Returns a given argument [`Arg`] by its [`ArgName`].
+    /// Returns the value converted into an [`Arg`] with the given value type, or [`None`] if it
+    /// is not valid for it. The argument is named after the positional values.
     ///
-    /// # Panics
+    /// # Example
     ///
-    /// Panics if the given argument does not exist in the [`Command`].
-    pub fn get(&self, argname: ArgName) -> &Arg {
-        self.args
-            .iter()
-            .find(|&arg| arg.argname == argname)
-            .unwrap_or_else(|| panic!("Argument '{argname}' does not exist"))
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .rest("PORT", "Ports to open.")
+    ///     .parse_from_str("80 0")
+    ///     .unwrap();
+    /// let port = ArgValue::Range(1..=65535, None);
+    /// assert_eq!(parsed.positional(0).unwrap().arg(port.clone()).unwrap().num(), Some(80));
+    /// assert!(parsed.positional(1).unwrap().arg(port).is_none());
+    /// ```
+    pub fn arg(&self, argvalue: ArgValue) -> Option<Arg> {
+        let mut arg = Arg::new(ArgName::long_static(self.name), argvalue, "");
+        arg.init(&mut VecDeque::from([self.value.to_string()]))
+            .ok()?;
+        arg.source = ValueSource::CommandLine;
+        Some(arg)
     }
 
-    /// Returns a given argument [`Arg`] by its [`ArgName`].
-    ///
-    /// Does not panic but returns [`None`] if the argument does not exist in the [`Command`].
-    pub fn try_get(&self, argname: ArgName) -> Option<&Arg> {
-        self.args.iter().find(|&arg| arg.argname == argname)
+    /// Returns the value as it was given.
+    #[inline]
+    pub fn string(&self) -> Option<&'a str> {
+        Some(self.value)
     }
 
-    /// Counts how many arguments were inserted by the user.
-    pub fn total_count(&self) -> usize {
-        let mut count: usize = 0;
-        for arg in &self.args {
-            count += arg.counter;
-        }
-        count
+    /// Returns the value as an [`i64`] (see [`ArgValue::Num`]).
+    pub fn num(&self) -> Option<i64> {
+        self.arg(ArgValue::Num(None))?.num()
     }
 
-    /// Checks how many times the argument has been inserted (`0` if none). Usually useful for flags.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the given `argname` does not exist in the [`Command`].
-    pub fn count(&self, argname: ArgName) -> usize {
-        self.args
-            .iter()
-            .find(|arg| arg.argname == argname)
-            .map(|arg| arg.counter)
-            .unwrap_or_else(|| panic!("Flag '{argname}' does not exist"))
+    /// Returns the value as a [`f64`] (see [`ArgValue::Float`]).
+    pub fn float(&self) -> Option<f64> {
+        self.arg(ArgValue::Float(None))?.float()
     }
 
-    fn init_arg(&mut self, argname: &ArgName, input: &mut Vec<String>) -> Result<(), String> {
-        for arg in &mut self.args {
-            if arg.argname == *argname {
-                arg.init(input)?;
-                return Ok(());
-            }
-        }
-        Err(format!("'{argname}' is not a valid argument."))
+    /// Returns the value as a [`PathBuf`] (see [`ArgValue::Path`]).
+    pub fn path(&self) -> Option<PathBuf> {
+        self.arg(ArgValue::Path(None))?.path().cloned()
     }
 }
 
-/// Builds the command line.
+/// Converts a [`ParsedCommand`] into a typed struct.
 ///
-/// It can be then used to parse the command line to get the arguments inserted by the user.
-/// See [`Command::parse`] or [`Command::parse_from`].
+/// Implement it to keep the bridge between the parsed arguments and the application's own
+/// configuration in one place, then use [`ParsedCommand::into_struct`].
+/// Every conversion error should be collected, so that the user can fix all of them at once.
 ///
 /// # Example
 ///
 /// ```rust
 /// # use tiny_args::*;
-/// let parsed = Command::create("myapp", "This is my cool app.")
-///     .author("Me!")
-///     .license("MY-LICENSE")
-///     .version("0.1.0")
-///     .arg(arg!(-'h', --help), value!(), "Shows this help.")
-///     .subcommand(
-///         Command::create("subcmd", "This is a subcommand.")
-///             .arg(arg!(-'p', --path), value!(path), "Insert a path.")
-///     )
-///     .parse();
+/// struct Config {
+///     port: i64,
+///     name: String,
+/// }
+///
+/// impl FromParsed for Config {
+///     fn from_parsed(parsed: &ParsedCommand) -> Result<Self, Vec<String>> {
+///         let mut errors = Vec::new();
+///         let port = parsed.args.get(arg!(--port)).num();
+///         if port.is_none() {
+///             errors.push("'--port' is required.".into());
+///         }
+///         let name = parsed.args.get(arg!(--name)).string();
+///         if name.is_none() {
+///             errors.push("'--name' is required.".into());
+///         }
+///         match (port, name) {
+///             (Some(port), Some(name)) => Ok(Config { port, name: name.into() }),
+///             _ => Err(errors),
+///         }
+///     }
+/// }
+///
+/// let err = Command::create("myapp", "This is my cool app.")
+///     .arg(arg!(--port), value!(num), "Insert a port.")
+///     .arg(arg!(--name), value!(string), "Insert a name.")
+///     .parse_from(vec!["myapp".into()])
+///     .unwrap()
+///     .into_struct::<Config>()
+///     .err()
+///     .unwrap();
+/// assert_eq!(err, "'--port' is required.\n'--name' is required.");
 /// ```
-pub struct Command {
-    name: &'static str,
-    description: &'static str,
-    author: Option<&'static str>,
-    version: Option<&'static str>,
-    license: Option<&'static str>,
-    color: bool,
-    args: ArgList,
-    subcommands: Vec<Command>,
-    parents: Vec<&'static str>,
+pub trait FromParsed: Sized {
+    /// Converts the parsed command, returning every conversion error on failure.
+    fn from_parsed(parsed: &ParsedCommand) -> Result<Self, Vec<String>>;
 }
 
-impl Command {
-    /// This function creates a new [`Command`].
-    pub fn create(name: &'static str, description: &'static str) -> Self {
-        Command {
-            name,
-            description,
-            version: None,
-            author: None,
-            license: None,
-            args: ArgList::new(),
-            subcommands: Vec::new(),
-            parents: Vec::new(),
-            color: true,
+impl ParsedCommand {
+    /// Rebuilds a command line that gives the same result when parsed again, without the
+    /// program's name (like [`ParsedCommand::raw`]).
+    ///
+    /// It contains only the arguments that were used: flags are repeated as many times as they
+    /// were counted, valued arguments are given their last value (or every value of a list) and
+    /// global arguments are given to the last command that has them. Their long name is used
+    /// when available. Each token can be passed to another process as it is, or quoted with
+    /// [`quote`] to build a line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'v', --verbose), ArgValue::Count(0), "Be verbose.")
+    ///     .subcommand(
+    ///         Command::create("greet", "Greets someone.")
+    ///             .arg(arg!(-'n', --name), value!(string), "Insert a name.")
+    ///             .rest("FILES", "Files to read."),
+    ///     )
+    ///     .parse_from_str("-vv greet -n 'Some One' -n me notes.txt")
+    ///     .unwrap();
+    /// let argv = parsed.to_argv();
+    /// assert_eq!(
+    ///     argv,
+    ///     ["--verbose", "--verbose", "greet", "--name", "me", "notes.txt"]
+    /// );
+    /// let line: Vec<String> = argv.iter().map(|token| quote(token)).collect();
+    /// assert_eq!(line.join(" "), "--verbose --verbose greet --name me notes.txt");
+    /// ```
+    pub fn to_argv(&self) -> Vec<String> {
+        let mut argv = Vec::new();
+        let lists: Vec<&ArgList> = self.ancestors.iter().chain([&self.args]).collect();
+        for (i, args) in lists.iter().enumerate() {
+            for arg in &args.args {
+                // Global arguments carry their value to the subcommand.
+                let inherited = lists
+                    .get(i + 1)
+                    .is_some_and(|next| arg.global && next.try_get(arg.argname.clone()).is_some());
+                if !inherited {
+                    argv.extend(arg.tokens());
+                }
+            }
+            if let Some(subcommand) = self.path.get(i) {
+                argv.push(subcommand.to_string());
+            }
         }
+        if let Some(plugin) = &self.plugin {
+            argv.push(plugin.name.clone());
+        }
+        argv.extend(self.rest.iter().cloned());
+        argv
     }
 
-    /// Specifies a new argument.
+    /// Serializes the result of the parsing as a single line of JSON, so that scripts and other
+    /// programs can read it without scraping text.
     ///
-    /// # Example:
+    /// The object has the `command` name, the `path` of subcommands (see [`ParsedCommand::path`]),
+    /// the `args` keyed by their long name (or short name if they have none), the positional values
+    /// in `rest` and the `plugin` name or `null`. Each argument has its `value` (`null` if it has
+    /// none) and its `source`: `default`, `command_line`, `override` or `env`, with the variable's
+    /// name in `env`. Flags are booleans, counts and integers are numbers, lists are arrays and
+    /// bytes are strings of hex digits prefixed with `hex:`. Sensitive values are replaced with
+    /// `***`.
+    ///
+    /// # Example
     ///
     /// ```rust
     /// # use tiny_args::*;
-    /// let cmd = Command::create("myapp", "This is my cool app.")
-    ///     .arg(arg!(-'h', --help), value!(), "Shows this help.");
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity.")
+    ///     .subcommand(
+    ///         Command::create("upload", "Uploads files.")
+    ///             .arg(arg!(--port), value!(num, 8080), "Insert a port.")
+    ///             .rest("FILE", "The files to upload."),
+    ///     )
+    ///     .parse_from_str("-v upload a.txt")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     parsed.to_json(),
+    ///     concat!(
+    ///         r#"{"command":"upload","path":["upload"],"args":{"#,
+    ///         r#""verbose":{"value":1,"source":"command_line"},"#,
+    ///         r#""port":{"value":8080,"source":"default"}},"#,
+    ///         r#""rest":["a.txt"],"plugin":null}"#
+    ///     )
+    /// );
     /// ```
+    pub fn to_json(&self) -> String {
+        json::parsed(self)
+    }
+
+    /// Returns the values that were discarded because their argument was used again and only
+    /// the last value is kept (see [`DuplicatePolicy::Overwrite`]), in the order they were
+    /// given. It is useful to note in audit logs that part of the input was ignored.
     ///
-    /// # Panic
+    /// The arguments of the parent commands come first and arguments with no discarded values
+    /// are not listed. The values of sensitive arguments are replaced with `***` (see
+    /// [`Arg::sensitive`]).
     ///
-    /// Panics if an argument with the same name was already inputted.
-    #[inline]
-    pub fn arg(mut self, argname: ArgName, argtype: ArgValue, description: &'static str) -> Self {
-        self.args.insert(Arg::new(argname, argtype, description));
-        self
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--path), value!(path), "Insert a path.")
+    ///     .parse_from_str("--path /tmp --path /srv --path /home")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     parsed.overwritten_values(),
+    ///     [(arg!(--path), vec!["/tmp".to_string(), "/srv".to_string()])]
+    /// );
+    /// ```
+    pub fn overwritten_values(&self) -> Vec<(ArgName, Vec<String>)> {
+        let lists = self.ancestors.iter().chain([&self.args]);
+        let mut overwritten: Vec<(ArgName, Vec<String>)> = Vec::new();
+        for arg in lists.flat_map(|args| &args.args) {
+            if arg.overwritten.is_empty() {
+                continue;
+            }
+            let values = if arg.sensitive {
+                vec!["***".into(); arg.overwritten.len()]
+            } else {
+                arg.overwritten.clone()
+            };
+            // Global arguments carry what they discarded to the subcommands, so the last copy
+            // has everything.
+            match overwritten
+                .iter_mut()
+                .find(|(name, _)| *name == arg.argname)
+            {
+                Some((_, previous)) => *previous = values,
+                None => overwritten.push((arg.argname.clone(), values)),
+            }
+        }
+        overwritten
     }
 
-    /// Specifies a new subcommand [`Command`].
+    /// Returns how many times `-v, --verbose` was used minus how many times `-q, --quiet` was,
+    /// so `0` is the default verbosity and negative levels are quieter.
     ///
-    /// # Panic
+    /// The flags are added by [`Command::verbosity_flags`]. If they were not, the arguments
+    /// with those names are counted instead, if any.
+    pub fn verbosity(&self) -> i64 {
+        // Global arguments carry their counts to the subcommands, so the last one is the total.
+        let count = |argname: ArgName| {
+            let lists = self.ancestors.iter().chain([&self.args]).rev();
+            lists
+                .filter_map(|args| args.try_count(argname.clone()))
+                .next()
+                .unwrap_or_default() as i64
+        };
+        count(arg!(-'v', --verbose)) - count(arg!(-'q', --quiet))
+    }
+
+    /// Returns when the output should be colored, as chosen with the `--color` option.
     ///
-    /// Panics if a subcommand with the same name was already inputted.
-    pub fn subcommand(mut self, subcmd: Command) -> Self {
-        if self.subcommands.iter().any(|s| s.name == subcmd.name) {
-            panic!("Subcommand '{}' already exists.", subcmd.name);
-        }
-        let mut subcmd = subcmd;
-        subcmd.add_parents(self.parents.clone(), self.name);
-        self.subcommands.push(subcmd);
-        self
+    /// The option is added by [`Command::color_flag`]. If it was not, the argument with that
+    /// name is read instead, if any. Without a valid choice it is [`ColorChoice::Auto`].
+    pub fn color_choice(&self) -> ColorChoice {
+        let mut lists = self.ancestors.iter().chain([&self.args]).rev();
+        lists
+            .find_map(|args| args.try_get(arg!(--color)))
+            .and_then(|arg| ColorChoice::from_name(arg.string()?))
+            .unwrap_or_default()
     }
 
-    /// Specifies the version of the program.
+    /// Returns a warning for each argument given by the user (on the command line or in the
+    /// environment, see [`Command::args_env`]) that the application never read, to catch the
+    /// flags that silently do nothing after a refactor.
     ///
-    /// It will appear on the help page ([`ParsedCommand::help`]).
-    #[inline]
-    pub fn version(mut self, version: &'static str) -> Self {
-        self.version = Some(version);
-        self
+    /// Call it once the command was dispatched: the arguments are marked as read by their
+    /// accessors or by [`Arg::mark_used`]. A global argument is read if any of its copies was.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(--name), value!(string), "Insert a name.")
+    ///     .arg(arg!(--dry-run), value!(), "Does nothing.")
+    ///     .parse_from_str("--name me --dry-run")
+    ///     .unwrap();
+    /// println!("Hello {}!", parsed.args.string_or(arg!(--name), "nobody"));
+    /// assert_eq!(parsed.finish(), ["'--dry-run' was given but never read"]);
+    /// ```
+    pub fn finish(&self) -> Vec<String> {
+        let lists: Vec<&ArgList> = self.ancestors.iter().chain([&self.args]).collect();
+        let read = |arg: &Arg| {
+            arg.is_used()
+                || arg.global
+                    && lists
+                        .iter()
+                        .filter_map(|args| args.try_get(arg.argname.clone()))
+                        .any(|copy| copy.global && copy.is_used())
+        };
+        let mut warnings = Vec::new();
+        for (i, args) in lists.iter().enumerate() {
+            for arg in &args.args {
+                // Global arguments carry their value to the subcommand.
+                let inherited = lists
+                    .get(i + 1)
+                    .is_some_and(|next| arg.global && next.try_get(arg.argname.clone()).is_some());
+                let given = matches!(arg.source, ValueSource::CommandLine | ValueSource::Env(_));
+                if !inherited && given && !read(arg) {
+                    warnings.push(format!("'{}' was given but never read", arg.argname));
+                }
+            }
+        }
+        warnings
     }
 
-    /// Specifies the author of the program.
+    /// Returns the names of the subcommands that were used to reach this command, without the
+    /// root's name.
     ///
-    /// It will appear on the help page ([`ParsedCommand::help`]).
+    /// If this is the root of the program the slice is empty.
     #[inline]
-    pub fn author(mut self, author: &'static str) -> Self {
-        self.author = Some(author);
-        self
+    pub fn path(&self) -> &[&'static str] {
+        &self.path
     }
 
-    /// Specifies the license of the program.
+    /// Returns the name and the description of each subcommand of this command, in the order
+    /// they were added. It is useful to suggest the subcommands that can follow (e.g. in an
+    /// interactive shell).
+    ///
+    /// The built-in `help` subcommand and the plugins are not listed.
     ///
-    /// It will appear on the help page ([`ParsedCommand::help`]).
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .subcommand(
+    ///         Command::create("user", "Manages users.")
+    ///             .subcommand(Command::create("add", "Adds a user."))
+    ///             .subcommand(Command::create("remove", "Removes a user.")),
+    ///     )
+    ///     .parse_from(vec!["myapp".into(), "user".into()])
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     parsed.available_subcommands(),
+    ///     [("add", "Adds a user."), ("remove", "Removes a user.")]
+    /// );
+    /// ```
     #[inline]
-    pub fn license(mut self, license: &'static str) -> Self {
-        self.license = Some(license);
-        self
+    pub fn available_subcommands(&self) -> &[(&'static str, &'static str)] {
+        &self.subcommands
     }
 
-    /// Specifies whether or not the help page should be colored.
-    /// By default it is colored.
+    /// Checks whether this is the subcommand reached with the given path. See
+    /// [`ParsedCommand::path`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .subcommand(
+    ///         Command::create("user", "Manages users.")
+    ///             .subcommand(Command::create("add", "Adds a user.")),
+    ///     )
+    ///     .parse_from(vec!["myapp".into(), "user".into(), "add".into()])
+    ///     .unwrap();
+    /// assert!(parsed.is(["user", "add"]));
+    /// assert!(!parsed.is(["user"]));
+    /// ```
     #[inline]
-    pub fn color(mut self, color: bool) -> Self {
-        self.color = color;
-        self
+    pub fn is(&self, path: impl AsRef<[&'static str]>) -> bool {
+        self.path == path.as_ref()
     }
 
-    fn add_parents(&mut self, grandparents: Vec<&'static str>, parent: &'static str) {
-        let mut parents = grandparents;
-        parents.push(parent);
-        self.parents = parents;
+    /// Checks whether the help flag declared as [`ArgValue::Flag`] was used with this command or
+    /// with one of its parents. Commands that do not declare it are skipped.
+    ///
+    /// The help flag is the one set with [`Command::help_flag`], or `-h, --help` if there is
+    /// none. An argument matches it only if all of its names are names of the help flag, so
+    /// `-h, --hidden` does not.
+    ///
+    /// This is useful when the help page is handled manually. The help flag set with
+    /// [`Command::help_flag`] does not need it, since it is handled during parsing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .arg(arg!(-'h', --help), value!(), "Shows this help.")
+    ///     .subcommand(Command::create("subcmd", "This is a subcommand."))
+    ///     .parse_from_str("-h subcmd")
+    ///     .unwrap();
+    /// if parsed.wants_help() {
+    ///     println!("{}", parsed.help);
+    /// }
+    /// # assert!(parsed.wants_help());
+    /// ```
+    pub fn wants_help(&self) -> bool {
+        iter::once(&self.args)
+            .chain(&self.ancestors)
+            .flat_map(|args| &args.args)
+            .filter(|arg| arg.argname.within(&self.help_flag))
+            .any(|arg| arg.argvalue == ArgValue::Flag && arg.counter > 0)
     }
 
-    /// Parses the command line arguments given by [`env::args`].
+    /// Converts this command into a struct implementing [`FromParsed`].
     ///
     /// # Returns
     ///
-    /// This function returns a [`Result`] that contains the [`ParsedCommand`].
-    /// In case of error, a [`String`] will be returned containing an error message that can be
-    /// displayed to the user.
+    /// In case of error, a [`String`] will be returned containing every conversion error, one per
+    /// line, that can be displayed to the user.
     #[inline]
-    pub fn parse(self) -> Result<ParsedCommand, String> {
-        self.parse_from(env::args().collect())
+    pub fn into_struct<T: FromParsed>(self) -> Result<T, String> {
+        T::from_parsed(&self).map_err(|errors| errors.join("\n"))
     }
 
-    /// Parses command line arguments from a custom [`Vec<String>`] list of arguments.
+    /// Replaces the value of the given argument. See [`ArgList::set`].
     ///
-    /// # Returns
+    /// # Panics
     ///
-    /// This function returns a [`Result`] that contains the [`ParsedCommand`].
-    /// In case of error, a [`String`] will be returned containing an error message that can be
-    /// displayed to the user.
+    /// Panics if the given argument does not exist in the [`Command`] or if the new value is not
+    /// of the same kind of the argument's value. See [`ArgList::try_set`].
+    #[cfg_attr(
+        feature = "strict",
+        deprecated(note = "panics on unknown names, use `args.try_set` instead")
+    )]
     #[inline]
-    pub fn parse_from(self, args: Vec<String>) -> Result<ParsedCommand, String> {
-        parser::parse(self, args)
+    pub fn override_value(&mut self, argname: ArgName, argvalue: ArgValue) {
+        if let Err(e) = self.args.try_set(argname, argvalue) {
+            panic!("{e}");
+        }
     }
-}
-
-/// A struct representing a parsed command.
-#[non_exhaustive]
-pub struct ParsedCommand {
-    /// Name of the command or subcommand.
-    pub name: &'static str,
 
-    /// The help page of the parsed command.
+    /// Returns the positional value at the given index (see [`Command::rest`]), or [`None`] if
+    /// there are not enough values.
     ///
-    /// It can be displayed to the user, for example when the `--help` flag is used.
-    pub help: String,
-
-    /// The list of parsed arguments.
+    /// # Example
     ///
-    /// You can access the values of each argument value inputted by the user.
-    pub args: ArgList,
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .rest("PORT", "Ports to open.")
+    ///     .parse_from_str("80 http")
+    ///     .unwrap();
+    /// assert_eq!(parsed.positional(0).unwrap().num(), Some(80));
+    /// assert_eq!(parsed.positional(1).unwrap().num(), None);
+    /// assert!(parsed.positional(2).is_none());
+    /// ```
+    pub fn positional(&self, index: usize) -> Option<Positional<'_>> {
+        Some(Positional {
+            name: self.rest_name?,
+            index,
+            value: self.rest.get(index)?,
+        })
+    }
 
-    /// The parent commands if this is a subcommand.
+    /// Returns every positional value (see [`Command::rest`]).
+    pub fn positionals(&self) -> impl Iterator<Item = Positional<'_>> {
+        (0..self.rest.len()).filter_map(|index| self.positional(index))
+    }
+
+    /// Returns every positional value if the positional values of this command have the given
+    /// name (see [`Command::rest`]), otherwise it returns none.
     ///
-    /// If this is the root of the program the [`Vec`] is empty.
-    pub parents: Vec<&'static str>,
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let parsed = Command::create("myapp", "This is my cool app.")
+    ///     .rest("FILE", "Files to upload.")
+    ///     .parse_from_str("a.txt b.txt")
+    ///     .unwrap();
+    /// assert_eq!(parsed.positionals_named("FILE").count(), 2);
+    /// assert_eq!(parsed.positionals_named("PORT").count(), 0);
+    /// ```
+    pub fn positionals_named<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = Positional<'a>> + 'a {
+        self.positionals()
+            .filter(move |positional| positional.name == name)
+    }
 }
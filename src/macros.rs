@@ -20,7 +20,7 @@
 // Email: hex0x0000@protonmail.com
 
 #[cfg(doc)]
-use crate::{ArgName, ArgValue};
+use crate::{Arg, ArgName, ArgValue, Command};
 
 /// A shorthand macro for [`ArgName`].
 ///
@@ -45,6 +45,22 @@ use crate::{ArgName, ArgValue};
 /// # use tiny_args::*;
 /// assert_eq!(arg! { --long-help }, ArgName::long("long-help"));
 /// ```
+///
+/// Long names that are not made of identifiers (e.g. with digits at the start of a word or with
+/// dots) can be written as string literals. Their names are checked at compile time.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// assert_eq!(arg!(--"http2"), ArgName::long("http2"));
+/// assert_eq!(arg!(-'l', --"log.level"), ArgName::both('l', "log.level"));
+/// ```
+///
+/// ```rust,compile_fail
+/// # use tiny_args::*;
+/// let name = arg!(--"log level");
+/// ```
 #[macro_export]
 macro_rules! arg {
     (--$long:ident) => {{
@@ -55,6 +71,14 @@ macro_rules! arg {
         ArgName::long_static(concat!(stringify!($first), $("-", stringify!($long),)+))
     }};
 
+    (--$long:literal) => {{
+        const _: () = assert!(
+            ArgName::is_valid_long($long),
+            "A long name cannot be empty, contain whitespace or start with a dash"
+        );
+        ArgName::long_static($long)
+    }};
+
     (-$short:literal, --$long:ident) => {{
         ArgName::both_static($short, stringify!($long))
     }};
@@ -63,6 +87,15 @@ macro_rules! arg {
         ArgName::both_static($short, concat!(stringify!($first), $("-", stringify!($long),)+))
     }};
 
+    (-$short:literal, --$long:literal) => {{
+        const _: () = assert!(
+            ArgName::is_valid_short($short) && ArgName::is_valid_long($long),
+            "A short name cannot be a dash nor whitespace and a long name cannot be empty, \
+             contain whitespace or start with a dash"
+        );
+        ArgName::both_static($short, $long)
+    }};
+
     (-$short:literal) => {{
         ArgName::short($short)
     }};
@@ -77,10 +110,36 @@ macro_rules! arg {
 /// assert_eq!(value!(), ArgValue::Flag);
 /// assert_eq!(value!(string), ArgValue::String(None));
 /// assert_eq!(value!(path, "/default/path"), ArgValue::Path(Some("/default/path".into())));
+/// assert_eq!(value!(count), ArgValue::Count(0));
+/// assert_eq!(value!(list, ["a", "b"]), ArgValue::List(vec!["a".into(), "b".into()]));
+/// assert_eq!(value!(paths, ["/a"]), ArgValue::PathList(vec!["/a".into()]));
+/// assert_eq!(value!(bytes, b"key"), ArgValue::Bytes(Some(b"key".to_vec())));
+/// assert_eq!(value!(ratio, 0.5), ArgValue::Ratio(Some(0.5)));
+/// ```
+///
+/// Values with constraints are written with the constraint after the kind:
+///
+/// ```rust
+/// # use tiny_args::*;
+/// assert_eq!(value!(num in 1..=65535), ArgValue::Range(1..=65535, None));
+/// assert_eq!(value!(num in 1..=65535, 8080), ArgValue::Range(1..=65535, Some(8080)));
+/// assert_eq!(value!(choice ["json", "yaml"]), ArgValue::Choice(&["json", "yaml"], None));
+/// assert_eq!(
+///     value!(choice ["json", "yaml"], "json"),
+///     ArgValue::Choice(&["json", "yaml"], Some("json".into()))
+/// );
+/// assert_eq!(value!(path exists), ArgValue::ExistingPath(None));
 /// ```
 ///
-/// Accepted values are: `string`, `num`, `float`, `path`. Each corresponding to their [`ArgValue`]
-/// field. Since [`ArgValue::Flag`] does not carry any value it is defined as `value!()`.
+/// Accepted values are: `string`, `num`, `float`, `path`, `list`, `count`, `paths`, `bytes`,
+/// `ratio`. Each corresponding to their [`ArgValue`] field (`paths` is [`ArgValue::PathList`]).
+/// Since [`ArgValue::Flag`] does not carry any value it is defined as `value!()`.
+/// [`ArgValue::Count`] starts from `0` unless another value is given. `num in` is
+/// [`ArgValue::Range`], `choice` is [`ArgValue::Choice`] and `path exists` is
+/// [`ArgValue::ExistingPath`].
+///
+/// Values without a default (and counts or numbers with one) can be used in constants and
+/// statics, see [`Command::arg_table`].
 #[macro_export]
 macro_rules! value {
     () => {
@@ -98,6 +157,30 @@ macro_rules! value {
     (path) => {
         ArgValue::Path(None)
     };
+    (list) => {
+        ArgValue::List(Vec::new())
+    };
+    (count) => {
+        ArgValue::Count(0)
+    };
+    (paths) => {
+        ArgValue::PathList(Vec::new())
+    };
+    (bytes) => {
+        ArgValue::Bytes(None)
+    };
+    (ratio) => {
+        ArgValue::Ratio(None)
+    };
+    (num in $range:expr) => {
+        ArgValue::Range($range, None)
+    };
+    (choice [$($choice:literal),+ $(,)?]) => {
+        ArgValue::Choice(&[$($choice),+], None)
+    };
+    (path exists) => {
+        ArgValue::ExistingPath(None)
+    };
     (string, $default:expr) => {
         ArgValue::String(Some($default.into()))
     };
@@ -110,4 +193,182 @@ macro_rules! value {
     (path, $default:expr) => {
         ArgValue::Path(Some($default.into()))
     };
+    (list, $default:expr) => {
+        ArgValue::List($default.into_iter().map(Into::into).collect())
+    };
+    (count, $default:expr) => {
+        ArgValue::Count($default)
+    };
+    (paths, $default:expr) => {
+        ArgValue::PathList($default.into_iter().map(Into::into).collect())
+    };
+    (bytes, $default:expr) => {
+        ArgValue::Bytes(Some($default.into()))
+    };
+    (ratio, $default:expr) => {
+        ArgValue::Ratio(Some($default))
+    };
+    (num in $range:expr, $default:expr) => {
+        ArgValue::Range($range, Some($default))
+    };
+    (choice [$($choice:literal),+ $(,)?], $default:expr) => {
+        ArgValue::Choice(&[$($choice),+], Some($default.into()))
+    };
+    (path exists, $default:expr) => {
+        ArgValue::ExistingPath(Some($default.into()))
+    };
+}
+
+/// A shorthand macro to declare a whole [`Command`] in one block.
+///
+/// It expands to the builder calls: `name` and `description` come first, followed by any
+/// builder method of [`Command`] written as `method: value` (or `method: (a, b)` for methods with
+/// more arguments), the `args` and the `subcommands`, which are declared like the root command.
+///
+/// Each argument is written as `[name] => [value], "description"`, where `name` and `value` are
+/// what would be given to [`arg`] and [`value`] and the description is a string literal. It can be
+/// followed by the builder methods of
+/// [`Arg`] in curly brackets.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = command! {
+///     name: "myapp",
+///     description: "This is my cool app.",
+///     version: "0.1.0",
+///     auto_version: true,
+///     args: [
+///         [-'v', --verbose] => [count], "Increases verbosity." { global: true },
+///         [--path] => [path, "/default/path"], "Specify a path to something.",
+///     ],
+///     subcommands: [
+///         {
+///             name: "upload",
+///             description: "Uploads some files.",
+///             rest: ("FILE", "Files to upload."),
+///             args: [
+///                 [-'n', --num] => [num, 42], "Insert a number.",
+///             ],
+///         },
+///     ],
+/// };
+/// let parsed = cmd.parse_from_str("upload -v a.txt").unwrap();
+/// assert!(parsed.is(["upload"]));
+/// assert_eq!(parsed.args.get(arg!(-'v')).count_value(), Some(1));
+/// assert_eq!(parsed.args.get(arg!(--num)).num(), Some(42));
+/// assert_eq!(parsed.rest, ["a.txt"]);
+/// ```
+#[macro_export]
+macro_rules! command {
+    (name: $name:expr, description: $description:expr $(, $($fields:tt)*)?) => {
+        $crate::command!(@fields Command::create($name, $description); $($($fields)*)?)
+    };
+
+    (@fields $cmd:expr;) => {
+        $cmd
+    };
+
+    (@fields $cmd:expr; args: [$($args:tt)*] $(, $($fields:tt)*)?) => {
+        $crate::command!(@fields $crate::command!(@args $cmd; $($args)*); $($($fields)*)?)
+    };
+
+    (@fields $cmd:expr; subcommands: [$({$($subcmd:tt)*}),* $(,)?] $(, $($fields:tt)*)?) => {
+        $crate::command!(
+            @fields $cmd$(.subcommand($crate::command!($($subcmd)*)))*; $($($fields)*)?
+        )
+    };
+
+    (@fields $cmd:expr; $method:ident: ($($value:expr),+) $(, $($fields:tt)*)?) => {
+        $crate::command!(@fields $cmd.$method($($value),+); $($($fields)*)?)
+    };
+
+    (@fields $cmd:expr; $method:ident: $value:expr $(, $($fields:tt)*)?) => {
+        $crate::command!(@fields $cmd.$method($value); $($($fields)*)?)
+    };
+
+    (@args $cmd:expr;) => {
+        $cmd
+    };
+
+    (
+        @args $cmd:expr;
+        [$($name:tt)*] => [$($value:tt)*], $description:literal
+        $({ $($method:ident: $option:expr),* $(,)? })?
+        $(, $($args:tt)*)?
+    ) => {
+        $crate::command!(
+            @args $cmd.add_arg(
+                Arg::new($crate::arg!($($name)*), $crate::value!($($value)*), $description)
+                    $($(.$method($option))*)?
+            );
+            $($($args)*)?
+        )
+    };
+}
+
+/// Declares a module with a constant [`ArgName`] for each argument and a `register` function
+/// that adds all of them to a [`Command`].
+///
+/// Arguments are declared like in [`command`], each preceded by the name of its constant (and
+/// optionally by its doc comments). Since every constant is registered, reading the arguments
+/// through the constants (e.g. `parsed.args.get(cli::NUM)`) cannot panic because of a typo:
+/// a misspelled constant is a compile error, while a misspelled [`arg`] would not be.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// args! {
+///     /// The arguments of my cool app.
+///     pub mod cli {
+///         /// Increases verbosity.
+///         VERBOSE: [-'v', --verbose] => [count], "Increases verbosity." { global: true },
+///         NUM: [-'n', --num] => [num, 42], "Insert a number.",
+///     }
+/// }
+///
+/// let cmd = cli::register(Command::create("myapp", "This is my cool app."));
+/// let parsed = cmd.parse_from_str("-vv").unwrap();
+/// assert_eq!(parsed.args.get(cli::VERBOSE).count_value(), Some(2));
+/// assert_eq!(parsed.args.get(cli::NUM).num(), Some(42));
+/// ```
+#[macro_export]
+macro_rules! args {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $module:ident {
+            $(
+                $(#[$argmeta:meta])*
+                $constant:ident: [$($name:tt)*] => [$($value:tt)*], $description:literal
+                $({ $($method:ident: $option:expr),* $(,)? })?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis mod $module {
+            #[allow(unused_imports)]
+            use $crate::{Arg, ArgName, ArgValue, Command};
+
+            $(
+                $(#[$argmeta])*
+                pub const $constant: ArgName = $crate::arg!($($name)*);
+            )*
+
+            /// Adds every argument of this module to the given command.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the command already has an argument with the same name.
+            pub fn register(cmd: Command) -> Command {
+                cmd$(
+                    .add_arg(
+                        Arg::new($constant, $crate::value!($($value)*), $description)
+                            $($(.$method($option))*)?
+                    )
+                )*
+            }
+        }
+    };
 }
@@ -0,0 +1,77 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+// Lines available on the terminal. `LINES` is exported by most shells.
+fn height() -> usize {
+    env::var("LINES")
+        .ok()
+        .and_then(|lines| lines.parse().ok())
+        .unwrap_or(24)
+}
+
+fn pager(text: &str) -> io::Result<()> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut pager = pager.split_whitespace();
+    let program = pager
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Empty pager"))?;
+    let mut child = Command::new(program)
+        .args(pager)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit the pager before reading everything.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// Prints the given text (usually a help page) through a pager.
+///
+/// The pager is `$PAGER`, or `less -R` if it is not set. It is used only if the standard
+/// output is a terminal and the text does not fit in it, otherwise (or if the pager cannot be
+/// started) the text is printed normally.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tiny_args::*;
+/// let parsed = Command::create("myapp", "This is my cool app.")
+///     .arg(arg!(-'h', --help), value!(), "Shows this help.")
+///     .parse()
+///     .unwrap();
+/// if parsed.wants_help() {
+///     print_paged(&parsed.help);
+/// }
+/// ```
+pub fn print_paged(text: &str) {
+    if io::stdout().is_terminal() && text.lines().count() >= height() && pager(text).is_ok() {
+        return;
+    }
+    println!("{text}");
+}
@@ -19,40 +19,767 @@
 //
 // Email: hex0x0000@protonmail.com
 
-use crate::*;
-
-fn args(mut argslist: ArgList, mut inputargs: Vec<String>) -> Result<ArgList, String> {
-    let mut argnameopt: Option<ArgName> = None;
-    while let Some(input) = inputargs.first() {
-        if let Some(argname) = &argnameopt {
-            argslist.init_arg(argname, &mut inputargs)?;
-            argnameopt.take();
-        } else if input.starts_with("--") {
-            if let Some(input) = input.get(2..) {
-                argnameopt.replace(ArgName::Long(input.into()));
-                inputargs.remove(0);
-            } else {
-                return Err(format!("'{input}' is not a valid long argument."));
+use std::{collections::VecDeque, env, fmt, iter, mem, path::Path};
+
+use crate::{
+    trace::{TraceEvent, Tracer},
+    *,
+};
+
+/// Which prefixes introduce the arguments on the command line. `--` always introduces the long
+/// ones.
+///
+/// See [`Command::prefixes`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = || {
+///     Command::create("legacy", "A tool with an old syntax.")
+///         .arg(arg!(-'x', --trace), value!(), "Traces the commands.")
+///         .prefixes(Prefixes {
+///             plus: true,
+///             ..Prefixes::DEFAULT
+///         })
+/// };
+/// let parsed = cmd().parse_from_str("-x +x").unwrap();
+/// assert_eq!(parsed.args.get(arg!(--trace)).enabled(), Some(false));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Prefixes {
+    /// Whether or not a single `-` introduces short arguments (`-v`, `-abc`). Without them,
+    /// such tokens are positional values (e.g. negative numbers) and no argument can have a
+    /// short name.
+    pub short: bool,
+
+    /// Whether or not a `+` switches off the short flags (`+x`, `+abc`), which are switched on
+    /// with `-` like usual. The last one that is used wins, see [`Arg::enabled`].
+    pub plus: bool,
+}
+
+impl Prefixes {
+    /// The usual prefixes: `-` for the short arguments and `--` for the long ones.
+    pub const DEFAULT: Prefixes = Prefixes {
+        short: true,
+        plus: false,
+    };
+
+    // Whether or not the token is introduced by one of the prefixes.
+    pub(crate) fn option(self, token: &str) -> bool {
+        token.starts_with("--")
+            || (self.short && token.starts_with('-'))
+            || (self.plus && token.len() > 1 && token.starts_with('+'))
+    }
+
+    // The name of an automatic flag, which has no short name without the short arguments.
+    pub(crate) fn name(self, argname: ArgName) -> ArgName {
+        match argname {
+            ArgName::Both { long, .. } if !self.short => ArgName::Long(long),
+            argname => argname,
+        }
+    }
+}
+
+impl Default for Prefixes {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A token of the command line, classified by its form. See [`Tokenizer`].
+///
+/// Parsing happens in two stages: the tokenizer classifies each token without knowing the
+/// command, then the resolver applies the tokens to the command, deciding which arguments take
+/// values and which tokens are subcommands. The tokenizer is public so that programs can resolve
+/// the tokens in their own way.
+///
+/// A token can be turned back into the original text with [`String::from`] or [`ToString`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Token {
+    /// A long argument without its dashes, with the value attached after the first `=`, if any
+    /// (`--name` or `--name=value`). A name that begins with a dash comes from a token with too
+    /// many dashes (`---name`).
+    LongOpt {
+        /// The name, without the dashes.
+        name: String,
+
+        /// The attached value.
+        value: Option<String>,
+    },
+
+    /// One or more short arguments without their dash (`-v`, `-abc`). Since the last one can
+    /// have an attached value (`-n5`), the characters are split by the resolver.
+    ShortCluster(String),
+
+    /// One or more short flags switched off, without their `+` (`+x`, `+abc`). It is only
+    /// returned if enabled, see [`Prefixes::plus`].
+    PlusCluster(String),
+
+    /// A token taken as the value of an argument, whatever its form. It is only returned by
+    /// [`Tokenizer::next_value`].
+    Value(String),
+
+    /// The `--` token, after which every token is a positional value.
+    Separator,
+
+    /// Any other token, which is a positional value or the name of a subcommand. A lone `-`
+    /// is a positional value too (usually meaning stdin).
+    Positional(String),
+}
+
+impl Token {
+    /// Classifies the token by its form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// assert_eq!(
+    ///     Token::new("--name=me".into()),
+    ///     Token::LongOpt {
+    ///         name: "name".into(),
+    ///         value: Some("me".into())
+    ///     }
+    /// );
+    /// assert_eq!(Token::new("-vn5".into()), Token::ShortCluster("vn5".into()));
+    /// assert_eq!(Token::new("--".into()), Token::Separator);
+    /// assert_eq!(Token::new("-".into()), Token::Positional("-".into()));
+    /// ```
+    #[inline]
+    pub fn new(token: String) -> Self {
+        Self::with_prefixes(token, Prefixes::DEFAULT)
+    }
+
+    /// Classifies the token by its form, with the given prefixes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tiny_args::*;
+    /// let prefixes = Prefixes {
+    ///     short: false,
+    ///     plus: true,
+    /// };
+    /// assert_eq!(
+    ///     Token::with_prefixes("-5".into(), prefixes),
+    ///     Token::Positional("-5".into())
+    /// );
+    /// assert_eq!(
+    ///     Token::with_prefixes("+ab".into(), prefixes),
+    ///     Token::PlusCluster("ab".into())
+    /// );
+    /// ```
+    pub fn with_prefixes(mut token: String, prefixes: Prefixes) -> Self {
+        // The names reuse the buffer of the token, so that only attached values allocate.
+        if token == "--" {
+            Token::Separator
+        } else if token.starts_with("--") {
+            token.drain(..2);
+            let value = token.find('=').map(|i| {
+                let value = token[i + 1..].to_string();
+                token.truncate(i);
+                value
+            });
+            Token::LongOpt { name: token, value }
+        } else if prefixes.short && token.len() > 1 && token.starts_with('-') {
+            token.remove(0);
+            Token::ShortCluster(token)
+        } else if prefixes.plus && token.len() > 1 && token.starts_with('+') {
+            token.remove(0);
+            Token::PlusCluster(token)
+        } else {
+            Token::Positional(token)
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LongOpt { name, value: None } => write!(f, "--{name}"),
+            Token::LongOpt {
+                name,
+                value: Some(value),
+            } => write!(f, "--{name}={value}"),
+            Token::ShortCluster(cluster) => write!(f, "-{cluster}"),
+            Token::PlusCluster(cluster) => write!(f, "+{cluster}"),
+            Token::Value(value) | Token::Positional(value) => f.write_str(value),
+            Token::Separator => f.write_str("--"),
+        }
+    }
+}
+
+impl From<Token> for String {
+    fn from(token: Token) -> Self {
+        match token {
+            Token::Value(value) | Token::Positional(value) => value,
+            token => token.to_string(),
+        }
+    }
+}
+
+/// The first stage of parsing, which splits the command line (without the program's name) into
+/// [`Token`]s.
+///
+/// Since only the command knows which arguments take a value, the tokens are classified by their
+/// form, and the caller takes the values with [`Tokenizer::next_value`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let mut tokens = Tokenizer::new(["-v", "--name", "-me-", "file"].map(String::from));
+/// assert_eq!(tokens.next(), Some(Token::ShortCluster("v".into())));
+/// assert_eq!(
+///     tokens.next(),
+///     Some(Token::LongOpt {
+///         name: "name".into(),
+///         value: None
+///     })
+/// );
+/// // The name takes a value, even if it begins with a dash.
+/// assert_eq!(tokens.next_value(), Some(Token::Value("-me-".into())));
+/// assert_eq!(tokens.next(), Some(Token::Positional("file".into())));
+/// assert_eq!(tokens.next(), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Tokenizer {
+    input: VecDeque<String>,
+    prefixes: Prefixes,
+}
+
+impl Tokenizer {
+    /// Creates a tokenizer of the given tokens, with the usual prefixes.
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Tokenizer {
+            input: args.into_iter().collect(),
+            prefixes: Prefixes::DEFAULT,
+        }
+    }
+
+    /// Specifies the prefixes that introduce the arguments (see [`Prefixes`]). By default they
+    /// are [`Prefixes::DEFAULT`].
+    #[inline]
+    pub fn prefixes(mut self, prefixes: Prefixes) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Takes the next token as a value, whatever its form.
+    #[inline]
+    pub fn next_value(&mut self) -> Option<Token> {
+        self.input.pop_front().map(Token::Value)
+    }
+
+    /// Returns the next token without taking it.
+    #[inline]
+    pub fn peek(&self) -> Option<Token> {
+        let token = self.input.front()?.clone();
+        Some(Token::with_prefixes(token, self.prefixes))
+    }
+
+    /// Returns the tokens that were not taken yet, unclassified.
+    #[inline]
+    pub fn remaining(self) -> Vec<String> {
+        self.input.into()
+    }
+}
+
+impl Iterator for Tokenizer {
+    type Item = Token;
+
+    #[inline]
+    fn next(&mut self) -> Option<Token> {
+        let token = self.input.pop_front()?;
+        Some(Token::with_prefixes(token, self.prefixes))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.input.len(), Some(self.input.len()))
+    }
+}
+
+// Initializes the argument, telling the tracer which token it takes as its value.
+pub(crate) fn init(
+    argslist: &mut ArgList,
+    argname: &ArgName,
+    inputargs: &mut VecDeque<String>,
+    warnings: &mut Vec<String>,
+    tracer: Option<&Tracer>,
+) -> Result<(), ErrorKind> {
+    if let (Some(tracer), Some(token)) = (tracer, inputargs.front()) {
+        if valued(argslist, argname.clone()) {
+            let secret = sensitive(argslist, argname.clone());
+            tracer.emit(TraceEvent::Value {
+                arg: argname,
+                token: if secret { "***" } else { token },
+            });
+        }
+    }
+    if let Some(arg) = argslist.try_get(argname.clone()) {
+        let misplaced = match arg.placement {
+            Placement::Anywhere => false,
+            Placement::Before => arg.inherited,
+            Placement::After => !arg.inherited,
+        };
+        if misplaced {
+            let kind = ErrorKind::MisplacedArg {
+                arg: arg.argname.clone(),
+                placement: arg.placement,
+            };
+            if valued(argslist, argname.clone()) {
+                inputargs.pop_front();
             }
-        } else if input.starts_with('-') {
-            if let Some(input) = input.chars().nth(1) {
-                argnameopt.replace(ArgName::Short(input));
-                inputargs.remove(0);
+            return Err(kind);
+        }
+    }
+    if let Err(kind) = argslist.init_arg(argname, inputargs, warnings) {
+        // The value of a repeated argument goes with it, so that it is not read as something
+        // else when every error is collected.
+        if matches!(kind, ErrorKind::Duplicate(_)) && valued(argslist, argname.clone()) {
+            inputargs.pop_front();
+        }
+        return Err(kind);
+    }
+    if let Some(tracer) = tracer {
+        if let Some(arg) = argslist.try_get(argname.clone()) {
+            tracer.emit(TraceEvent::Arg(arg));
+        }
+    }
+    Ok(())
+}
+
+// Records the error if every error is collected (see `Command::collect_errors`), otherwise
+// returns it.
+fn report(errors: &mut Option<Vec<ErrorKind>>, kind: ErrorKind) -> Result<(), ErrorKind> {
+    match errors {
+        Some(errors) => {
+            errors.push(kind);
+            Ok(())
+        }
+        None => Err(kind),
+    }
+}
+
+// Whether or not the token names an argument that the list does not have. Only the names
+// before the value of a short argument are checked (`-abvalue`).
+fn unknown(argslist: &ArgList, token: &Token) -> bool {
+    match token {
+        Token::LongOpt { name, .. } => {
+            argslist.try_get(argslist.long_name(name)).is_none() && argslist.negated(name).is_none()
+        }
+        Token::ShortCluster(cluster) => {
+            for short in cluster.chars() {
+                match argslist.try_get(ArgName::Short(short)) {
+                    None => return true,
+                    Some(arg) if !matches!(arg.argvalue, ArgValue::Flag | ArgValue::Count(_)) => {
+                        break
+                    }
+                    Some(_) => (),
+                }
+            }
+            false
+        }
+        Token::PlusCluster(cluster) => cluster.chars().any(|short| {
+            !matches!(
+                argslist
+                    .try_get(ArgName::Short(short))
+                    .map(|arg| &arg.argvalue),
+                Some(ArgValue::Flag)
+            )
+        }),
+        Token::Separator => true,
+        Token::Value(_) | Token::Positional(_) => false,
+    }
+}
+
+// Switches off the flag with the given short name, which was used in a cluster of `+` (see
+// `Prefixes::plus`).
+pub(crate) fn toggle(argslist: &mut ArgList, short: char, cluster: &str) -> Result<(), ErrorKind> {
+    let argname = ArgName::Short(short);
+    match argslist.try_get(argname.clone()) {
+        None => Err(ErrorKind::UnknownArg(argname)),
+        Some(arg) if arg.argvalue != ArgValue::Flag => {
+            Err(ErrorKind::InvalidArg(format!("+{cluster}")))
+        }
+        Some(_) => argslist.negate_arg(&argname, None),
+    }
+}
+
+// Switches off the flags of a cluster of `+` (`+abc`).
+fn toggles(
+    argslist: &mut ArgList,
+    cluster: &str,
+    tracer: Option<&Tracer>,
+    errors: &mut Option<Vec<ErrorKind>>,
+) -> Result<(), ErrorKind> {
+    for short in cluster.chars() {
+        if let Err(kind) = toggle(argslist, short, cluster) {
+            report(errors, kind)?;
+        } else if let (Some(tracer), Some(arg)) = (tracer, argslist.try_get(ArgName::Short(short)))
+        {
+            tracer.emit(TraceEvent::Arg(arg));
+        }
+    }
+    Ok(())
+}
+
+// Initializes the argument named by a long token, which takes the next token as its value
+// unless the value is attached or the next token is `--`.
+#[allow(clippy::too_many_arguments)]
+fn long(
+    argslist: &mut ArgList,
+    name: &str,
+    value: Option<String>,
+    tokens: &mut Tokenizer,
+    warnings: &mut Vec<String>,
+    tracer: Option<&Tracer>,
+    errors: &mut Option<Vec<ErrorKind>>,
+) -> Result<(), ErrorKind> {
+    let argname = argslist.long_name(name);
+    let negated = argslist
+        .negated(name)
+        .filter(|_| argslist.try_get(argname.clone()).is_none())
+        .map(|arg| arg.argname.clone());
+    if let Some(negated) = negated {
+        if let Err(kind) = argslist.negate_arg(&negated, value) {
+            report(errors, kind)?;
+        } else if let (Some(tracer), Some(arg)) = (tracer, argslist.try_get(negated)) {
+            tracer.emit(TraceEvent::Arg(arg));
+        }
+        return Ok(());
+    }
+    match value {
+        Some(value) => {
+            if let Some(arg) = argslist.try_get(argname.clone()) {
+                if !valued(argslist, argname.clone()) {
+                    return report(errors, arg.invalid_value(value, "omitted"));
+                }
+            }
+            tokens.input.push_front(value);
+        }
+        // `--` is never a value, unless it is attached (`--key=--`).
+        None if tokens.input.front().is_some_and(|next| next == "--")
+            && valued(argslist, argname.clone()) =>
+        {
+            return report(errors, ErrorKind::MissingValue(argname));
+        }
+        None => (),
+    }
+    if let Err(kind) = init(argslist, &argname, &mut tokens.input, warnings, tracer) {
+        report(errors, kind)?;
+    }
+    Ok(())
+}
+
+// Initializes the arguments of a short cluster (`-abc`), the last of which can have an
+// attached value (`-n5`).
+fn shorts(
+    argslist: &mut ArgList,
+    cluster: &str,
+    tokens: &mut Tokenizer,
+    warnings: &mut Vec<String>,
+    tracer: Option<&Tracer>,
+    errors: &mut Option<Vec<ErrorKind>>,
+) -> Result<(), ErrorKind> {
+    let mut chars = cluster.chars();
+    let mut short = chars.next().expect("The token is not a lone dash");
+    loop {
+        let attached = chars.as_str();
+        if attached.starts_with(width::is_zero_width) {
+            return report(errors, ErrorKind::InvalidArg(format!("-{cluster}")));
+        }
+        let argname = ArgName::Short(short);
+        if !attached.is_empty() && !valued(argslist, argname.clone()) {
+            if let Err(kind) = init(argslist, &argname, &mut tokens.input, warnings, tracer) {
+                report(errors, kind)?;
+            }
+            short = chars.next().expect("The cluster is not over");
+            continue;
+        }
+        if !attached.is_empty() {
+            // The value can be separated by `=` too (`-n=5`).
+            let value = attached.strip_prefix('=').unwrap_or(attached);
+            tokens.input.push_front(value.into());
+        }
+        if let Err(kind) = init(argslist, &argname, &mut tokens.input, warnings, tracer) {
+            report(errors, kind)?;
+        }
+        return Ok(());
+    }
+}
+
+// The resolver, which applies the tokens of a command to its list of arguments. The arguments
+// take their values straight from the tokenizer, whatever their form. If the leftovers are
+// given, the tokens that are not recognized are moved there instead of being errors.
+// If `posix` is set, the first token that is neither an argument nor a value ends the arguments:
+// it is moved with every following token to the positional values or to the leftovers. `--` always
+// ends them, and every following token is moved there too.
+#[allow(clippy::too_many_arguments)]
+fn resolve(
+    argslist: &mut ArgList,
+    inputargs: VecDeque<String>,
+    mut rest: Option<&mut Vec<String>>,
+    mut leftovers: Option<&mut Vec<String>>,
+    posix: bool,
+    prefixes: Prefixes,
+    warnings: &mut Vec<String>,
+    tracer: Option<&Tracer>,
+    errors: &mut Option<Vec<ErrorKind>>,
+) -> Result<(), ErrorKind> {
+    let mut tokens = Tokenizer {
+        input: inputargs,
+        prefixes,
+    };
+    while let Some(token) = tokens.next() {
+        let dashed = !matches!(&token, Token::Positional(value) if !value.starts_with('-'));
+        if let Some(tracer) = tracer.filter(|_| dashed) {
+            tracer.emit(TraceEvent::Token(&redact(argslist, &token.to_string())));
+        }
+        let unrecognized = match &token {
+            Token::Positional(value) => value != "-" && rest.is_none(),
+            Token::Separator => rest.is_none(),
+            token => unknown(argslist, token),
+        };
+        if let Some(leftovers) = leftovers.as_mut().filter(|_| unrecognized) {
+            let end = if (posix && !dashed) || token == Token::Separator {
+                tokens.input.len()
             } else {
-                return Err(format!("'{input}' is not a valid short argument."));
+                0
+            };
+            leftovers.push(token.into());
+            leftovers.extend(tokens.input.drain(..end));
+            continue;
+        }
+        match token {
+            Token::LongOpt { ref name, .. } if name.starts_with('-') => {
+                report(errors, ErrorKind::TooManyDashes(token.into()))?;
             }
-        } else {
-            return Err(format!("'{input}' is not an argument nor a value."));
+            Token::Separator if tokens.input.is_empty() => {
+                report(errors, ErrorKind::TrailingDoubleDash)?;
+            }
+            // With positional values, a lone `-` is one of them (usually meaning stdin).
+            Token::Positional(value) if value == "-" && rest.is_none() => {
+                report(errors, ErrorKind::LoneDash)?;
+            }
+            Token::Separator => {
+                for value in tokens.input.drain(..) {
+                    match &mut rest {
+                        Some(rest) => {
+                            if let Some(tracer) = tracer {
+                                tracer.emit(TraceEvent::Positional(&value));
+                            }
+                            rest.push(value);
+                        }
+                        None => report(errors, ErrorKind::UnexpectedToken(value))?,
+                    }
+                }
+            }
+            Token::LongOpt { name, value } => {
+                long(
+                    argslist,
+                    &name,
+                    value,
+                    &mut tokens,
+                    warnings,
+                    tracer,
+                    errors,
+                )?;
+            }
+            Token::ShortCluster(cluster) => {
+                shorts(argslist, &cluster, &mut tokens, warnings, tracer, errors)?;
+            }
+            Token::PlusCluster(cluster) => toggles(argslist, &cluster, tracer, errors)?,
+            Token::Value(value) | Token::Positional(value) => match &mut rest {
+                Some(rest) => {
+                    let end = if posix { tokens.input.len() } else { 0 };
+                    for value in iter::once(value).chain(tokens.input.drain(..end)) {
+                        if let Some(tracer) = tracer {
+                            tracer.emit(TraceEvent::Positional(&value));
+                        }
+                        rest.push(value);
+                    }
+                }
+                None => report(errors, ErrorKind::UnexpectedToken(value))?,
+            },
         }
     }
-    if let Some(argname) = &argnameopt {
-        argslist.init_arg(argname, &mut inputargs)?;
+    Ok(())
+}
+
+// Adds the verbosity flags to the root, unless it already uses their names.
+fn add_verbosity(root: &mut Command) {
+    if !root.verbosity {
+        return;
+    }
+    let flags = [
+        (arg!(-'v', --verbose), root.strings.verbose_flag),
+        (arg!(-'q', --quiet), root.strings.quiet_flag),
+    ];
+    for (argname, description) in flags {
+        let argname = root.prefixes.name(argname);
+        if root.args.try_get(argname.clone()).is_none() {
+            let flag = Arg::new(argname, ArgValue::Count(0), description);
+            root.args.insert(flag.global(true));
+        }
     }
-    Ok(argslist)
+}
+
+// Checks that no argument has a short name if the short arguments are disabled.
+fn check_shorts(cmd: &Command, help_flag: Option<&ArgName>) {
+    let argnames = cmd.args.args.iter().map(|a| &a.argname).chain(help_flag);
+    for argname in argnames {
+        if matches!(argname, ArgName::Short(_) | ArgName::Both { .. }) {
+            panic!(
+                "The argument '{argname}' of '{}' has a short name, but the short arguments are \
+                 disabled",
+                cmd.name
+            );
+        }
+    }
+    for subcmd in &cmd.subcommands {
+        check_shorts(subcmd, None);
+    }
+}
+
+// Adds the color option to the root, unless it already uses its name.
+fn add_color(root: &mut Command) {
+    if root.color_flag && root.args.try_get(arg!(--color)).is_none() {
+        let option = Arg::new(
+            arg!(--color),
+            ArgValue::Choice(ColorChoice::NAMES, Some("auto".into())),
+            root.strings.color_flag,
+        );
+        root.args.insert(option.value_name("WHEN").global(true));
+    }
+}
+
+// Returns the last valid choice of the color option, looking ahead of the parser so that the
+// help pages and the errors follow it. Invalid values are left to the parser, which reports them.
+fn color_choice(input: &[String]) -> Option<ColorChoice> {
+    let mut tokens = input.iter().take_while(|t| *t != "--");
+    let mut choice = None;
+    while let Some(token) = tokens.next() {
+        let value = match token.strip_prefix("--color") {
+            Some("") => tokens.next().map(String::as_str),
+            Some(value) => value.strip_prefix('='),
+            None => None,
+        };
+        choice = value.and_then(ColorChoice::from_name).or(choice);
+    }
+    choice
+}
+
+// Colors the help pages and the errors of every command as chosen.
+fn set_color(cmd: &mut Command, color: bool) {
+    cmd.terminal = Some(TerminalInfo {
+        color,
+        ..cmd.terminal_info()
+    });
+    for subcmd in &mut cmd.subcommands {
+        set_color(subcmd, color);
+    }
+}
+
+// Adds the global arguments of each command to its subcommands.
+fn inherit_globals(cmd: &mut Command, inherited: &[Arg]) {
+    for argname in &cmd.without_globals {
+        if !inherited.iter().any(|a| a.argname == *argname) {
+            panic!(
+                "'{argname}' is not a global argument of the parents of '{}'",
+                cmd.name
+            );
+        }
+    }
+    if let Some(arg) = cmd
+        .args
+        .args
+        .iter()
+        .find(|a| !a.global && a.placement != Placement::Anywhere)
+    {
+        panic!(
+            "The argument '{}' can only be placed if it is global",
+            arg.argname
+        );
+    }
+    for arg in &cmd.args.args {
+        for name in &arg.only_with {
+            if !cmd.subcommands.iter().any(|s| s.name == *name) {
+                panic!(
+                    "The argument '{}' is only valid with '{name}', which is not a subcommand \
+                     of '{}'",
+                    arg.argname, cmd.name
+                );
+            }
+        }
+    }
+    for arg in inherited {
+        if !cmd.without_globals.contains(&arg.argname) {
+            cmd.args.insert(Arg {
+                inherited: true,
+                ..arg.clone()
+            });
+        }
+    }
+    for argname in cmd.required_groups.iter().flatten() {
+        if cmd.args.try_get(argname.clone()).is_none() {
+            panic!("'{argname}' is not an argument of '{}'", cmd.name);
+        }
+    }
+    let globals: Vec<Arg> = cmd.args.args.iter().filter(|a| a.global).cloned().collect();
+    for subcmd in &mut cmd.subcommands {
+        inherit_globals(subcmd, &globals);
+    }
+}
+
+// Gives a subcommand the settings and the metadata of its parent, when it is reached.
+fn inherit(parent: &Command, found: &mut Command) {
+    found.strings = parent.strings;
+    found.prefixes = parent.prefixes;
+    found.error_formatter = parent.error_formatter.clone();
+    found.tracer = parent.tracer.clone();
+    found.version = found.version.or(parent.version);
+    found.author = found.author.or(parent.author);
+    found.license = found.license.or(parent.license);
+    found.homepage = found.homepage.or(parent.homepage);
+    found.repository = found.repository.or(parent.repository);
+}
+
+// Detaches the subcommand at the given path from the tree, with everything it would get from
+// its parents if the whole tree was parsed, so that it can be parsed on its own.
+pub(crate) fn detach<'p>(
+    mut cmd: Command,
+    path: impl IntoIterator<Item = &'p str>,
+) -> Option<Command> {
+    add_verbosity(&mut cmd);
+    add_color(&mut cmd);
+    for name in path {
+        let mut found = extract(&mut cmd.subcommands, name)?;
+        inherit(&cmd, &mut found);
+        found.help_flag = cmd.help_flag.clone();
+        found.builtin_help = cmd.builtin_help;
+        found.color_flag = cmd.color_flag;
+        for arg in cmd.args.args.iter().filter(|a| a.global) {
+            if !found.without_globals.contains(&arg.argname)
+                && found.args.try_get(arg.argname.clone()).is_none()
+            {
+                found.args.insert(Arg {
+                    inherited: true,
+                    ..arg.clone()
+                });
+            }
+        }
+        // The globals it opted out of are gone, so the list would refer to nothing.
+        found.without_globals.clear();
+        cmd = found;
+    }
+    Some(cmd)
 }
 
 // NOTE: use Vec extract_if when it becomes stable
-fn extract(mut subcmds: Vec<Command>, name: &str) -> Option<Command> {
+fn extract(subcmds: &mut Vec<Command>, name: &str) -> Option<Command> {
     let mut i = 0;
     while i < subcmds.len() {
         if subcmds[i].name == name {
@@ -63,29 +790,594 @@ fn extract(mut subcmds: Vec<Command>, name: &str) -> Option<Command> {
     None
 }
 
-fn traverse(root: Command, args: &mut Vec<String>) -> Result<Command, String> {
-    let mut cmd = root;
-    while let Some(arg) = args.first() {
-        if arg.starts_with('-') {
+// Whether or not the argument exists and takes a value.
+pub(crate) fn valued(args: &ArgList, argname: ArgName) -> bool {
+    args.try_get(argname)
+        .is_some_and(|arg| !matches!(arg.argvalue, ArgValue::Flag | ArgValue::Count(_)))
+}
+
+// Whether or not the argument exists, takes a value and its value is secret.
+fn sensitive(args: &ArgList, argname: ArgName) -> bool {
+    valued(args, argname.clone()) && args.try_get(argname).is_some_and(|arg| arg.sensitive)
+}
+
+// Returns the argument of the command that takes the next token as its value, if the given
+// token is one.
+fn takes_value(cmd: &Command, token: &str) -> Option<ArgName> {
+    if let Some(long) = token.strip_prefix("--") {
+        let argname = cmd.args.long_name(long);
+        return (!long.contains('=') && valued(&cmd.args, argname.clone())).then_some(argname);
+    }
+    let mut chars = token[1..].chars();
+    while let Some(short) = chars.next() {
+        if valued(&cmd.args, ArgName::Short(short)) {
+            return chars.as_str().is_empty().then_some(ArgName::Short(short));
+        }
+    }
+    None
+}
+
+// Returns the argument that takes a value named by the token, whether or not the value is
+// attached to it.
+fn valued_name(args: &ArgList, token: &str) -> Option<ArgName> {
+    if let Some(long) = token.strip_prefix("--") {
+        let name = long.split_once('=').map_or(long, |(name, _)| name);
+        let argname = args.long_name(name);
+        return valued(args, argname.clone()).then_some(argname);
+    }
+    token[1..]
+        .chars()
+        .map(ArgName::Short)
+        .find(|argname| valued(args, argname.clone()))
+}
+
+// How many values the argument can take after its first one. See `Arg::num_values`.
+fn extra_values(args: &ArgList, argname: ArgName) -> usize {
+    args.try_get(argname)
+        .and_then(|arg| arg.num_values.as_ref())
+        .map_or(0, |range| range.end() - 1)
+}
+
+// Masks the value of a sensitive argument attached to the token (`--key=value` or `-kvalue`).
+fn redact(args: &ArgList, token: &str) -> String {
+    if let Some(long) = token.strip_prefix("--") {
+        return match long.split_once('=') {
+            Some((name, _)) if sensitive(args, args.long_name(name)) => {
+                format!("--{name}=***")
+            }
+            _ => token.into(),
+        };
+    }
+    let mut chars = token[1..].chars();
+    while let Some(short) = chars.next() {
+        if valued(args, ArgName::Short(short)) {
+            let attached = chars.as_str();
+            if !attached.is_empty() && sensitive(args, ArgName::Short(short)) {
+                let value = attached.strip_prefix('=').unwrap_or(attached);
+                return format!("{}***", &token[..token.len() - value.len()]);
+            }
             break;
         }
-        if let Some(found) = extract(cmd.subcommands, arg) {
-            cmd = found;
-            args.remove(0);
+    }
+    token.into()
+}
+
+// Resolves the subcommand named by the input, splitting the input between the commands that
+// were traversed (root first): each command gets the arguments that precede its subcommand.
+// The built-in `help` subcommand can be placed before the names of the subcommands, in which
+// case the returned flag is set. The input is also returned with the values of the sensitive
+// arguments masked.
+// If `known` is set, unknown subcommands are left to the command's arguments.
+// If a plugin is given, it is returned with the tokens that follow it, which are not parsed.
+#[allow(clippy::type_complexity)]
+fn traverse(
+    root: Command,
+    args: impl Iterator<Item = String>,
+    known: bool,
+    warnings: &mut Vec<String>,
+) -> Result<
+    (
+        Vec<(Command, VecDeque<String>)>,
+        bool,
+        Vec<String>,
+        Option<(Plugin, Vec<String>)>,
+    ),
+    ParseError,
+> {
+    let mut levels = Vec::new();
+    let mut plugin: Option<(Plugin, Vec<String>)> = None;
+    let autocorrect = root.autocorrect;
+    let mut cmd = root;
+    let mut input = VecDeque::with_capacity(args.size_hint().0);
+    let mut raw = Vec::with_capacity(args.size_hint().0);
+    let mut help = false;
+    let mut value = false;
+    let mut secret = false;
+    let mut positional = false;
+    // How many more values the last argument can take, and whether or not they are secret.
+    let mut more = 0;
+    let mut group = (0, false);
+    for arg in args {
+        if let Some((_, external)) = &mut plugin {
+            raw.push(arg.clone());
+            external.push(arg);
+            continue;
+        }
+        // Neither `--` nor a subcommand is taken as a value, so the argument is left without one.
+        if value && (arg == "--" || cmd.subcommands.iter().any(|s| s.name == arg)) {
+            (value, secret) = (false, false);
+        }
+        if more > 0 && !arg.starts_with('-') {
+            more -= 1;
+            raw.push(if secret { "***".into() } else { arg.clone() });
+            secret &= more > 0;
+            input.push_back(arg);
+            continue;
+        } else if more > 0 {
+            (more, secret) = (0, false);
+        }
+        // Only dashed arguments can take a value, the flags switched off with `+` cannot.
+        let option = cmd.prefixes.option(&arg);
+        let dashed = !value && option && arg.starts_with('-');
+        raw.push(if secret {
+            "***".into()
+        } else if dashed {
+            redact(&cmd.args, &arg)
         } else {
-            return Err(format!("'{arg}' is not a valid subcommand."));
+            arg.clone()
+        });
+        let taker = dashed.then(|| takes_value(&cmd, &arg)).flatten();
+        let named = dashed.then(|| valued_name(&cmd.args, &arg)).flatten();
+        if let Some(argname) = &named {
+            let extra = extra_values(&cmd.args, argname.clone());
+            group = (extra, extra > 0 && sensitive(&cmd.args, argname.clone()));
+        }
+        secret = taker
+            .clone()
+            .is_some_and(|argname| sensitive(&cmd.args, argname));
+        if value || positional || (option && !help) {
+            // The first value is either this token or attached to the argument.
+            if value || (named.is_some() && taker.is_none()) {
+                (more, secret) = group;
+            }
+            value = !value && !positional && taker.is_some();
+            // Every token after `--` is a positional value of this command.
+            positional |= option && arg == "--";
+            input.push_back(arg);
+        } else if !help && arg == "help" && help::builtin_help(&cmd) {
+            help = true;
+        } else if let Some(mut found) = extract(&mut cmd.subcommands, &arg).or_else(|| {
+            // Only tokens that would be unknown subcommands are corrected.
+            let plugin = cmd.plugins.iter().any(|p| p.name == arg);
+            if !help && (plugin || cmd.rest.is_some() || !input.is_empty() || known) {
+                return None;
+            }
+            let name = autocorrect::resolve(autocorrect, &cmd.subcommands, &arg, &cmd.strings)?;
+            warnings.push(format!("'{arg}' was corrected to '{name}'"));
+            extract(&mut cmd.subcommands, name)
+        }) {
+            inherit(&cmd, &mut found);
+            if let Some(tracer) = &cmd.tracer {
+                tracer.emit(TraceEvent::Subcommand {
+                    parent: cmd.name,
+                    name: found.name,
+                });
+            }
+            levels.push((mem::replace(&mut cmd, found), mem::take(&mut input)));
+        } else if let Some(found) = cmd.plugins.iter().find(|p| !help && p.name == arg) {
+            plugin = Some((found.clone(), Vec::new()));
+        } else if (cmd.rest.is_some() || !input.is_empty() || known) && !help {
+            positional = cmd.rest.is_some();
+            input.push_back(arg);
+        } else {
+            return Err(ParseError::new(ErrorKind::UnknownSubcommand(arg), &cmd));
+        }
+    }
+    levels.push((cmd, input));
+    Ok((levels, help, raw, plugin))
+}
+
+// Reports the groups of the command of which no argument was used.
+fn check_groups(cmd: &Command, errors: &mut Option<Vec<ErrorKind>>) -> Result<(), ErrorKind> {
+    for group in &cmd.required_groups {
+        let used = group.iter().any(|argname| {
+            cmd.args
+                .try_get(argname.clone())
+                .is_some_and(|arg| arg.counter > 0 || arg.enabled.is_some())
+        });
+        if !used {
+            report(errors, ErrorKind::MissingOneOf(group.clone()))?;
+        }
+    }
+    Ok(())
+}
+
+// Reports the arguments used without any of the subcommands they are valid with. A global
+// argument is reported once, even though each subcommand has a copy of it.
+fn check_subcommands(
+    lists: &[&ArgList],
+    path: &[&str],
+    errors: &mut Option<Vec<ErrorKind>>,
+) -> Result<(), ErrorKind> {
+    let mut reported: Vec<&ArgName> = Vec::new();
+    for arg in lists.iter().flat_map(|list| &list.args) {
+        if arg.only_with.is_empty()
+            || (arg.counter == 0 && arg.enabled.is_none())
+            || arg.only_with.iter().any(|name| path.contains(name))
+            || reported.contains(&&arg.argname)
+        {
+            continue;
+        }
+        reported.push(&arg.argname);
+        report(
+            errors,
+            ErrorKind::RequiresSubcommand {
+                arg: arg.argname.clone(),
+                subcommands: arg.only_with.clone(),
+            },
+        )?;
+    }
+    Ok(())
+}
+
+// Carries the global arguments used with a command to its subcommand.
+fn pass_globals(parent: &ArgList, child: &mut ArgList) {
+    for arg in parent
+        .args
+        .iter()
+        .filter(|a| a.global && (a.counter > 0 || a.enabled.is_some()))
+    {
+        if let Some(inherited) = child.args.iter_mut().find(|a| a.argname == arg.argname) {
+            inherited.argvalue = arg.argvalue.clone();
+            inherited.counter = arg.counter;
+            inherited.enabled = arg.enabled;
+            inherited.sizes = arg.sizes.clone();
+            inherited.overwritten = arg.overwritten.clone();
+            inherited.source = arg.source;
+        }
+    }
+}
+
+fn counters(args: &ArgList) -> Vec<(usize, Option<bool>)> {
+    args.args
+        .iter()
+        .map(|arg| (arg.counter, arg.enabled))
+        .collect()
+}
+
+// Records where the arguments that were used since the counters were taken come from.
+fn mark(args: &mut ArgList, counters: Vec<(usize, Option<bool>)>, source: ValueSource) {
+    for (arg, (counter, enabled)) in args.args.iter_mut().zip(counters) {
+        // Negated flags are switched off without being counted.
+        if arg.counter > counter || arg.enabled != enabled {
+            arg.source = source;
         }
     }
-    Ok(cmd)
 }
 
-pub fn parse(root: Command, mut input: Vec<String>) -> Result<ParsedCommand, String> {
-    input.remove(0);
-    let command = traverse(root, &mut input)?;
-    Ok(ParsedCommand {
+// Adds the version flag, unless the command already uses one of its names.
+fn add_version(command: &mut Command) -> bool {
+    command.version_flag = command.prefixes.name(command.version_flag.clone());
+    if !command.auto_version || command.args.try_get(command.version_flag.clone()).is_some() {
+        return false;
+    }
+    command.args.insert(Arg::new(
+        command.version_flag.clone(),
+        ArgValue::Flag,
+        command.strings.version_flag,
+    ));
+    true
+}
+
+// Gives the help settings of the root to every command and adds the help flag to them, unless
+// they already use one of its names.
+fn add_help(cmd: &mut Command, help_flag: &Option<ArgName>, builtin_help: bool) {
+    cmd.help_flag = help_flag.clone();
+    cmd.builtin_help = builtin_help;
+    if let Some(flag) = help_flag {
+        if cmd.args.try_get(flag.clone()).is_none() {
+            cmd.args.insert(Arg::new(
+                flag.clone(),
+                ArgValue::Flag,
+                cmd.strings.help_flag,
+            ));
+        }
+    }
+    for subcmd in &mut cmd.subcommands {
+        subcmd.strings = cmd.strings;
+        add_help(subcmd, help_flag, builtin_help);
+    }
+}
+
+// Reports the errors of the tokens, which were held back so that the help and version flags
+// win over them.
+fn settle(errors: &mut Option<Vec<ErrorKind>>, held: Vec<ErrorKind>) -> Result<(), ErrorKind> {
+    held.into_iter().try_for_each(|kind| report(errors, kind))
+}
+
+// Whether or not the help flag was used.
+fn wants_help(args: &ArgList, help_flag: &Option<ArgName>) -> bool {
+    help_flag
+        .as_ref()
+        .and_then(|flag| args.try_get(flag.clone()))
+        .is_some_and(|arg| arg.counter > 0 && arg.argvalue == ArgValue::Flag)
+}
+
+// Warns about the variables with the prefix that are neither the one with extra arguments nor
+// named after a long argument. See `Command::env_prefix`.
+fn unknown_env(
+    prefix: &str,
+    args_env: Option<&str>,
+    lists: &[&ArgList],
+    warnings: &mut Vec<String>,
+) {
+    let known: Vec<String> = lists
+        .iter()
+        .flat_map(|args| &args.args)
+        .filter_map(|arg| match &arg.argname {
+            ArgName::Long(long) | ArgName::Both { long, .. } => Some(format!(
+                "{prefix}{}",
+                long.to_uppercase().replace(['-', '.'], "_")
+            )),
+            ArgName::Short(_) => None,
+        })
+        .chain(args_env.map(String::from))
+        .collect();
+    let mut unknown: Vec<String> = env::vars_os()
+        .filter_map(|(var, _)| var.into_string().ok())
+        .filter(|var| var.starts_with(prefix) && !known.contains(var))
+        .collect();
+    unknown.sort();
+    for var in unknown {
+        warnings.push(format!("'{var}' is not a known environment variable"));
+    }
+}
+
+// Returns the subcommand selected by the binary's name, if any.
+fn applet(root: &Command, program: &str) -> Option<String> {
+    let name = Path::new(program).file_name()?.to_str()?;
+    let name = name
+        .strip_prefix(root.name)
+        .and_then(|n| n.strip_prefix('-'))
+        .unwrap_or(name);
+    root.subcommands
+        .iter()
+        .any(|s| s.name == name)
+        .then(|| name.into())
+}
+
+// Parses the input (with the program's name first, if any: an empty input is a command line without
+// arguments), which is consumed while the subcommands are resolved. If the leftovers are given, the
+// tokens that are not recognized are moved there instead of being errors.
+pub fn parse(
+    mut root: Command,
+    input: impl IntoIterator<Item = String>,
+    mut leftovers: Option<&mut Vec<String>>,
+) -> Result<ParsedCommand, ParseError> {
+    add_verbosity(&mut root);
+    add_color(&mut root);
+    if !root.prefixes.short {
+        check_shorts(&root, root.help_flag.as_ref());
+    }
+    inherit_globals(&mut root, &[]);
+    let (help_flag, builtin_help) = (root.help_flag.clone(), root.builtin_help);
+    add_help(&mut root, &help_flag, builtin_help);
+    let mut input = input.into_iter();
+    // Programs can be executed with no arguments at all, not even their name.
+    let program = input.next().unwrap_or_default();
+    // The tokens are consumed lazily, unless the color option has to be found ahead of them.
+    let ahead: Vec<String> = if root.color_flag {
+        input.by_ref().collect()
+    } else {
+        Vec::new()
+    };
+    match color_choice(&ahead) {
+        Some(ColorChoice::Always) => set_color(&mut root, true),
+        Some(ColorChoice::Never) => set_color(&mut root, false),
+        Some(ColorChoice::Auto) | None => (),
+    }
+    let (args_env, env_prefix, color_flag) = (root.args_env, root.env_prefix, root.color_flag);
+    let mut errors = root.collect_errors.then(Vec::new);
+    let applet = root.multicall.then(|| applet(&root, &program)).flatten();
+    let applet_name = applet.is_some();
+    let input = applet.into_iter().chain(ahead).chain(input);
+    let mut warnings = Vec::new();
+    let (mut levels, help_subcommand, mut raw, plugin) =
+        traverse(root, input, leftovers.is_some(), &mut warnings)?;
+    if applet_name {
+        raw.remove(0);
+    }
+    let (mut command, input) = levels.pop().expect("The command is always traversed");
+    let auto_version = add_version(&mut command);
+    if help_subcommand {
+        let page = help::create(&command, true);
+        return Err(ParseError::new(ErrorKind::DisplayHelp(page), &command));
+    }
+    if command.subcommand_required
+        && !command.subcommands.is_empty()
+        && input.is_empty()
+        && plugin.is_none()
+    {
+        let subcommands = command.subcommands.iter().map(|s| s.name).collect();
+        report(&mut errors, ErrorKind::MissingSubcommand(subcommands))
+            .map_err(|kind| ParseError::new(kind, &command))?;
+    }
+    let mut extra = VecDeque::new();
+    if let Some(var) = args_env {
+        let value = env::var_os(var).unwrap_or_default();
+        // The extra arguments are parsed on their own, so the last one cannot take its value
+        // from the command line.
+        match split(&value.to_string_lossy()) {
+            Ok(tokens)
+                if !tokens
+                    .last()
+                    .is_some_and(|t| t.starts_with('-') && takes_value(&command, t).is_some()) =>
+            {
+                extra = tokens.into()
+            }
+            _ => {
+                return Err(ParseError::new(
+                    ErrorKind::InvalidEnvArgs(var.into()),
+                    &command,
+                ))
+            }
+        }
+    }
+    let mut path: Vec<&'static str> = command.parents.iter().skip(1).copied().collect();
+    if !command.parents.is_empty() {
+        path.push(command.name);
+    }
+    let help = help::create(&command, false);
+    // The long page differs only if an argument has a long description.
+    let long_help = if command
+        .args
+        .args
+        .iter()
+        .any(|a| a.long_description.is_some())
+    {
+        help::create(&command, true)
+    } else {
+        help.clone()
+    };
+    // The parent commands with the errors of their tokens, which are held back like those of the
+    // command, so that the help and version flags win over them too.
+    let mut parents: Vec<(Command, Vec<ErrorKind>)> = Vec::new();
+    let mut validators: Vec<error::Validator> = Vec::new();
+    for (mut ancestor, input) in levels {
+        validators.extend(ancestor.validator.clone());
+        if let Some((parent, _)) = parents.last() {
+            pass_globals(&parent.args, &mut ancestor.args);
+        }
+        if let Some(tracer) = &ancestor.tracer {
+            tracer.emit(TraceEvent::Command(ancestor.name));
+        }
+        let before = counters(&ancestor.args);
+        let mut held = Some(Vec::new());
+        resolve(
+            &mut ancestor.args,
+            input,
+            None,
+            leftovers.as_deref_mut(),
+            ancestor.posix_strict,
+            ancestor.prefixes,
+            &mut warnings,
+            ancestor.tracer.as_ref(),
+            &mut held,
+        )
+        .expect("The errors are held");
+        mark(&mut ancestor.args, before, ValueSource::CommandLine);
+        if wants_help(&ancestor.args, &help_flag) {
+            return Err(ParseError::new(ErrorKind::DisplayHelp(long_help), &command));
+        }
+        parents.push((ancestor, held.unwrap_or_default()));
+    }
+    if let Some((parent, _)) = parents.last() {
+        pass_globals(&parent.args, &mut command.args);
+    }
+    let mut rest = Vec::new();
+    if let Some(tracer) = &command.tracer {
+        tracer.emit(TraceEvent::Command(command.name));
+    }
+    let sources = [
+        (extra, ValueSource::Env(args_env.unwrap_or_default())),
+        (input, ValueSource::CommandLine),
+    ];
+    // The errors of the tokens are held back, even if they are not collected, since the help and
+    // version flags win over every error.
+    let mut held = Some(Vec::new());
+    for (input, source) in sources {
+        let before = counters(&command.args);
+        resolve(
+            &mut command.args,
+            input,
+            command.rest.is_some().then_some(&mut rest),
+            leftovers.as_deref_mut(),
+            command.posix_strict,
+            command.prefixes,
+            &mut warnings,
+            command.tracer.as_ref(),
+            &mut held,
+        )
+        .expect("The errors are held");
+        mark(&mut command.args, before, source);
+    }
+    if wants_help(&command.args, &help_flag) {
+        return Err(ParseError::new(ErrorKind::DisplayHelp(long_help), &command));
+    }
+    // The version page is created only when it is printed.
+    let version_flag = command.version_flag.clone();
+    if auto_version && command.args.try_count(version_flag).is_some_and(|c| c > 0) {
+        let page = help::version(&command);
+        return Err(ParseError::new(ErrorKind::DisplayVersion(page), &command));
+    }
+    let mut ancestors: Vec<ArgList> = Vec::with_capacity(parents.len());
+    for (parent, held) in parents {
+        settle(&mut errors, held).map_err(|kind| ParseError::new(kind, &parent))?;
+        check_groups(&parent, &mut errors).map_err(|kind| ParseError::new(kind, &parent))?;
+        ancestors.push(parent.args);
+    }
+    settle(&mut errors, held.unwrap_or_default())
+        .map_err(|kind| ParseError::new(kind, &command))?;
+    let plugin = plugin.map(|(plugin, external)| {
+        rest.extend(external);
+        plugin
+    });
+    check_groups(&command, &mut errors).map_err(|kind| ParseError::new(kind, &command))?;
+    let lists: Vec<&ArgList> = ancestors.iter().chain([&command.args]).collect();
+    check_subcommands(&lists, &path, &mut errors)
+        .map_err(|kind| ParseError::new(kind, &command))?;
+    if let Some(Rest { name, min, .. }) = command.rest.filter(|_| plugin.is_none()) {
+        if rest.len() < min {
+            report(&mut errors, ErrorKind::TooFewValues { name, min })
+                .map_err(|kind| ParseError::new(kind, &command))?;
+        }
+    }
+    if let Some(prefix) = env_prefix {
+        let lists: Vec<&ArgList> = ancestors.iter().chain([&command.args]).collect();
+        unknown_env(prefix, args_env, &lists, &mut warnings);
+    }
+    if let Some(errors) = errors.filter(|errors| !errors.is_empty()) {
+        return Err(ParseError::collected(errors, &command));
+    }
+    let parsed = ParsedCommand {
         name: command.name,
-        help: help::create(&command),
-        args: args(command.args, input)?,
+        description: command.description,
+        version: command.version,
+        author: command.author,
+        license: command.license,
+        help,
+        long_help,
+        args: command.args,
         parents: command.parents,
-    })
+        ancestors,
+        warnings,
+        raw,
+        rest,
+        plugin,
+        path,
+        help_flag: help_flag.unwrap_or(arg!(-'h', --help)),
+        rest_name: command.rest.map(|rest| rest.name),
+        subcommands: command
+            .subcommands
+            .iter()
+            .map(|subcmd| (subcmd.name, subcmd.description))
+            .collect(),
+    };
+    // The color option is read ahead of the parser.
+    if color_flag {
+        for args in parsed.ancestors.iter().chain([&parsed.args]) {
+            if let Some(arg) = args.try_get(arg!(--color)) {
+                arg.mark_used();
+            }
+        }
+    }
+    validators.extend(command.validator.clone());
+    for validator in validators {
+        if let Err(reason) = (validator.0)(&parsed) {
+            command.args = parsed.args;
+            command.parents = parsed.parents;
+            return Err(ParseError::new(ErrorKind::Validation(reason), &command));
+        }
+    }
+    Ok(parsed)
 }
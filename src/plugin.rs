@@ -0,0 +1,121 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{self, ExitStatus},
+};
+
+/// An external executable used as a subcommand (cargo-style), e.g. `tinycloud-backup` for
+/// `tinycloud backup`. See [`Command::plugins`](crate::Command::plugins).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tiny_args::*;
+/// let parsed = Command::create("tinycloud", "This is my cool app.")
+///     .plugins(Plugin::discover("tinycloud-"))
+///     .parse()
+///     .unwrap();
+/// if let Some(plugin) = &parsed.plugin {
+///     let status = plugin.run(&parsed.rest).unwrap();
+///     std::process::exit(status.code().unwrap_or(1));
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plugin {
+    /// Name of the subcommand, which is the name of the executable without the prefix.
+    pub name: String,
+
+    /// Path of the executable.
+    pub path: PathBuf,
+}
+
+impl Plugin {
+    /// Finds the executables in `PATH` whose name starts with the given prefix (e.g.
+    /// `tinycloud-`). See [`Plugin::discover_in`].
+    pub fn discover(prefix: &str) -> Vec<Plugin> {
+        Self::discover_in(prefix, &std::env::var_os("PATH").unwrap_or_default())
+    }
+
+    /// Finds the executables whose name starts with the given prefix in a list of directories
+    /// written like `PATH`.
+    ///
+    /// If more executables have the same name, the first one is used like the shell would. The
+    /// plugins are sorted by name and those that could not be used as subcommands (e.g. with a
+    /// name that starts with a dash) are skipped.
+    pub fn discover_in(prefix: &str, dirs: &OsStr) -> Vec<Plugin> {
+        let mut plugins: Vec<Plugin> = Vec::new();
+        for dir in std::env::split_paths(dirs) {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Some(name) = name(&path, prefix) else {
+                    continue;
+                };
+                if !executable(&path) || plugins.iter().any(|plugin| plugin.name == name) {
+                    continue;
+                }
+                plugins.push(Plugin { name, path });
+            }
+        }
+        plugins.sort_by(|a, b| a.name.cmp(&b.name));
+        plugins
+    }
+
+    /// Runs the executable with the given arguments, waiting for it to finish. Its input and
+    /// output are the ones of this program.
+    pub fn run(&self, args: &[String]) -> io::Result<ExitStatus> {
+        process::Command::new(&self.path).args(args).status()
+    }
+}
+
+// The name of the subcommand of an executable, if it has the prefix.
+fn name(path: &Path, prefix: &str) -> Option<String> {
+    let name = if cfg!(windows) {
+        path.file_stem()
+    } else {
+        path.file_name()
+    };
+    let name = name?.to_str()?.strip_prefix(prefix)?;
+    if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(name.into())
+}
+
+#[cfg(unix)]
+fn executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn executable(path: &Path) -> bool {
+    let exe = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"));
+    exe && path.is_file()
+}
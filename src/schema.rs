@@ -0,0 +1,145 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use crate::*;
+
+pub(crate) fn key(argname: &ArgName) -> String {
+    match argname {
+        ArgName::Short(short) => short.to_string(),
+        ArgName::Long(long) | ArgName::Both { long, .. } => long.to_string(),
+    }
+}
+
+pub(crate) fn string(value: &str) -> String {
+    let mut buf = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\t' => buf.push_str("\\t"),
+            c if c.is_control() => buf.push_str(&format!("\\u{:04X}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+    buf
+}
+
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        "nan".into()
+    } else if value.is_infinite() {
+        if value > 0.0 { "inf" } else { "-inf" }.into()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+// Returns the type of the value and its default in TOML syntax, if any.
+fn value(argvalue: &ArgValue) -> (String, Option<String>) {
+    match argvalue {
+        ArgValue::String(value) => ("string".into(), value.as_deref().map(string)),
+        ArgValue::Num(value) => ("integer".into(), value.map(|v| v.to_string())),
+        ArgValue::Float(value) => ("float".into(), value.map(float)),
+        ArgValue::Ratio(value) => ("float, between 0 and 1".into(), value.map(float)),
+        ArgValue::Path(value) => (
+            "path".into(),
+            value.as_ref().map(|v| string(&v.display().to_string())),
+        ),
+        ArgValue::ExistingPath(value) => (
+            "path, must exist".into(),
+            value.as_ref().map(|v| string(&v.display().to_string())),
+        ),
+        ArgValue::Flag => ("boolean".into(), Some("false".into())),
+        ArgValue::Choice(choices, value) => (
+            format!("string, one of: {}", choices.join(", ")),
+            value.as_deref().map(string),
+        ),
+        ArgValue::Range(range, value) => (
+            format!("integer, between {} and {}", range.start(), range.end()),
+            value.map(|v| v.to_string()),
+        ),
+        ArgValue::List(values) => (
+            "array of strings".into(),
+            Some(format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|v| string(v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        ),
+        ArgValue::Count(value) => ("integer".into(), Some(value.to_string())),
+        ArgValue::Bytes(value) => (
+            "string, base64 or hex: followed by hex digits".into(),
+            value.as_deref().map(|v| string(&encoding::encode(v))),
+        ),
+        ArgValue::Other(value) => (
+            value.type_name().into(),
+            value.default_display().map(|v| string(&v)),
+        ),
+        ArgValue::PathList(values) => (
+            "array of paths".into(),
+            Some(format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|v| string(&v.display().to_string()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        ),
+    }
+}
+
+fn table(cmd: &Command, path: &[&str], buf: &mut String) {
+    if !path.is_empty() {
+        buf.push_str(&format!("\n[{}]\n", path.join(".")));
+    }
+    buf.push_str(&format!("# {}\n", cmd.description));
+    // Templates are published with the program, so they only have the public arguments.
+    let public = |visibility| visibility == Visibility::Public;
+    for arg in cmd.args.args.iter().filter(|arg| public(arg.visibility)) {
+        let (kind, default) = value(&arg.argvalue);
+        let default = default.filter(|_| !arg.sensitive);
+        buf.push_str(&format!("\n# {}\n# type: {kind}\n", arg.description));
+        if let Some(note) = arg.deprecated {
+            buf.push_str(&format!("# deprecated: {note}\n"));
+        }
+        match default {
+            Some(default) => buf.push_str(&format!("{} = {default}\n", key(&arg.argname))),
+            None => buf.push_str(&format!("#{} =\n", key(&arg.argname))),
+        }
+    }
+    for subcmd in cmd.subcommands.iter().filter(|s| public(s.visibility)) {
+        let mut path = path.to_vec();
+        path.push(subcmd.name);
+        table(subcmd, &path, buf);
+    }
+}
+
+pub fn toml(cmd: &Command) -> String {
+    let mut buf = String::new();
+    table(cmd, &[], &mut buf);
+    buf
+}
@@ -0,0 +1,463 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::ArgValueKind;
+
+/// A semantic version (e.g. `1.2.3-beta.1+build.5`). Requires the `semver` feature.
+///
+/// Versions are ordered like in the [specification](https://semver.org): pre-release versions
+/// come before their release, while the build metadata is compared only to keep the order
+/// consistent with equality.
+///
+/// It can be used as the value of an argument with [`ArgValue::Other`](crate::ArgValue::Other),
+/// holding an optional default, and read with [`Arg::version`](crate::Arg::version).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let parsed = Command::create("myapp", "This is my cool app.")
+///     .arg(arg!(--target), ArgValue::Other(Box::new(None::<Version>)), "Insert a version.")
+///     .parse_from_str("--target 1.2.3-rc.1")
+///     .unwrap();
+/// let target = parsed.args.try_get(arg!(--target)).unwrap().version().unwrap();
+/// assert_eq!(target, &Version::new(1, 2, 3).pre("rc.1"));
+/// assert!(target < &Version::new(1, 2, 3));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Version {
+    /// The major version, increased by incompatible changes.
+    pub major: u64,
+
+    /// The minor version, increased by compatible additions.
+    pub minor: u64,
+
+    /// The patch version, increased by compatible fixes.
+    pub patch: u64,
+
+    /// The dot separated pre-release identifiers (e.g. `beta.1`), empty for a release.
+    pub pre: String,
+
+    /// The dot separated build metadata (e.g. `build.5`), empty if there is none.
+    pub build: String,
+}
+
+impl Version {
+    /// Creates a new release [`Version`].
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: String::new(),
+            build: String::new(),
+        }
+    }
+
+    /// Specifies the pre-release identifiers (e.g. `beta.1`).
+    #[inline]
+    pub fn pre(mut self, pre: &str) -> Self {
+        self.pre = pre.into();
+        self
+    }
+
+    /// Specifies the build metadata (e.g. `build.5`).
+    #[inline]
+    pub fn build(mut self, build: &str) -> Self {
+        self.build = build.into();
+        self
+    }
+}
+
+// A number without leading zeros.
+fn number(text: &str) -> Option<u64> {
+    if text.is_empty() || (text.len() > 1 && text.starts_with('0')) {
+        return None;
+    }
+    text.bytes()
+        .all(|b| b.is_ascii_digit())
+        .then(|| text.parse().ok())
+        .flatten()
+}
+
+// Dot separated identifiers made of ASCII alphanumerics and dashes.
+fn identifiers(text: &str) -> bool {
+    text.split('.')
+        .all(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-'))
+}
+
+// Compares pre-release identifiers: numeric ones are compared as numbers and come before the
+// others, and a release comes after every pre-release.
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => (),
+    }
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| compare_pre(&self.pre, &other.pre))
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Version {
+    type Err = String;
+
+    /// Parses a version like `1.2.3`, optionally followed by pre-release identifiers and build
+    /// metadata. On failure it returns what the version must be.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || String::from("a version like 1.2.3");
+        let (text, build) = text
+            .split_once('+')
+            .map_or((text, None), |(t, b)| (t, Some(b)));
+        let (text, pre) = text
+            .split_once('-')
+            .map_or((text, None), |(t, p)| (t, Some(p)));
+        if !pre.is_none_or(identifiers) || !build.is_none_or(identifiers) {
+            return Err(invalid());
+        }
+        let (pre, build) = (pre.unwrap_or(""), build.unwrap_or(""));
+        let mut parts = text.split('.').map(number);
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                Ok(Version::new(major, minor, patch).pre(pre).build(build))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre)?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+const OPS: [(&str, Op); 7] = [
+    (">=", Op::GreaterEq),
+    ("<=", Op::LessEq),
+    ("=", Op::Exact),
+    (">", Op::Greater),
+    ("<", Op::Less),
+    ("~", Op::Tilde),
+    ("^", Op::Caret),
+];
+
+// A bound of the versions accepted by a comparator, with whether or not it is included.
+type Bound = Option<(Version, bool)>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: String,
+}
+
+impl Comparator {
+    fn parse(text: &str) -> Option<Self> {
+        let (op, text) = OPS
+            .iter()
+            .find_map(|(symbol, op)| Some((*op, text.strip_prefix(symbol)?)))
+            .unwrap_or((Op::Caret, text));
+        let text = text.trim_start();
+        let (text, pre) = text
+            .split_once('-')
+            .map_or((text, None), |(t, p)| (t, Some(p)));
+        if !pre.is_none_or(identifiers) {
+            return None;
+        }
+        let pre = pre.unwrap_or("");
+        let mut parts = text.split('.').map(number);
+        let (major, minor, patch) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Some(major)), None, None, None) => (major, None, None),
+            (Some(Some(major)), Some(Some(minor)), None, None) => (major, Some(minor), None),
+            (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                (major, Some(minor), Some(patch))
+            }
+            _ => return None,
+        };
+        // Only complete versions can have pre-release identifiers.
+        if patch.is_none() && !pre.is_empty() {
+            return None;
+        }
+        Some(Self {
+            op,
+            major,
+            minor,
+            patch,
+            pre: pre.into(),
+        })
+    }
+
+    // The versions accepted by the comparator, as a lower and an upper bound.
+    fn bounds(&self) -> (Bound, Bound) {
+        let (major, minor, patch) = (self.major, self.minor, self.patch);
+        let full = Version::new(major, minor.unwrap_or(0), patch.unwrap_or(0)).pre(&self.pre);
+        // The first version after the given parts (e.g. `1.3.0` for `1.2`).
+        let next = match (minor, patch) {
+            (Some(minor), Some(patch)) => Version::new(major, minor, patch + 1),
+            (Some(minor), None) => Version::new(major, minor + 1, 0),
+            _ => Version::new(major + 1, 0, 0),
+        };
+        match self.op {
+            Op::Exact if patch.is_some() => (Some((full.clone(), true)), Some((full, true))),
+            Op::Exact => (Some((full, true)), Some((next, false))),
+            Op::Greater if patch.is_some() => (Some((full, false)), None),
+            Op::Greater => (Some((next, true)), None),
+            Op::GreaterEq => (Some((full, true)), None),
+            Op::Less => (None, Some((full, false))),
+            Op::LessEq if patch.is_some() => (None, Some((full, true))),
+            Op::LessEq => (None, Some((next, false))),
+            Op::Tilde => {
+                let upper = match minor {
+                    Some(minor) => Version::new(major, minor + 1, 0),
+                    None => Version::new(major + 1, 0, 0),
+                };
+                (Some((full, true)), Some((upper, false)))
+            }
+            Op::Caret => {
+                let upper = match (minor, patch) {
+                    _ if major > 0 || minor.is_none() => Version::new(major + 1, 0, 0),
+                    (Some(minor), _) if minor > 0 || patch.is_none() => {
+                        Version::new(0, minor + 1, 0)
+                    }
+                    _ => next,
+                };
+                (Some((full, true)), Some((upper, false)))
+            }
+        }
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        let (lower, upper) = self.bounds();
+        let above = lower.is_none_or(|(lower, included)| match version.cmp(&lower) {
+            Ordering::Greater => true,
+            Ordering::Equal => included,
+            Ordering::Less => false,
+        });
+        let below = upper.is_none_or(|(upper, included)| match version.cmp(&upper) {
+            Ordering::Less => true,
+            Ordering::Equal => included,
+            Ordering::Greater => false,
+        });
+        above && below
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = OPS
+            .iter()
+            .find(|(_, op)| *op == self.op)
+            .map_or("", |(s, _)| s);
+        write!(f, "{symbol}{}", self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{minor}")?;
+        }
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// A requirement on a [`Version`], made of comma separated comparators that must all match
+/// (e.g. `>=1.2, <2`), or `*` for any version. Requires the `semver` feature.
+///
+/// The comparators work like in Cargo:
+///
+/// - `^1.2.3` (or just `1.2.3`) accepts the compatible versions, `>=1.2.3, <2.0.0`. If the
+///   major version is `0` the minor version is the incompatible one (`^0.2.3` is
+///   `>=0.2.3, <0.3.0`).
+/// - `~1.2.3` accepts only patch updates, `>=1.2.3, <1.3.0`.
+/// - `=1.2`, `>1.2`, `>=1.2`, `<1.2` and `<=1.2` compare the versions, where missing parts
+///   accept any value (e.g. `=1.2` is `>=1.2.0, <1.3.0`).
+///
+/// Pre-release versions are accepted only if a comparator has a pre-release on the same
+/// version (e.g. `>=1.2.3-beta` accepts `1.2.3-rc` but not `1.2.4-rc`).
+///
+/// It can be used as the value of an argument with [`ArgValue::Other`](crate::ArgValue::Other),
+/// holding an optional default, and read with [`Arg::version_req`](crate::Arg::version_req).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let req: VersionReq = ">=1.2, <2".parse().unwrap();
+/// assert!(req.matches(&Version::new(1, 4, 0)));
+/// assert!(!req.matches(&Version::new(2, 0, 0)));
+/// assert!(!req.matches(&Version::new(1, 5, 0).pre("beta")));
+/// assert_eq!(req.to_string(), ">=1.2, <2");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// The requirement that accepts every version, except pre-releases.
+    pub const ANY: VersionReq = VersionReq {
+        comparators: Vec::new(),
+    };
+
+    /// Checks whether or not the version meets the requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        if !version.pre.is_empty()
+            && !self.comparators.iter().any(|comparator| {
+                !comparator.pre.is_empty()
+                    && (comparator.major, comparator.minor, comparator.patch)
+                        == (version.major, Some(version.minor), Some(version.patch))
+            })
+        {
+            return false;
+        }
+        self.comparators
+            .iter()
+            .all(|comparator| comparator.matches(version))
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = String;
+
+    /// Parses a requirement. On failure it returns what the requirement must be.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text.trim() == "*" {
+            return Ok(Self::ANY);
+        }
+        let comparators: Option<Vec<Comparator>> = text
+            .split(',')
+            .map(|c| Comparator::parse(c.trim()))
+            .collect();
+        match comparators {
+            Some(comparators) => Ok(Self { comparators }),
+            None => Err("a version requirement like ^1.2 or >=1.2, <2".into()),
+        }
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.comparators.is_empty() {
+            return write!(f, "*");
+        }
+        for (i, comparator) in self.comparators.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{comparator}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ArgValueKind for Option<Version> {
+    fn parse(&mut self, input: &str) -> Result<(), String> {
+        *self = Some(input.parse()?);
+        Ok(())
+    }
+
+    fn type_name(&self) -> &str {
+        "version"
+    }
+
+    fn default_display(&self) -> Option<String> {
+        self.as_ref().map(Version::to_string)
+    }
+
+    fn clone_box(&self) -> Box<dyn ArgValueKind> {
+        Box::new(self.clone())
+    }
+}
+
+impl ArgValueKind for Option<VersionReq> {
+    fn parse(&mut self, input: &str) -> Result<(), String> {
+        *self = Some(input.parse()?);
+        Ok(())
+    }
+
+    fn type_name(&self) -> &str {
+        "version requirement"
+    }
+
+    fn default_display(&self) -> Option<String> {
+        self.as_ref().map(VersionReq::to_string)
+    }
+
+    fn clone_box(&self) -> Box<dyn ArgValueKind> {
+        Box::new(self.clone())
+    }
+}
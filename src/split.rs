@@ -0,0 +1,133 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{error, fmt};
+
+/// An error found while splitting a line into arguments. See [`split()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitError {
+    /// A quote was opened but never closed. It carries the quote character.
+    UnterminatedQuote(char),
+
+    /// The line ends with a backslash, which escapes nothing.
+    TrailingBackslash,
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnterminatedQuote(quote) => write!(f, "unterminated quote ({quote})"),
+            Self::TrailingBackslash => f.write_str("trailing backslash"),
+        }
+    }
+}
+
+impl error::Error for SplitError {}
+
+/// Splits a line into arguments like a POSIX shell would.
+///
+/// Arguments are separated by whitespace. Single quotes preserve everything inside them, double
+/// quotes preserve everything except backslashes escaping `"`, `\`, `$` and `` ` ``, and a
+/// backslash outside of quotes escapes the following character. Nothing is expanded.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// assert_eq!(
+///     split(r#"user add --name "Some One" --note 'it\'s'"#),
+///     Err(SplitError::UnterminatedQuote('\''))
+/// );
+/// assert_eq!(
+///     split(r#"user add --name "Some One" --note it\'s"#).unwrap(),
+///     ["user", "add", "--name", "Some One", "--note", "it's"]
+/// );
+/// ```
+pub fn split(line: &str) -> Result<Vec<String>, SplitError> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(word) = word.take() {
+                    words.push(word);
+                }
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or(SplitError::UnterminatedQuote('\''))? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or(SplitError::UnterminatedQuote('"'))? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or(SplitError::UnterminatedQuote('"'))? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            '\n' => (),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => match chars.next().ok_or(SplitError::TrailingBackslash)? {
+                '\n' => (),
+                c => word.get_or_insert_with(String::new).push(c),
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(word) = word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Quotes a word so that [`split()`] (or a POSIX shell) reads it back unchanged.
+///
+/// Words made only of characters that need no quoting are returned as they are.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// assert_eq!(quote("--name"), "--name");
+/// assert_eq!(quote("Some One"), "'Some One'");
+/// assert_eq!(quote("it's"), r"'it'\''s'");
+/// assert_eq!(split(&quote("it's")).unwrap(), ["it's"]);
+/// ```
+pub fn quote(word: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !word.is_empty() && word.chars().all(plain) {
+        return word.into();
+    }
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
@@ -0,0 +1,277 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+#[cfg(doc)]
+use crate::{Command, ErrorKind};
+
+/// The text used in help pages, version pages and errors.
+///
+/// Replace it with [`Command::strings`] to localize the output of the command line.
+/// Some fields are templates: their placeholders (e.g. `{arg}`) are replaced with the actual
+/// values, and are listed in the field's documentation.
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = Command::create("myapp", "Questa è la mia bella app.").strings(Strings {
+///     usage: "UTILIZZO:",
+///     args: "ARGOMENTI:",
+///     subcommands: "SOTTOCOMANDI:",
+///     unknown_arg: "'{arg}' non è un argomento valido.",
+///     ..Strings::ENGLISH
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Strings {
+    /// Heading of the usage section.
+    pub usage: &'static str,
+
+    /// Heading of the arguments section.
+    pub args: &'static str,
+
+    /// Heading of the subcommands section.
+    pub subcommands: &'static str,
+
+    /// Text preceding the license.
+    pub licensed_under: &'static str,
+
+    /// Heading of the examples section.
+    pub examples: &'static str,
+
+    /// Heading of the arguments and subcommands that have no provider (see
+    /// [`Command::provider`]).
+    pub core: &'static str,
+
+    /// Label of the homepage.
+    pub homepage: &'static str,
+
+    /// Label of the source code repository.
+    pub repository: &'static str,
+
+    /// Description of the built-in `help` subcommand.
+    pub help_subcommand: &'static str,
+
+    /// Description of the plugins (see [`Command::plugins`]).
+    pub plugin: &'static str,
+
+    /// Description of the automatic version flag.
+    pub version_flag: &'static str,
+
+    /// Description of the automatic help flag.
+    pub help_flag: &'static str,
+
+    /// Description of the verbose flag (see [`Command::verbosity_flags`]).
+    pub verbose_flag: &'static str,
+
+    /// Description of the quiet flag (see [`Command::verbosity_flags`]).
+    pub quiet_flag: &'static str,
+
+    /// Description of the color option (see [`Command::color_flag`]).
+    pub color_flag: &'static str,
+
+    /// Label of the arguments' default values.
+    pub default: &'static str,
+
+    /// Label of the arguments' possible values.
+    pub possible: &'static str,
+
+    /// Label of the arguments' ranges.
+    pub range: &'static str,
+
+    /// Label of the arguments' examples.
+    pub example: &'static str,
+
+    /// Tag of deprecated arguments.
+    pub deprecated: &'static str,
+
+    /// Label of the commit hash in the version page.
+    pub commit: &'static str,
+
+    /// Label of the build date in the version page.
+    pub build_date: &'static str,
+
+    /// Label of the target in the version page.
+    pub target: &'static str,
+
+    /// Prefix of rendered errors.
+    pub error: &'static str,
+
+    /// Hint at the end of rendered errors. Placeholders: `{help}`.
+    pub try_help: &'static str,
+
+    /// Question asked before listing the close subcommands (see
+    /// [`Autocorrect::Prompt`](crate::Autocorrect::Prompt)). Placeholders: `{subcommand}`.
+    pub autocorrect_prompt: &'static str,
+
+    /// Request of a choice among the close subcommands (see
+    /// [`Autocorrect::Prompt`](crate::Autocorrect::Prompt)). Placeholders: `{count}`.
+    pub autocorrect_choice: &'static str,
+
+    /// Message of [`ErrorKind::UnknownArg`]. Placeholders: `{arg}`.
+    pub unknown_arg: &'static str,
+
+    /// Message of [`ErrorKind::InvalidArg`]. Placeholders: `{arg}`.
+    pub invalid_arg: &'static str,
+
+    /// Message of [`ErrorKind::NonUtf8Arg`]. Placeholders: `{arg}`.
+    pub non_utf8_arg: &'static str,
+
+    /// Message of [`ErrorKind::TooManyDashes`]. Placeholders: `{arg}`.
+    pub too_many_dashes: &'static str,
+
+    /// Message of [`ErrorKind::LoneDash`]. Placeholders: `{arg}`.
+    pub lone_dash: &'static str,
+
+    /// Message of [`ErrorKind::TrailingDoubleDash`]. Placeholders: `{arg}`.
+    pub trailing_double_dash: &'static str,
+
+    /// Message of [`ErrorKind::UnexpectedToken`]. Placeholders: `{token}`.
+    pub unexpected_token: &'static str,
+
+    /// Message of [`ErrorKind::InvalidValue`]. Placeholders: `{arg}`, `{reason}`.
+    pub invalid_value: &'static str,
+
+    /// Message of [`ErrorKind::InvalidValue`] in rendered errors.
+    /// Placeholders: `{arg}`, `{value}`, `{reason}`.
+    pub invalid_value_detailed: &'static str,
+
+    /// Message of [`ErrorKind::MissingValue`]. Placeholders: `{arg}`.
+    pub missing_value: &'static str,
+
+    /// Message of [`ErrorKind::MisplacedArg`] for [`Placement::Before`](crate::Placement::Before).
+    /// Placeholders: `{arg}`.
+    pub misplaced_before: &'static str,
+
+    /// Message of [`ErrorKind::MisplacedArg`] for [`Placement::After`](crate::Placement::After).
+    /// Placeholders: `{arg}`.
+    pub misplaced_after: &'static str,
+
+    /// Message of [`ErrorKind::MissingOneOf`]. Placeholders: `{args}`.
+    pub missing_one_of: &'static str,
+
+    /// Message of [`ErrorKind::RequiresSubcommand`]. Placeholders: `{arg}`, `{subcommands}`.
+    pub requires_subcommand: &'static str,
+
+    /// Message of [`ErrorKind::Duplicate`]. Placeholders: `{arg}`.
+    pub duplicate: &'static str,
+
+    /// Message of [`ErrorKind::UnknownSubcommand`]. Placeholders: `{subcommand}`.
+    pub unknown_subcommand: &'static str,
+
+    /// Message of [`ErrorKind::MissingSubcommand`]. Placeholders: `{command}`, `{subcommands}`.
+    pub missing_subcommand: &'static str,
+
+    /// Message of [`ErrorKind::InvalidEnvArgs`]. Placeholders: `{var}`.
+    pub invalid_env_args: &'static str,
+
+    /// Message of [`ErrorKind::InvalidLine`]. Placeholders: `{reason}`.
+    pub invalid_line: &'static str,
+
+    /// Message of [`ErrorKind::TooFewValues`]. Placeholders: `{name}`, `{min}`.
+    pub too_few_values: &'static str,
+
+    /// Message of [`ErrorKind::Validation`]. Placeholders: `{reason}`.
+    pub validation: &'static str,
+}
+
+impl Strings {
+    /// The default text.
+    pub const ENGLISH: Strings = Strings {
+        usage: "USAGE:",
+        args: "ARGS:",
+        subcommands: "SUBCOMMANDS:",
+        licensed_under: "Licensed under",
+        examples: "EXAMPLES:",
+        core: "Core",
+        homepage: "Homepage",
+        repository: "Repository",
+        help_subcommand: "Shows the help of the given subcommand.",
+        plugin: "Runs an external command.",
+        version_flag: "Shows the version.",
+        help_flag: "Shows this help.",
+        verbose_flag: "Increases the verbosity.",
+        quiet_flag: "Decreases the verbosity.",
+        color_flag: "Specifies when to color the output.",
+        default: "default",
+        possible: "possible",
+        range: "range",
+        example: "example",
+        deprecated: "deprecated",
+        commit: "commit",
+        build_date: "build date",
+        target: "target",
+        error: "error:",
+        try_help: "For more information, try '{help}'.",
+        autocorrect_prompt: "'{subcommand}' is not a valid subcommand. Did you mean one of these?",
+        autocorrect_choice: "Run which one? [1-{count}, anything else aborts]",
+        unknown_arg: "'{arg}' is not a valid argument.",
+        invalid_arg: "'{arg}' is not a valid argument.",
+        non_utf8_arg: "'{arg}' is not valid UTF-8.",
+        too_many_dashes: "'{arg}' starts with too many dashes: use '-' or '--'.",
+        lone_dash: "'{arg}' can only be given as the value of an argument.",
+        trailing_double_dash: "'{arg}' is not followed by anything.",
+        unexpected_token: "'{token}' is not an argument nor a value.",
+        invalid_value: "'{arg}' value's must be {reason}",
+        invalid_value_detailed: "invalid value '{value}' for '{arg}': must be {reason}",
+        missing_value: "missing value for '{arg}'.",
+        misplaced_before: "'{arg}' must be given before the subcommand.",
+        misplaced_after: "'{arg}' must be given after the subcommand.",
+        missing_one_of: "one of {args} is required.",
+        requires_subcommand: "'{arg}' can only be used with the subcommands: {subcommands}.",
+        duplicate: "'{arg}' cannot be used more than once.",
+        unknown_subcommand: "'{subcommand}' is not a valid subcommand.",
+        missing_subcommand: "'{command}' requires a subcommand: {subcommands}.",
+        invalid_env_args: "'{var}' does not contain properly quoted arguments.",
+        invalid_line: "the command line is not properly quoted: {reason}.",
+        too_few_values: "at least {min} '{name}' values are required.",
+        validation: "{reason}.",
+    };
+
+    // Replaces the placeholders of a template. Unknown placeholders are left as they are.
+    pub(crate) fn fill(template: &str, values: &[(&str, &str)]) -> String {
+        let mut buf = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            buf.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let value = rest.find('}').and_then(|end| {
+                let (_, value) = values.iter().find(|(key, _)| *key == &rest[1..end])?;
+                Some((value, end))
+            });
+            if let Some((value, end)) = value {
+                buf.push_str(value);
+                rest = &rest[end + 1..];
+            } else {
+                buf.push('{');
+                rest = &rest[1..];
+            }
+        }
+        buf.push_str(rest);
+        buf
+    }
+}
+
+impl Default for Strings {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
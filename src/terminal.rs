@@ -0,0 +1,155 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+    sync::OnceLock,
+};
+
+/// What the terminal the output is written to supports, which decides how help pages and
+/// errors are rendered.
+///
+/// It is detected once per process (see [`TerminalInfo::detect`]) and can be overridden for a
+/// command with [`Command::terminal`](crate::Command::terminal).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let cmd = Command::create("myapp", "This is my cool app.").terminal(TerminalInfo {
+///     width: Some(80),
+///     ..TerminalInfo::PLAIN
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TerminalInfo {
+    /// The columns the help pages are wrapped at, if any.
+    pub width: Option<usize>,
+
+    /// Whether or not the output is colored with ANSI escape sequences. It has no effect without
+    /// the `color` feature.
+    pub color: bool,
+
+    /// Whether or not the terminal can show characters that are not ASCII. The built-in
+    /// renderers do not depend on it, but custom ones (e.g. an error formatter) can.
+    pub unicode: bool,
+}
+
+impl TerminalInfo {
+    /// A terminal that is not wrapped, colored nor able to show Unicode, like a file.
+    pub const PLAIN: Self = Self {
+        width: None,
+        color: false,
+        unicode: false,
+    };
+
+    /// Detects what the terminal supports. The result is computed on the first call and reused
+    /// for the rest of the process.
+    ///
+    /// - The output is colored if both the standard output and the standard error are
+    ///   terminals, `NO_COLOR` is not set and `TERM` is not `dumb`. A `CLICOLOR_FORCE` other
+    ///   than `0` colors it anyway.
+    /// - The width is read from `COLUMNS` if the standard output is a terminal.
+    /// - Unicode is supported on Windows and if the locale (`LC_ALL`, `LC_CTYPE` or `LANG`, the
+    ///   first one that is set) uses UTF-8.
+    pub fn detect() -> Self {
+        static DETECTED: OnceLock<TerminalInfo> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            let var = |name| env::var(name).ok().filter(|value| !value.is_empty());
+            let terminal = io::stdout().is_terminal() && io::stderr().is_terminal();
+            let forced = var("CLICOLOR_FORCE").is_some_and(|value| value != "0");
+            let color = forced
+                || (terminal
+                    && var("NO_COLOR").is_none()
+                    && var("TERM").as_deref() != Some("dumb"));
+            let width = io::stdout()
+                .is_terminal()
+                .then(|| var("COLUMNS")?.parse().ok())
+                .flatten()
+                .filter(|&width| width > 0);
+            let unicode = cfg!(windows)
+                || ["LC_ALL", "LC_CTYPE", "LANG"]
+                    .into_iter()
+                    .find_map(var)
+                    .is_some_and(|locale| {
+                        let locale = locale.to_ascii_lowercase();
+                        locale.contains("utf-8") || locale.contains("utf8")
+                    });
+            Self {
+                width,
+                color: cfg!(feature = "color") && color,
+                unicode,
+            }
+        })
+    }
+}
+
+/// When the output is colored, as chosen with the `--color` option (see
+/// [`Command::color_flag`](crate::Command::color_flag)).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let parsed = Command::create("myapp", "This is my cool app.")
+///     .color_flag()
+///     .parse_from_str("--color never")
+///     .unwrap();
+/// assert_eq!(parsed.color_choice(), ColorChoice::Never);
+/// assert!(!parsed.color_choice().enabled());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Colored if the terminal supports it (see [`TerminalInfo::detect`]). This is the default.
+    #[default]
+    Auto,
+
+    /// Always colored.
+    Always,
+
+    /// Never colored.
+    Never,
+}
+
+impl ColorChoice {
+    pub(crate) const NAMES: &'static [&'static str] = &["auto", "always", "never"];
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Returns whether or not the output should be colored, detecting it for
+    /// [`ColorChoice::Auto`]. Like the help pages and the errors, it is never colored without the
+    /// `color` feature.
+    pub fn enabled(self) -> bool {
+        match self {
+            Self::Auto => TerminalInfo::detect().color,
+            Self::Always => cfg!(feature = "color"),
+            Self::Never => false,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use crate::*;
+
+// The names used by the generated arguments: the argument at index `i` can use the short name
+// `SHORTS[i]` and the long name `LONGS[i]`.
+const SHORTS: &[u8; 20] = b"abcdefgijklmnopqrstu";
+const LONGS: [&str; 20] = [
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "india", "juliett", "kilo",
+    "lima", "mike", "november", "oscar", "papa", "quebec", "romeo", "sierra", "tango", "uniform",
+];
+const SUBCOMMANDS: [&str; 6] = ["add", "remove", "list", "show", "sync", "watch"];
+const CHOICES: &[&str] = &["json", "yaml", "toml"];
+// Tokens that are not valid where they are inserted by `Generator::mutate`.
+const GARBAGE: [&str; 12] = [
+    "-",
+    "--",
+    "---",
+    "-=",
+    "--=",
+    "=",
+    "-\u{200b}",
+    "--unknown",
+    "-Z9",
+    "ünïcode",
+    "",
+    " ",
+];
+// The generated words never contain an `h`, so they can never be the built-in `help`
+// subcommand.
+const LETTERS: &[u8; 25] = b"abcdefgijklmnopqrstuvwxyz";
+
+/// A command line generated for a command. See [`Generator::input`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    /// The command line, starting with the name of the program.
+    pub argv: Vec<String>,
+
+    /// How many times each argument was used in each traversed command (the root first). Every
+    /// argument of the commands is listed, even if it was not used.
+    pub uses: Vec<Vec<(ArgName, usize)>>,
+
+    /// The positional values given to the last command.
+    pub rest: Vec<String>,
+}
+
+/// A seeded generator of random commands and command lines, meant for property tests. Requires
+/// the `testing` feature.
+///
+/// The same seed always gives the same commands and inputs, so failures can be reproduced.
+/// Generated commands have no help flag nor version flag, so parsing them never returns a
+/// requested page (see [`ParseError::is_page`]).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// for seed in 0..100 {
+///     let mut gen = Generator::new(seed);
+///     let cmd = gen.command();
+///     let input = gen.input(&cmd);
+///     let parsed = cmd.parse_from(input.argv.clone()).unwrap();
+///     for (argname, uses) in input.uses.last().unwrap() {
+///         assert_eq!(parsed.args.try_count(argname.clone()), Some(*uses));
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    /// Creates a new [`Generator`] from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    // SplitMix64.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Returns a number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    fn word(&mut self) -> String {
+        (0..1 + self.below(8))
+            .map(|_| LETTERS[self.below(LETTERS.len())] as char)
+            .collect()
+    }
+
+    // Picks `n` distinct indexes in `0..len`.
+    fn pick(&mut self, n: usize, len: usize) -> Vec<usize> {
+        let mut all: Vec<usize> = (0..len).collect();
+        (0..n.min(len))
+            .map(|_| all.remove(self.below(all.len())))
+            .collect()
+    }
+
+    /// Generates a random command, with up to two levels of subcommands. Every kind of name and
+    /// most kinds of values are used.
+    ///
+    /// Since the generator is seeded, a command can be generated again to be parsed more than
+    /// once.
+    pub fn command(&mut self) -> Command {
+        self.tree("generated", 0)
+    }
+
+    fn tree(&mut self, name: &'static str, depth: usize) -> Command {
+        let mut cmd = Command::create(name, "A generated command.").color(false);
+        let count = self.below(7);
+        for i in self.pick(count, LONGS.len()) {
+            let (short, long) = (SHORTS[i] as char, LONGS[i]);
+            let argname = match self.below(3) {
+                0 => ArgName::Short(short),
+                1 => ArgName::Long(long.into()),
+                _ => ArgName::Both {
+                    short,
+                    long: long.into(),
+                },
+            };
+            let argvalue = match self.below(9) {
+                0 => ArgValue::Flag,
+                1 => ArgValue::Count(0),
+                2 => ArgValue::Num(None),
+                3 => ArgValue::Float(None),
+                4 => ArgValue::Choice(CHOICES, None),
+                5 => ArgValue::Range(-100..=100, None),
+                6 => ArgValue::List(Vec::new()),
+                7 => ArgValue::Path(None),
+                _ => ArgValue::String(None),
+            };
+            cmd = cmd.add_arg(Arg::new(argname, argvalue, "A generated argument."));
+        }
+        if depth < 2 && self.chance(50) {
+            let count = 1 + self.below(3);
+            for i in self.pick(count, SUBCOMMANDS.len()) {
+                let subcmd = self.tree(SUBCOMMANDS[i], depth + 1);
+                cmd = cmd.subcommand(subcmd);
+            }
+            cmd = cmd.subcommand_required(self.chance(50));
+        } else if self.chance(50) {
+            cmd = cmd.rest("VALUES", "The generated values.");
+        }
+        cmd
+    }
+
+    fn value(&mut self, argvalue: &ArgValue) -> String {
+        match argvalue {
+            ArgValue::Num(_) => (self.next() as i64 / 2).to_string(),
+            ArgValue::Float(_) => (self.below(4000) as f64 / 4.0 - 500.0).to_string(),
+            ArgValue::Choice(choices, _) => choices[self.below(choices.len())].into(),
+            ArgValue::Range(range, _) => {
+                let span = (range.end() - range.start()) as usize + 1;
+                (range.start() + self.below(span) as i64).to_string()
+            }
+            _ => self.word(),
+        }
+    }
+
+    /// Generates a valid command line for the given command: it traverses a random path of
+    /// subcommands, giving each command random arguments in every supported form (e.g.
+    /// `--long value`, `--long=value`, `-svalue`, `-s=value` or clustered flags), followed by
+    /// positional values if the last command takes them.
+    pub fn input(&mut self, cmd: &Command) -> Input {
+        let mut input = Input {
+            argv: vec![cmd.name.into()],
+            uses: Vec::new(),
+            rest: Vec::new(),
+        };
+        let mut cmd = cmd;
+        loop {
+            let args = &cmd.args.args;
+            let mut uses = vec![0; args.len()];
+            // The index of the last token made only of short flags, which can be extended.
+            let mut cluster: Option<usize> = None;
+            for _ in 0..self.below(6).min(args.len() * 2) {
+                let index = self.below(args.len());
+                let arg = &args[index];
+                uses[index] += 1;
+                let flag = matches!(arg.argvalue, ArgValue::Flag | ArgValue::Count(_));
+                let short = match &arg.argname {
+                    ArgName::Short(short) => Some(*short),
+                    ArgName::Both { short, .. } if self.chance(50) => Some(*short),
+                    _ => None,
+                };
+                let long = match &arg.argname {
+                    ArgName::Long(long) | ArgName::Both { long, .. } => &**long,
+                    ArgName::Short(_) => "",
+                };
+                match (short, flag) {
+                    (Some(short), true) => match cluster {
+                        Some(i) if self.chance(50) => input.argv[i].push(short),
+                        _ => {
+                            cluster = Some(input.argv.len());
+                            input.argv.push(format!("-{short}"));
+                        }
+                    },
+                    (None, true) => input.argv.push(format!("--{long}")),
+                    (Some(short), false) => {
+                        let value = self.value(&arg.argvalue);
+                        match self.below(3) {
+                            0 => input.argv.push(format!("-{short}{value}")),
+                            1 => input.argv.push(format!("-{short}={value}")),
+                            _ => input.argv.extend([format!("-{short}"), value]),
+                        }
+                    }
+                    (None, false) => {
+                        let value = self.value(&arg.argvalue);
+                        if self.chance(50) {
+                            input.argv.push(format!("--{long}={value}"));
+                        } else {
+                            input.argv.extend([format!("--{long}"), value]);
+                        }
+                    }
+                }
+                if !flag {
+                    cluster = None;
+                }
+            }
+            input.uses.push(
+                args.iter()
+                    .zip(uses)
+                    .map(|(arg, uses)| (arg.argname.clone(), uses))
+                    .collect(),
+            );
+            let subcommands = &cmd.subcommands;
+            if !subcommands.is_empty() && (cmd.subcommand_required || self.chance(75)) {
+                cmd = &subcommands[self.below(subcommands.len())];
+                input.argv.push(cmd.name.into());
+                continue;
+            }
+            if cmd.rest.is_some() {
+                for _ in 0..self.below(4) {
+                    let value = self.word();
+                    input.argv.push(value.clone());
+                    input.rest.push(value);
+                }
+            }
+            return input;
+        }
+    }
+
+    /// Changes the given command line so that it is likely to be invalid: a token is removed,
+    /// duplicated, swapped with another one, cut, or an invalid token is inserted. The name of
+    /// the program (the first token) is never changed.
+    pub fn mutate(&mut self, argv: &mut Vec<String>) {
+        let len = argv.len();
+        let index = 1 + self.below(len);
+        if index == len {
+            argv.push(GARBAGE[self.below(GARBAGE.len())].into());
+            return;
+        }
+        match self.below(5) {
+            0 => {
+                argv.remove(index);
+            }
+            1 => argv.insert(index, argv[index].clone()),
+            2 => {
+                let other = 1 + self.below(len - 1);
+                argv.swap(index, other);
+            }
+            3 => {
+                let token = &mut argv[index];
+                let cut = token.char_indices().nth(self.below(token.len() + 1));
+                if let Some((cut, _)) = cut {
+                    token.truncate(cut);
+                }
+            }
+            _ => argv.insert(index, GARBAGE[self.below(GARBAGE.len())].into()),
+        }
+    }
+}
@@ -19,9 +19,12 @@
 //
 // Email: hex0x0000@protonmail.com
 
-use std::f64::consts::PI;
+// The tests also cover the methods deprecated by the `strict` feature.
+#![cfg_attr(feature = "strict", allow(deprecated))]
 
-use crate::*;
+use std::{env, f64::consts::PI};
+
+use crate::{help::HelpOptions, *};
 
 #[test]
 fn arg_name_equality() {
@@ -60,6 +63,10 @@ fn test_value_macro() {
     assert_eq!(value!(num), ArgValue::Num(None));
     assert_eq!(value!(float), ArgValue::Float(None));
     assert_eq!(value!(path), ArgValue::Path(None));
+    assert_eq!(value!(count), ArgValue::Count(0));
+    assert_eq!(value!(list), ArgValue::List(vec![]));
+    assert_eq!(value!(paths), ArgValue::PathList(vec![]));
+    assert_eq!(value!(bytes), ArgValue::Bytes(None));
     assert_eq!(
         value!(string, "a b c"),
         ArgValue::String(Some("a b c".into()))
@@ -67,6 +74,7 @@ fn test_value_macro() {
     assert_eq!(value!(num, 2), ArgValue::Num(Some(2)));
     assert_eq!(value!(float, 2.3), ArgValue::Float(Some(2.3)));
     assert_eq!(value!(path, "/path"), ArgValue::Path(Some("/path".into())));
+    assert_eq!(value!(count, 2), ArgValue::Count(2));
 }
 
 #[test]
@@ -113,6 +121,17 @@ fn mkargs(vec: &[&str]) -> Vec<String> {
     vec.iter().map(|&s| s.into()).collect()
 }
 
+// Parses the input, which must request the help page or the version page, and returns the kind
+// of the page.
+fn page(cmd: Command, input: &[&str]) -> ErrorKind {
+    let err = cmd
+        .parse_from(mkargs(input))
+        .err()
+        .expect("No page was requested");
+    assert!(err.is_page(), "{err}");
+    err.kind().clone()
+}
+
 #[test]
 fn test_subcmd() {
     let input = mkargs(&["test-program", "test", "--idk2", "a b c"]);
@@ -138,6 +157,7 @@ fn test_subcmd() {
         "Just insert something again"
     );
     assert!(parsed.help.starts_with("testception test"));
+    assert_eq!(parsed.raw, vec!["test", "--idk2", "a b c"]);
 }
 
 #[test]
@@ -247,7 +267,4912 @@ fn test_tabbing() {
         .subcommand(Command::create("testsubanan", "testsub"))
         .subcommand(Command::create("testsubaaaaaaaa", "testsub"))
         .subcommand(Command::create("testsubaaaaaaaaaaaaa", "testsub"))
+        .color(false)
         .parse_from(vec!["test".into()])
         .unwrap();
     println!("{}", cmd.help);
+    // The descriptions are aligned after the widest name, except for the names that are too
+    // wide, which are on their own line.
+    let indent = format!("\t{}", " ".repeat(23));
+    assert!(cmd
+        .help
+        .contains("\t--aaaaaaa              testestetstestestest\n"));
+    assert!(cmd
+        .help
+        .contains("\t--aaaaaaaaaaaaaaaaaaa  testestetstestestest\n"));
+    assert!(cmd.help.contains(&format!(
+        "\t--aaaaaaaaaaaaaaaaaaaaaaaa\n{indent}testestetstestestest\n"
+    )));
+    assert!(cmd.help.contains("\ttestsub                testsub\n"));
+    assert!(cmd.help.contains("\ttestsubaaaaaaaaaaaaa   testsub\n"));
+}
+
+#[test]
+fn test_help_defaults() {
+    let parsed = test_command()
+        .color(false)
+        .parse_from(mkargs(&["test-program", "--path", "/some/path"]))
+        .unwrap();
+    println!("{}", parsed.help);
+    assert!(parsed
+        .help
+        .contains("Insert a path [default: /default/path]"));
+    assert!(parsed.help.contains("Insert a number [default: 3]"));
+    assert!(parsed
+        .help
+        .contains("Just insert something again [default: default value]"));
+    assert!(parsed.help.contains("Insert a float\n"));
+}
+
+#[test]
+fn test_auto_version() {
+    let err = Command::create("test", "Tests the version page")
+        .version("0.1.0")
+        .long_version("A longer version text")
+        .build_info(BuildInfo {
+            git_hash: Some("1a2b3c4"),
+            target: Some("x86_64-unknown-linux-gnu"),
+            ..Default::default()
+        })
+        .color(false)
+        .auto_version(true)
+        .parse_from(mkargs(&["test-program", "--version"]))
+        .err()
+        .unwrap();
+    assert!(err.is_page());
+    assert_eq!(err.code(), "E_DISPLAY_VERSION");
+    assert_eq!(
+        err.to_string(),
+        "test 0.1.0\nA longer version text\ncommit: 1a2b3c4\ntarget: x86_64-unknown-linux-gnu"
+    );
+    let parsed = Command::create("test", "Tests the version page")
+        .version("0.1.0")
+        .auto_version(true)
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert_eq!(parsed.args.count(arg!(-'V')), 0);
+    // The command's own arguments take precedence over the version flag.
+    let parsed = test_command()
+        .auto_version(true)
+        .parse_from(mkargs(&["test-program", "-V"]))
+        .unwrap();
+    assert_eq!(parsed.args.count(arg!(-'V')), 1);
+    assert!(parsed.args.try_get(arg!(--version)).is_none());
+}
+
+#[test]
+fn test_help_subcommand() {
+    let input = &["test-program", "help", "user", "add"];
+    let cmd = Command::create("root", "Tests the help subcommand").subcommand(
+        Command::create("user", "Manages users")
+            .subcommand(Command::create("add", "Adds a user").color(false)),
+    );
+    let ErrorKind::DisplayHelp(help) = page(cmd, input) else {
+        panic!("The help page was not requested");
+    };
+    assert!(help.starts_with("root user add"));
+    let parsed = Command::create("root", "Tests the help subcommand")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user")),
+        )
+        .parse_from(mkargs(&["test-program", "user", "add"]))
+        .unwrap();
+    assert_eq!(parsed.path(), ["user", "add"]);
+    assert!(parsed.is(["user", "add"]));
+    let parsed = Command::create("root", "Tests the help subcommand")
+        .subcommand(Command::create("user", "Manages users"))
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(parsed.is([]));
+    let cmd = Command::create("root", "Tests the help subcommand")
+        .color(false)
+        .subcommand(Command::create("user", "Manages users"));
+    let ErrorKind::DisplayHelp(help) = page(cmd, &["test-program", "help"]) else {
+        panic!("The help page was not requested");
+    };
+    assert!(help.contains("\thelp  Shows the help"));
+    for input in [
+        &["test-program", "help", "nope"],
+        &["test-program", "help", "--idk"],
+    ] {
+        let err = Command::create("root", "Tests the help subcommand")
+            .subcommand(Command::create("user", "Manages users"))
+            .parse_from(mkargs(input))
+            .err()
+            .unwrap();
+        assert!(!err.is_page());
+    }
+}
+
+#[test]
+fn test_subcommand_required() {
+    let err = Command::create("root", "Tests required subcommands")
+        .arg(arg!(--idk), value!(), "Just insert something")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .subcommand_required(true)
+        .parse_from(mkargs(&["test-program"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::MissingSubcommand(vec!["sub"]));
+    assert_eq!(err.to_string(), "'root' requires a subcommand: sub.");
+    assert!(err
+        .render(false)
+        .contains("sub.\n\nUSAGE:\n\troot [--idk] <SUBCOMMAND>\n"));
+    assert!(Command::create("root", "Tests required subcommands")
+        .arg(arg!(--idk), value!(), "Just insert something")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .subcommand_required(true)
+        .parse_from(mkargs(&["test-program", "sub"]))
+        .is_ok());
+    assert!(Command::create("root", "Tests required subcommands")
+        .arg(arg!(--idk), value!(), "Just insert something")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .subcommand_required(true)
+        .parse_from(mkargs(&["test-program", "--idk"]))
+        .is_ok());
+}
+
+#[test]
+fn test_multicall() {
+    for program in ["/usr/bin/tinycloud-backup", "backup"] {
+        let parsed = Command::create("tinycloud", "Tests multicall binaries")
+            .subcommand(Command::create("backup", "Makes a backup").arg(
+                arg!(--path),
+                value!(path),
+                "Insert a path",
+            ))
+            .multicall(true)
+            .parse_from(mkargs(&[program, "--path", "/some/path"]))
+            .unwrap();
+        assert_eq!(parsed.name, "backup");
+        assert_eq!(parsed.parents, vec!["tinycloud"]);
+    }
+    let parsed = Command::create("tinycloud", "Tests multicall binaries")
+        .subcommand(Command::create("backup", "Makes a backup"))
+        .multicall(true)
+        .parse_from(mkargs(&["/usr/bin/tinycloud", "backup"]))
+        .unwrap();
+    assert_eq!(parsed.name, "backup");
+    let parsed = Command::create("tinycloud", "Tests multicall binaries")
+        .subcommand(Command::create("backup", "Makes a backup"))
+        .multicall(true)
+        .parse_from(mkargs(&["tinycloud"]))
+        .unwrap();
+    assert_eq!(parsed.name, "tinycloud");
+}
+
+#[test]
+fn test_count_value() {
+    let input = mkargs(&["test-program", "-v", "--verbose", "-v", "--num", "4"]);
+    let parsed = test_command()
+        .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity")
+        .parse_from(input)
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(-'v')).count_value(), Some(3));
+    assert_eq!(parsed.args.get(arg!(--num)).count_value(), None);
+    assert_eq!(parsed.args.get(arg!(--num)).counter, 1);
+}
+
+#[test]
+fn test_duplicate_policy() {
+    let parsed = test_command()
+        .add_arg(Arg::new(
+            arg!(--tag),
+            value!(list, ["default"]),
+            "Insert a tag",
+        ))
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--tag)).list().unwrap(), ["default"]);
+    let input = mkargs(&[
+        "test-program",
+        "--tag",
+        "a",
+        "--tag",
+        "b",
+        "--last",
+        "a",
+        "--last",
+        "b",
+        "--once",
+        "a",
+    ]);
+    let parsed = test_command()
+        .add_arg(Arg::new(
+            arg!(--tag),
+            value!(list, ["default"]),
+            "Insert a tag",
+        ))
+        .add_arg(
+            Arg::new(arg!(--once), value!(string), "Insert something once")
+                .duplicates(DuplicatePolicy::Error),
+        )
+        .add_arg(
+            Arg::new(arg!(--last), value!(list), "Insert the last tag")
+                .duplicates(DuplicatePolicy::Overwrite),
+        )
+        .parse_from(input)
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--tag)).list().unwrap(), ["a", "b"]);
+    assert_eq!(parsed.args.get(arg!(--last)).list().unwrap(), ["b"]);
+    let input = mkargs(&["test-program", "--once", "a", "--once", "b"]);
+    let err = test_command()
+        .add_arg(
+            Arg::new(arg!(--once), value!(string), "Insert something once")
+                .duplicates(DuplicatePolicy::Error),
+        )
+        .parse_from(input)
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::Duplicate(arg!(--once)));
+    assert_eq!(err.to_string(), "'--once' cannot be used more than once.");
+}
+
+#[test]
+#[should_panic]
+fn test_duplicate_policy_fail() {
+    Arg::new(arg!(--num), value!(num), "Insert a number").duplicates(DuplicatePolicy::Append);
+}
+
+#[test]
+fn test_value_name() {
+    let parsed = Command::create("test", "Tests value names")
+        .add_arg(Arg::new(arg!(--timeout), value!(num), "Insert a timeout").value_name("SECONDS"))
+        .color(false)
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(parsed
+        .help
+        .contains("\t--timeout <SECONDS>  Insert a timeout\n"));
+}
+
+#[test]
+fn test_constrained_values() {
+    let input = mkargs(&["test-program", "--format", "yaml", "--port", "8080"]);
+    let parsed = Command::create("test", "Tests constrained values")
+        .arg(
+            arg!(--format),
+            ArgValue::Choice(&["json", "yaml"], Some("json".into())),
+            "Insert a format",
+        )
+        .arg(
+            arg!(--port),
+            ArgValue::Range(1..=65535, None),
+            "Insert a port",
+        )
+        .color(false)
+        .parse_from(input)
+        .unwrap();
+    assert!(parsed
+        .help
+        .contains("Insert a format [possible: json, yaml] [default: json]"));
+    assert!(parsed.help.contains("Insert a port [range: 1-65535]"));
+    assert_eq!(parsed.args.get(arg!(--format)).string(), Some("yaml"));
+    assert_eq!(parsed.args.get(arg!(--port)).num(), Some(8080));
+    let input = mkargs(&["test-program", "--format", "toml"]);
+    assert!(Command::create("test", "Tests constrained values")
+        .arg(
+            arg!(--format),
+            ArgValue::Choice(&["json", "yaml"], None),
+            "Insert a format",
+        )
+        .parse_from(input)
+        .is_err());
+    let input = mkargs(&["test-program", "--port", "0"]);
+    assert!(Command::create("test", "Tests constrained values")
+        .arg(
+            arg!(--port),
+            ArgValue::Range(1..=65535, None),
+            "Insert a port",
+        )
+        .parse_from(input)
+        .is_err());
+}
+
+#[test]
+fn test_deprecated() {
+    let parsed = test_command()
+        .color(false)
+        .add_arg(
+            Arg::new(arg!(--old), value!(string), "Insert something old")
+                .deprecated("use --idk instead"),
+        )
+        .parse_from(mkargs(&[
+            "test-program",
+            "--old",
+            "value",
+            "--idk",
+            "value",
+        ]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--old)).string(), Some("value"));
+    assert_eq!(
+        parsed.warnings,
+        vec!["'--old' is deprecated: use --idk instead"]
+    );
+    assert!(parsed.help.contains("Insert something old [deprecated]"));
+}
+
+#[test]
+fn test_global_args() {
+    let parsed = Command::create("root", "Tests global arguments")
+        .add_arg(Arg::new(arg!(--json), value!(), "Outputs JSON").global(true))
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user")),
+        )
+        .parse_from(mkargs(&["test-program", "user", "add", "--json"]))
+        .unwrap();
+    assert_eq!(parsed.args.count(arg!(--json)), 1);
+    let parsed =
+        Command::create("root", "Tests global arguments")
+            .add_arg(Arg::new(arg!(--json), value!(), "Outputs JSON").global(true))
+            .subcommand(Command::create("user", "Manages users").subcommand(
+                Command::create("login", "Logs in a user").without_global(arg!(--json)),
+            ))
+            .parse_from(mkargs(&["test-program", "user", "login"]))
+            .unwrap();
+    assert!(parsed.args.try_get(arg!(--json)).is_none());
+    let input = mkargs(&["test-program", "user", "login", "--json"]);
+    assert!(Command::create("root", "Tests global arguments")
+        .add_arg(Arg::new(arg!(--json), value!(), "Outputs JSON").global(true))
+        .subcommand(
+            Command::create("user", "Manages users").subcommand(
+                Command::create("login", "Logs in a user").without_global(arg!(--json)),
+            ),
+        )
+        .parse_from(input)
+        .is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_without_global_fail() {
+    let _ = Command::create("root", "Tests global arguments")
+        .add_arg(Arg::new(arg!(--json), value!(), "Outputs JSON").global(true))
+        .subcommand(Command::create("other", "Another subcommand").without_global(arg!(--xml)))
+        .parse_from(mkargs(&["test-program"]));
+}
+
+#[test]
+fn test_override_value() {
+    let mut parsed = test_command()
+        .parse_from(mkargs(&["test-program", "--num", "6"]))
+        .unwrap();
+    parsed.args.set(arg!(--idk), value!(string, "from config"));
+    parsed.override_value(arg!(--num), value!(num, 7));
+    assert_eq!(parsed.args.get(arg!(--idk)).string(), Some("from config"));
+    assert_eq!(parsed.args.get(arg!(--num)).num(), Some(7));
+    assert_eq!(parsed.args.count(arg!(--num)), 1);
+    assert_eq!(parsed.args.get(arg!(--idk)).source(), ValueSource::Override);
+    assert_eq!(parsed.args.get(arg!(--num)).source(), ValueSource::Override);
+    assert_eq!(
+        parsed.args.get(arg!(--float)).source(),
+        ValueSource::Default
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_override_value_fail() {
+    let mut parsed = test_command()
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    parsed.override_value(arg!(--num), value!(string, "6"));
+}
+
+#[test]
+fn test_fallbacks() {
+    let parsed = test_command()
+        .parse_from(mkargs(&["test-program", "--float", "2.5"]))
+        .unwrap();
+    assert_eq!(parsed.args.string_or(arg!(--idk), "fallback"), "fallback");
+    assert_eq!(
+        parsed.args.string_or(arg!(--idk2), "fallback"),
+        "default value"
+    );
+    assert_eq!(parsed.args.num_or(arg!(--num), 5), 3);
+    assert_eq!(parsed.args.num_or(arg!(--nope), 5), 5);
+    assert_eq!(parsed.args.float_or(arg!(--float), 1.0), 2.5);
+    assert_eq!(
+        parsed.args.path_or(arg!(--path), Path::new("/fallback")),
+        Path::new("/default/path")
+    );
+    assert_eq!(
+        parsed.args.path_or(arg!(--nope), Path::new("/fallback")),
+        Path::new("/fallback")
+    );
+}
+
+struct TestConfig {
+    num: i64,
+    path: PathBuf,
+}
+
+impl FromParsed for TestConfig {
+    fn from_parsed(parsed: &ParsedCommand) -> Result<Self, Vec<String>> {
+        let num = parsed.args.get(arg!(--num)).num();
+        let path = parsed.args.get(arg!(--path)).path();
+        match (num, path) {
+            (Some(num), Some(path)) => Ok(TestConfig {
+                num,
+                path: path.clone(),
+            }),
+            _ => Err(vec!["Missing values".into()]),
+        }
+    }
+}
+
+#[test]
+fn test_into_struct() {
+    let config: TestConfig = test_command()
+        .parse_from(mkargs(&["test-program", "--num", "6"]))
+        .unwrap()
+        .into_struct()
+        .unwrap();
+    assert_eq!(config.num, 6);
+    assert_eq!(config.path, PathBuf::from("/default/path"));
+}
+
+#[test]
+fn test_long_help() {
+    let parsed = Command::create("test", "Tests long help pages")
+        .add_arg(
+            Arg::new(arg!(--path), value!(path), "Insert a path")
+                .long_description("Insert a path to a directory that will be used for something"),
+        )
+        .color(false)
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(parsed.help.contains("Insert a path\n"));
+    assert!(!parsed.help.contains("to a directory"));
+    assert!(parsed
+        .long_help
+        .contains("Insert a path to a directory that will be used for something"));
+}
+
+#[test]
+fn test_error_render() {
+    let input = mkargs(&["test-program", "test", "--num", "6x"]);
+    let err = Command::create("root", "Tests error rendering")
+        .subcommand(test_command().arg(arg!(--nope), value!(), "Does nothing"))
+        .parse_from(input)
+        .err()
+        .unwrap();
+    assert_eq!(err.command(), "root test");
+    assert_eq!(
+        err.render(false),
+        "error: invalid value '6x' for '--num': must be a valid number: \
+         invalid digit found in string\n\n\
+         USAGE:\n\troot test [-V] [--path <PATH>] [--num <NUM>] [--float <FLOAT>] \
+         [--idk <IDK>] [--idk2 <IDK2>] [--help] [--nope]\n\n\
+         For more information, try 'root help test'."
+    );
+    let input = mkargs(&["test-program", "test", "--what"]);
+    let err = Command::create("root", "Tests error rendering")
+        .subcommand(test_command())
+        .parse_from(input)
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownArg(arg!(--what)));
+    assert!(err
+        .render(false)
+        .starts_with("error: '--what' is not a valid argument."));
+}
+
+#[test]
+fn test_strings() {
+    let strings = Strings {
+        usage: "UTILIZZO:",
+        args: "ARGOMENTI:",
+        unknown_arg: "'{arg}' non è un argomento valido.",
+        try_help: "Per altre informazioni, prova '{help}'.",
+        ..Strings::ENGLISH
+    };
+    let parsed = Command::create("root", "Tests localization")
+        .subcommand(test_command().color(false))
+        .strings(strings)
+        .parse_from(mkargs(&["test-program", "test"]))
+        .unwrap();
+    assert!(parsed.help.contains("UTILIZZO:\n"));
+    assert!(parsed.help.contains("ARGOMENTI:\n"));
+    let err = Command::create("root", "Tests localization")
+        .subcommand(test_command())
+        .strings(strings)
+        .parse_from(mkargs(&["test-program", "test", "--what"]))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "'--what' non è un argomento valido.");
+    assert!(err
+        .render(false)
+        .ends_with("Per altre informazioni, prova 'root help test'."));
+    assert_eq!(
+        Strings::fill("{a} {b} {c} {", &[("a", "{b}"), ("b", "1")]),
+        "{b} 1 {c} {"
+    );
+}
+
+#[test]
+fn test_config_template() {
+    let template = Command::create("root", "Tests config templates")
+        .arg(arg!(--float), value!(float, 2.0), "Insert a float")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .add_arg(Arg::new(arg!(--tag), value!(list, ["a \"b\""]), "Insert a tag").deprecated("no"))
+        .arg(
+            arg!(--format),
+            ArgValue::Choice(&["json", "yaml"], Some("json".into())),
+            "Insert a format",
+        )
+        .subcommand(Command::create("user", "Manages users").subcommand(
+            Command::create("add", "Adds a user").arg(arg!(--admin), value!(), "Is an admin"),
+        ))
+        .config_template();
+    println!("{template}");
+    assert!(template.contains("# Insert a float\n# type: float\nfloat = 2.0\n"));
+    assert!(template.contains("# type: integer\nv = 0\n"));
+    assert!(template.contains("# deprecated: no\ntag = [\"a \\\"b\\\"\"]\n"));
+    assert!(template.contains("# type: string, one of: json, yaml\nformat = \"json\"\n"));
+    assert!(template.contains("\n[user]\n# Manages users\n"));
+    assert!(template.contains(
+        "\n[user.add]\n# Adds a user\n\n# Is an admin\n# type: boolean\nadmin = false\n"
+    ));
+}
+
+#[test]
+fn test_extend() {
+    let parsed = Command::create("root", "Tests extending commands")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .arg(arg!(--name), value!(string), "Insert a name")
+                .extend(Command::create("shared", "Shared arguments").arg(
+                    arg!(-'v', --verbose),
+                    value!(count),
+                    "Increases verbosity",
+                )),
+        )
+        .parse_from(mkargs(&["test-program", "user", "-v", "--name", "me"]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--verbose)).count_value(), Some(1));
+    assert_eq!(parsed.args.get(arg!(--name)).string(), Some("me"));
+    let parsed = Command::create("root", "Tests extending commands")
+        .subcommand(
+            Command::create("user", "Manages users").extend(
+                Command::create("shared", "Shared arguments")
+                    .subcommand(Command::create("status", "Shows the status")),
+            ),
+        )
+        .parse_from(mkargs(&["test-program", "user", "status"]))
+        .unwrap();
+    assert!(parsed.is(["user", "status"]));
+    assert_eq!(parsed.parents, vec!["root", "user"]);
+}
+
+#[test]
+#[should_panic]
+fn test_extend_fail() {
+    Command::create("root", "Tests conflicting extensions")
+        .arg(arg!(-'v'), value!(), "Shows the version")
+        .extend(Command::create("shared", "Shared arguments").arg(
+            arg!(-'v', --verbose),
+            value!(count),
+            "Increases verbosity",
+        ));
+}
+
+#[test]
+fn test_args_env() {
+    env::set_var("TINY_ARGS_TEST_OPTS", "--idk 'from env' --num 4");
+    let parsed = Command::create("root", "Tests arguments from the environment")
+        .subcommand(test_command())
+        .args_env("TINY_ARGS_TEST_OPTS")
+        .parse_from(mkargs(&["test-program", "test", "--num", "5"]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--idk)).string(), Some("from env"));
+    assert_eq!(parsed.args.get(arg!(--num)).num(), Some(5));
+    assert_eq!(parsed.raw, vec!["test", "--num", "5"]);
+    env::set_var("TINY_ARGS_TEST_OPTS", "--idk 'from env");
+    let err = Command::create("root", "Tests arguments from the environment")
+        .subcommand(test_command())
+        .args_env("TINY_ARGS_TEST_OPTS")
+        .parse_from(mkargs(&["test-program", "test"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::InvalidEnvArgs("TINY_ARGS_TEST_OPTS".into())
+    );
+}
+
+#[test]
+fn test_split() {
+    assert_eq!(
+        split(r#"a  "b \"c\" d"'e'\ f"#).unwrap(),
+        ["a", "b \"c\" de f"]
+    );
+    assert_eq!(split(" \"\" '' ").unwrap(), ["", ""]);
+    assert_eq!(split("a\\"), Err(SplitError::TrailingBackslash));
+    assert_eq!(split("\"a"), Err(SplitError::UnterminatedQuote('"')));
+    let parsed = test_command()
+        .parse_from_str("--idk 'a b' --num 6")
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--idk)).string(), Some("a b"));
+    assert_eq!(parsed.raw, vec!["--idk", "a b", "--num", "6"]);
+    let err = test_command().parse_from_str("--idk 'a b").err().unwrap();
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::InvalidLine(SplitError::UnterminatedQuote('\''))
+    );
+    assert_eq!(
+        err.to_string(),
+        "the command line is not properly quoted: unterminated quote (')."
+    );
+}
+
+#[test]
+fn test_path_list() {
+    let parsed = Command::create("test", "Tests path lists")
+        .arg(arg!(--exclude), value!(paths, ["/tmp"]), "Exclude a path")
+        .add_arg(Arg::new(arg!(-'I'), value!(paths), "Include directories").split_paths(true))
+        .color(false)
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.get(arg!(--exclude)).paths().unwrap(),
+        [PathBuf::from("/tmp")]
+    );
+    assert!(parsed.help.contains("Exclude a path [default: /tmp]"));
+    let joined = env::join_paths(["/a", "/b"]).unwrap();
+    let input = vec![
+        "test-program".into(),
+        "--exclude".into(),
+        "/a:b".into(),
+        "--exclude".into(),
+        "/c".into(),
+        "-I".into(),
+        joined.into_string().unwrap(),
+    ];
+    let parsed = Command::create("test", "Tests path lists")
+        .arg(arg!(--exclude), value!(paths, ["/tmp"]), "Exclude a path")
+        .add_arg(Arg::new(arg!(-'I'), value!(paths), "Include directories").split_paths(true))
+        .color(false)
+        .parse_from(input)
+        .unwrap();
+    assert_eq!(
+        parsed.args.get(arg!(--exclude)).paths().unwrap(),
+        [PathBuf::from("/a:b"), PathBuf::from("/c")]
+    );
+    assert_eq!(
+        parsed.args.get(arg!(-'I')).paths().unwrap(),
+        [PathBuf::from("/a"), PathBuf::from("/b")]
+    );
+    assert_eq!(parsed.args.get(arg!(-'I')).path(), None);
+}
+
+#[test]
+fn test_bytes() {
+    let parsed = Command::create("test", "Tests bytes")
+        .arg(arg!(--key), value!(bytes, b"\x00\xff"), "Insert a key")
+        .color(false)
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(parsed.help.contains("Insert a key [default: hex:00ff]"));
+    for (input, bytes) in [
+        ("hex:DEADbeef", &b"\xde\xad\xbe\xef"[..]),
+        ("", b""),
+        ("TWFu", b"Man"),
+        ("TWE=", b"Ma"),
+        ("TQ", b"M"),
+        ("-_8", b"\xfb\xff"),
+    ] {
+        let parsed = Command::create("test", "Tests bytes")
+            .arg(arg!(--key), value!(bytes, b"\x00\xff"), "Insert a key")
+            .color(false)
+            .parse_from(mkargs(&["test-program", "--key", input]))
+            .unwrap();
+        assert_eq!(parsed.args.get(arg!(--key)).bytes_raw(), Some(bytes));
+    }
+    for input in ["hex:abc", "hex:zz", "T", "TW*a"] {
+        let err = Command::create("test", "Tests bytes")
+            .arg(arg!(--key), value!(bytes, b"\x00\xff"), "Insert a key")
+            .color(false)
+            .parse_from(mkargs(&["test-program", "--key", input]))
+            .err()
+            .unwrap();
+        assert!(matches!(err.kind(), ErrorKind::InvalidValue { .. }));
+    }
+}
+
+#[test]
+fn test_float_policy() {
+    let cmd = |policy| {
+        Command::create("test", "Tests float policies")
+            .add_arg(Arg::new(arg!(--float), value!(float), "Insert a float").float_policy(policy))
+    };
+    let parse = |policy, value: &str| {
+        cmd(policy)
+            .parse_from(mkargs(&["test-program", "--float", value]))
+            .map(|parsed| parsed.args.get(arg!(--float)).float().unwrap())
+    };
+    assert!(parse(FloatPolicy::ANY, "NaN").unwrap().is_nan());
+    assert_eq!(parse(FloatPolicy::ANY, "-inf").unwrap(), f64::NEG_INFINITY);
+    assert!(parse(FloatPolicy::ANY, "1,5").is_err());
+    assert!(parse(FloatPolicy::FINITE, "NaN").is_err());
+    assert!(parse(FloatPolicy::FINITE, "inf").is_err());
+    let policy = FloatPolicy {
+        max_magnitude: Some(10.0),
+        decimal_comma: true,
+        ..FloatPolicy::FINITE
+    };
+    assert_eq!(parse(policy, "-1,5").unwrap(), -1.5);
+    assert_eq!(parse(policy, "2.5").unwrap(), 2.5);
+    assert!(parse(policy, "1,000.5").is_err());
+    let err = parse(policy, "-10.5").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "'--float' value's must be between -10 and 10"
+    );
+}
+
+#[test]
+fn test_rest() {
+    let parsed = Command::create("root", "Tests positional values")
+        .arg(arg!(-'v'), value!(), "Shows more details")
+        .arg(arg!(--num), value!(num), "Insert a number")
+        .rest("FILE", "Files to upload")
+        .rest_min(2)
+        .subcommand(Command::create("sub", "A subcommand"))
+        .color(false)
+        .parse_from(mkargs(&["test-program", "a", "-v", "b", "--num", "3", "c"]))
+        .unwrap();
+    assert_eq!(parsed.rest, ["a", "b", "c"]);
+    assert_eq!(parsed.args.count(arg!(-'v')), 1);
+    assert_eq!(parsed.args.get(arg!(--num)).num(), Some(3));
+    assert!(parsed
+        .help
+        .contains("\troot [-v] [--num <NUM>] <FILE>...\n"));
+    assert!(parsed.help.contains("\t<FILE>...  Files to upload\n"));
+    let parsed = Command::create("root", "Tests positional values")
+        .rest("FILE", "Files to upload")
+        .rest_min(2)
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test-program", "sub"]))
+        .unwrap();
+    assert!(parsed.is(["sub"]));
+    assert!(parsed.rest.is_empty());
+    let err = Command::create("root", "Tests positional values")
+        .rest("FILE", "Files to upload")
+        .rest_min(2)
+        .parse_from(mkargs(&["test-program", "a"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::TooFewValues {
+            name: "FILE",
+            min: 2
+        }
+    );
+    assert_eq!(err.to_string(), "at least 2 'FILE' values are required.");
+    let err = test_command()
+        .parse_from(mkargs(&["test-program", "a"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownSubcommand("a".into()));
+}
+
+#[test]
+#[should_panic]
+fn test_rest_fail() {
+    Command::create("root", "Tests missing positional values").rest_min(1);
+}
+
+#[test]
+fn test_ancestors() {
+    let input = mkargs(&[
+        "test-program",
+        "-v",
+        "--json",
+        "user",
+        "--name=add",
+        "add",
+        "--kind",
+        "admin",
+    ]);
+    let parsed = Command::create("root", "Tests nested arguments")
+        .arg(arg!(-'v', --verbose), value!(), "Shows more details")
+        .add_arg(Arg::new(arg!(--json), value!(), "Outputs JSON").global(true))
+        .subcommand(
+            Command::create("user", "Manages users")
+                .arg(arg!(--name), value!(string), "Insert a name")
+                .subcommand(Command::create("add", "Adds a user").arg(
+                    arg!(--kind),
+                    value!(string),
+                    "Insert a kind",
+                )),
+        )
+        .parse_from(input)
+        .unwrap();
+    assert!(parsed.is(["user", "add"]));
+    assert_eq!(parsed.ancestors.len(), 2);
+    assert_eq!(parsed.ancestors[0].count(arg!(-'v')), 1);
+    assert_eq!(parsed.ancestors[1].get(arg!(--name)).string(), Some("add"));
+    assert_eq!(parsed.args.get(arg!(--kind)).string(), Some("admin"));
+    assert_eq!(parsed.args.count(arg!(--json)), 1);
+    // A subcommand is not taken as a value.
+    let input = mkargs(&["test-program", "user", "--name", "add", "--kind", "admin"]);
+    let err = Command::create("root", "Tests nested arguments")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .arg(arg!(--name), value!(string), "Insert a name")
+                .subcommand(Command::create("add", "Adds a user").arg(
+                    arg!(--kind),
+                    value!(string),
+                    "Insert a kind",
+                )),
+        )
+        .parse_from(input)
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::MissingValue(arg!(--name)));
+    assert_eq!(err.command(), "root user");
+    let input = mkargs(&["test-program", "user", "--kind", "admin", "add"]);
+    let err = Command::create("root", "Tests nested arguments")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .arg(arg!(--name), value!(string), "Insert a name")
+                .subcommand(Command::create("add", "Adds a user").arg(
+                    arg!(--kind),
+                    value!(string),
+                    "Insert a kind",
+                )),
+        )
+        .parse_from(input)
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownArg(arg!(--kind)));
+    assert_eq!(err.command(), "root user");
+    let input = mkargs(&["test-program", "-v", "nope"]);
+    let err = Command::create("root", "Tests nested arguments")
+        .arg(arg!(-'v', --verbose), value!(), "Shows more details")
+        .add_arg(Arg::new(arg!(--json), value!(), "Outputs JSON").global(true))
+        .subcommand(
+            Command::create("user", "Manages users")
+                .arg(arg!(--name), value!(string), "Insert a name")
+                .subcommand(Command::create("add", "Adds a user").arg(
+                    arg!(--kind),
+                    value!(string),
+                    "Insert a kind",
+                )),
+        )
+        .parse_from(input)
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnexpectedToken("nope".into()));
+}
+
+#[test]
+fn test_print() {
+    let cmd = test_command().color(false);
+    let mut buf = Vec::new();
+    cmd.print_help(&mut buf).unwrap();
+    let help = String::from_utf8(buf).unwrap();
+    assert!(help.starts_with("test "));
+    assert!(help.ends_with("Licensed under GPL-3.0\n"));
+    let err = cmd
+        .parse_from(mkargs(&["test-program", "--what"]))
+        .err()
+        .unwrap();
+    let mut buf = Vec::new();
+    err.print(&mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), err.render(false) + "\n");
+}
+
+#[test]
+fn test_error_formatter() {
+    let err = Command::create("root", "Tests error formatters")
+        .subcommand(test_command())
+        .error_formatter(|err| format!("{} failed: {err}", err.command()))
+        .parse_from(mkargs(&["test-program", "test", "--num", "x"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.render(true),
+        "root test failed: '--num' value's must be a valid number: invalid digit found in string"
+    );
+    assert_eq!(
+        err.usage(),
+        "USAGE:\n\troot test [-V] [--path <PATH>] [--num <NUM>] [--float <FLOAT>] \
+         [--idk <IDK>] [--idk2 <IDK2>] [--help]"
+    );
+}
+
+#[test]
+fn test_help_flag() {
+    for input in [
+        &["test-program", "sub", "-?"][..],
+        &["test-program", "--help", "sub"],
+    ] {
+        let ErrorKind::DisplayHelp(help) = page(
+            Command::create("root", "Tests help flags")
+                .arg(arg!(-'h', --host), value!(string), "Insert a host")
+                .subcommand(Command::create("sub", "A subcommand").color(false))
+                .help_flag(arg!(-'?', --help))
+                .color(false),
+            input,
+        ) else {
+            panic!("The help page was not requested");
+        };
+        assert!(help.starts_with("root sub"));
+        assert!(help.contains("\t-?, --help  Shows this help.\n"));
+    }
+    let parsed = Command::create("root", "Tests help flags")
+        .arg(arg!(-'h', --host), value!(string), "Insert a host")
+        .subcommand(Command::create("sub", "A subcommand").color(false))
+        .help_flag(arg!(-'?', --help))
+        .color(false)
+        .parse_from(mkargs(&["test-program", "-h", "localhost"]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--host)).string(), Some("localhost"));
+    let err = Command::create("root", "Tests help flags")
+        .arg(arg!(-'h', --host), value!(string), "Insert a host")
+        .subcommand(Command::create("sub", "A subcommand").color(false))
+        .help_flag(arg!(-'?', --help))
+        .color(false)
+        .disable_help()
+        .parse_from(mkargs(&["test-program", "help"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownSubcommand("help".into()));
+    assert!(err
+        .render(false)
+        .ends_with("USAGE:\n\troot [--host <HOST>]\n\troot [--host <HOST>] [SUBCOMMAND]"));
+    let err = Command::create("root", "Tests help flags")
+        .arg(arg!(-'h', --host), value!(string), "Insert a host")
+        .subcommand(Command::create("sub", "A subcommand").color(false))
+        .help_flag(arg!(-'?', --help))
+        .color(false)
+        .disable_help()
+        .help_flag(arg!(-'?', --help))
+        .parse_from(mkargs(&["test-program", "sub", "--what"]))
+        .err()
+        .unwrap();
+    assert!(err.render(false).ends_with("try 'root sub --help'."));
+    let parsed = test_command()
+        .version_flag(arg!(--version))
+        .auto_version(true)
+        .color(false)
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(parsed.help.contains("\t--version   Shows the version.\n"));
+}
+
+#[test]
+fn test_arg_name_validation() {
+    assert_eq!(ArgName::try_both('h', "help"), Ok(arg!(-'h', --help)));
+    assert_eq!(
+        ArgName::try_short(' '),
+        Err(ArgNameError::InvalidShort(' '))
+    );
+    assert_eq!(ArgName::try_long(""), Err(ArgNameError::Empty));
+    assert_eq!(
+        ArgName::try_long("  weird name"),
+        Err(ArgNameError::Whitespace("  weird name".into()))
+    );
+    assert_eq!(
+        ArgName::try_both('h', "-help"),
+        Err(ArgNameError::LeadingDash("-help".into()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_arg_name_validation_fail() {
+    arg!(-'-');
+}
+
+#[test]
+fn test_short_clusters() {
+    let input = mkargs(&["test-program", "-vév🦀n5", "-名", "me"]);
+    let parsed = Command::create("test", "Tests short arguments")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .arg(arg!(-'é'), value!(), "Does something")
+        .arg(arg!(-'🦀', --crab), value!(), "Shows a crab")
+        .arg(arg!(-'n'), value!(num), "Insert a number")
+        .arg(arg!(-'名', --name), value!(string), "Insert a name")
+        .color(false)
+        .parse_from(input)
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(-'v')).count_value(), Some(2));
+    assert_eq!(parsed.args.count(arg!(-'é')), 1);
+    assert_eq!(parsed.args.count(arg!(--crab)), 1);
+    assert_eq!(parsed.args.get(arg!(-'n')).num(), Some(5));
+    assert_eq!(parsed.args.get(arg!(--name)).string(), Some("me"));
+    let parsed = Command::create("test", "Tests short arguments")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .arg(arg!(-'é'), value!(), "Does something")
+        .arg(arg!(-'🦀', --crab), value!(), "Shows a crab")
+        .arg(arg!(-'n'), value!(num), "Insert a number")
+        .arg(arg!(-'名', --name), value!(string), "Insert a name")
+        .color(false)
+        .parse_from(mkargs(&["test-program", "-vn", "-5", "-名é"]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(-'n')).num(), Some(-5));
+    assert_eq!(parsed.args.get(arg!(--name)).string(), Some("é"));
+    assert!(parsed.help.contains("\t-名, --name  Insert a name\n"));
+    assert!(parsed.help.contains("\t-🦀, --crab  Shows a crab\n"));
+    assert!(parsed.help.contains("\t-n           Insert a number\n"));
+    let err = Command::create("test", "Tests short arguments")
+        .arg(arg!(-'é'), value!(), "Does something")
+        .parse_from(mkargs(&["test-program", "-e\u{301}"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::InvalidArg("-e\u{301}".into()));
+    let err = Command::create("test", "Tests short arguments")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .parse_from(mkargs(&["test-program", "-vx"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownArg(arg!(-'x')));
+    assert_eq!(width::width("a名🦀e\u{301}👨\u{200D}👩"), 8);
+}
+
+#[test]
+fn test_attached_long_values() {
+    let input = mkargs(&[
+        "test-program",
+        "--idk=name=foo",
+        "--idk2=-value",
+        "--num=-4",
+        "--path=",
+    ]);
+    let parsed = test_command().parse_from(input).unwrap();
+    assert_eq!(parsed.args.get(arg!(--idk)).string(), Some("name=foo"));
+    assert_eq!(parsed.args.get(arg!(--idk2)).string(), Some("-value"));
+    assert_eq!(parsed.args.get(arg!(--num)).num(), Some(-4));
+    assert_eq!(parsed.args.get(arg!(--path)).path(), Some(&PathBuf::new()));
+    let err = test_command()
+        .parse_from(mkargs(&["test-program", "--help=yes"]))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "'-h, --help' value's must be omitted");
+    let err = test_command()
+        .parse_from(mkargs(&["test-program", "--what=yes"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownArg(arg!(--what)));
+    let parsed = Command::create("root", "Tests attached values in subcommands")
+        .arg(arg!(--name), value!(string), "Insert a name")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test-program", "--name=me", "sub"]))
+        .unwrap();
+    assert!(parsed.is(["sub"]));
+    assert_eq!(parsed.ancestors[0].get(arg!(--name)).string(), Some("me"));
+}
+
+#[test]
+fn test_help_escape() {
+    let parsed = Command::create("test", "Tests escaping\n\x1b[2Jin help pages")
+        .arg(
+            arg!(--idk),
+            value!(string, "a\tb"),
+            "Just insert\nsomething \x1b[31mred",
+        )
+        .subcommand(Command::create("sub", "A\rsubcommand"))
+        .color(false)
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    println!("{}", parsed.help);
+    assert!(parsed
+        .help
+        .contains("Tests escaping\n\\u{1b}[2Jin help pages"));
+    assert!(parsed
+        .help
+        .contains("\t--idk  Just insert something \\u{1b}[31mred [default: a b]\n"));
+    assert!(parsed.help.contains("\tsub    A subcommand\n"));
+}
+
+#[test]
+fn test_color_feature() {
+    let cmd = Command::create("test", "Tests the color feature")
+        .arg(arg!(--num), value!(num), "Insert a number")
+        .color(true);
+    let mut buf = Vec::new();
+    cmd.print_help(&mut buf).unwrap();
+    let help = String::from_utf8(buf).unwrap();
+    let err = cmd
+        .parse_from(mkargs(&["test-program", "--num", "x"]))
+        .err()
+        .unwrap();
+    println!("{help}\n{}", err.render(true));
+    assert_eq!(help.contains('\x1b'), cfg!(feature = "color"));
+    assert_eq!(err.render(true).contains('\x1b'), cfg!(feature = "color"));
+}
+
+#[derive(Clone, Debug)]
+struct Hostname(Option<String>);
+
+impl ArgValueKind for Hostname {
+    fn parse(&mut self, input: &str) -> Result<(), String> {
+        if input.is_empty() || !input.chars().all(|c| c.is_ascii_alphanumeric() || c == '.') {
+            return Err("a valid hostname".into());
+        }
+        self.0 = Some(input.into());
+        Ok(())
+    }
+
+    fn type_name(&self) -> &str {
+        "hostname"
+    }
+
+    fn default_display(&self) -> Option<String> {
+        self.0.clone()
+    }
+
+    fn clone_box(&self) -> Box<dyn ArgValueKind> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_other_value() {
+    let cmd = Command::create("test", "Tests custom values")
+        .arg(
+            arg!(--host),
+            ArgValue::Other(Box::new(Hostname(Some("localhost".into())))),
+            "Insert a hostname",
+        )
+        .color(false);
+    assert!(cmd
+        .config_template()
+        .contains("# type: hostname\nhost = \"localhost\"\n"));
+    let parsed = cmd.parse_from(mkargs(&["test-program"])).unwrap();
+    assert!(parsed.help.contains("[default: localhost]"));
+    let parsed = Command::create("test", "Tests custom values")
+        .arg(
+            arg!(--host),
+            ArgValue::Other(Box::new(Hostname(Some("localhost".into())))),
+            "Insert a hostname",
+        )
+        .color(false)
+        .parse_from(mkargs(&["test-program", "--host", "example.org"]))
+        .unwrap();
+    let host = parsed.args.get(arg!(--host));
+    assert_eq!(
+        host.other::<Hostname>().unwrap().0.as_deref(),
+        Some("example.org")
+    );
+    assert!(host.string().is_none());
+    let err = Command::create("test", "Tests custom values")
+        .arg(
+            arg!(--host),
+            ArgValue::Other(Box::new(Hostname(None))),
+            "Insert a hostname",
+        )
+        .parse_from(mkargs(&["test-program", "--host", "a b"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::InvalidValue {
+            arg: arg!(--host),
+            value: "a b".into(),
+            reason: "a valid hostname".into()
+        }
+    );
+}
+
+#[test]
+fn test_inherited_metadata() {
+    let parsed = Command::create("test", "Tests inherited metadata")
+        .version("1.2.3")
+        .author("Someone")
+        .license("GPL-3.0")
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .subcommand(Command::create("leaf", "A leaf").color(false))
+                .color(false),
+        )
+        .parse_from(mkargs(&["test-program", "sub", "leaf"]))
+        .unwrap();
+    assert!(parsed
+        .help
+        .starts_with("test sub leaf 1.2.3\nSomeone\nA leaf\n"));
+    assert!(parsed.help.ends_with("GPL-3.0"));
+    let parsed = Command::create("test", "Tests inherited metadata")
+        .version("1.2.3")
+        .author("Someone")
+        .license("GPL-3.0")
+        .subcommand(
+            Command::create("other", "Another subcommand")
+                .version("0.1.0")
+                .author("Someone else")
+                .color(false),
+        )
+        .parse_from(mkargs(&["test-program", "other"]))
+        .unwrap();
+    assert!(parsed
+        .help
+        .starts_with("test other 0.1.0\nSomeone else\nAnother subcommand\n"));
+    assert!(parsed.help.ends_with("GPL-3.0"));
+}
+
+#[cfg(feature = "definitions")]
+#[test]
+fn test_from_toml_str() {
+    let cmd = Command::from_toml_str(
+        r#"
+        # A whole command tree.
+        name = "test"
+        description = "Tests definitions"
+        version = "1.0.0"
+        color = false
+
+        homepage = "https://example.com"
+
+        [rest]
+        name = "FILE"
+        description = "Some files"
+
+        [[examples]]
+        line = "test -v sub leaf"
+        description = "Reaches the leaf"
+
+        [[args]]
+        short = "v"
+        long = "verbose"
+        type = "count"
+        global = true
+        description = "Increases verbosity"
+
+        [[args]]
+        long = "format"
+        type = "choice"
+        choices = [ "json", 'yaml' ]
+        default = "json"
+        description = "Output format"
+
+        [[subcommands]]
+        name = "sub"
+        description = "A \"subcommand\""
+        args = [{ long = "ratio", type = "float", default = 0.5, description = "A ratio" }]
+
+        [[subcommands.subcommands]]
+        name = "leaf"
+        description = "A leaf"
+
+        [[subcommands.subcommands.args]]
+        long = "tag"
+        type = "list"
+        duplicates = "append"
+        delimiter = ","
+        merge = "dedupe"
+        default = ["a", "b"]
+        description = "Tags"
+        "#,
+    )
+    .unwrap();
+    let mut help = Vec::new();
+    cmd.print_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("\ttest -v sub leaf\n\t\tReaches the leaf\n"));
+    let parsed = cmd
+        .parse_from(mkargs(&[
+            "test-program",
+            "-v",
+            "sub",
+            "leaf",
+            "--tag",
+            "x",
+            "--tag=y,x",
+        ]))
+        .unwrap();
+    assert!(parsed.is(["sub", "leaf"]));
+    assert_eq!(parsed.args.get(arg!(-'v')).count_value(), Some(1));
+    assert_eq!(
+        parsed.args.get(arg!(--tag)).list(),
+        Some(&["x".into(), "y".into()][..])
+    );
+    assert_eq!(parsed.ancestors[1].get(arg!(--ratio)).float(), Some(0.5));
+    assert_eq!(
+        parsed.ancestors[0].get(arg!(--format)).string(),
+        Some("json")
+    );
+    assert!(parsed.help.contains("1.0.0"));
+    assert!(parsed.help.contains("Homepage: https://example.com\n"));
+
+    let err = |document| Command::from_toml_str(document).err().unwrap();
+    assert_eq!(
+        err("name = \"test\"\ndescription = \"x\"\n[[args]]\nlong = \"a\"\ntype = \"idk\"\ndescription = \"x\""),
+        DefinitionError::Invalid(
+            "args[0]".into(),
+            "unknown type 'idk', must be one of: string, num, float, path, flag, choice, range, \
+             list, count, paths, bytes, ratio"
+                .into()
+        )
+    );
+    assert_eq!(
+        err("name = \"test\"\ndescription = \"x\"\ncolour = false"),
+        DefinitionError::Invalid("".into(), "unknown field 'colour'".into())
+    );
+    assert_eq!(
+        err("name = \"test\"\ndescription = \"x\"\nvisibility = \"secret\""),
+        DefinitionError::Invalid(
+            "".into(),
+            "'visibility' must be one of: public, internal, hidden".into()
+        )
+    );
+    assert_eq!(
+        err("name = \"test\"\n\ndescription = \"x"),
+        DefinitionError::Syntax(3, "unterminated string".into())
+    );
+    assert_eq!(
+        err("name = \"test\"\nname = \"again\""),
+        DefinitionError::Syntax(2, "'name' is defined twice".into())
+    );
+}
+
+#[test]
+fn test_completions() {
+    let cmd = Command::create("test-app", "Tests completions")
+        .arg(
+            arg!(-'v', --verbose),
+            value!(count),
+            "Increases \"verbosity\"",
+        )
+        .add_arg(Arg::new(arg!(--config), value!(path), "Config: it's a file").global(true))
+        .arg(
+            arg!(-'f', --format),
+            ArgValue::Choice(&["json", "yaml"], None),
+            "Output [format]",
+        )
+        .auto_version(true)
+        .help_flag(arg!(-'h', --help))
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .rest("FILE", "Some files")
+                .subcommand(Command::create("leaf", "A leaf")),
+        );
+    let bash = generate(Shell::Bash, &cmd);
+    println!("{bash}");
+    assert!(bash.contains("\"test_app,sub\") cmd=\"test_app__sub\" ;;\n"));
+    assert!(bash.contains("\"test_app__sub,leaf\") cmd=\"test_app__sub__leaf\" ;;\n"));
+    assert!(bash.contains("compgen -W \"json yaml\""));
+    assert!(bash.contains(
+        "compgen -W \"-v --verbose --config -f --format -h --help -V --version sub help\""
+    ));
+    assert!(bash
+        .contains("compgen -W \"--config -h --help\" -- \"${cur}\"))\n            ;;\n    esac"));
+    assert!(bash.ends_with("complete -F _test_app -o bashdefault -o default test-app\n"));
+
+    let zsh = generate(Shell::Zsh, &cmd);
+    println!("{zsh}");
+    assert!(zsh.starts_with("#compdef test-app\n"));
+    assert!(zsh.contains("'--config=[Config: it'\\''s a file]:value:_files'"));
+    assert!(zsh.contains("'-f+[Output \\[format\\]]:value:(\"json\" \"yaml\")'"));
+    assert!(zsh.contains("'*-v[Increases \"verbosity\"]'"));
+    assert!(zsh.contains("'1:subcommand:((sub\\:\"A subcommand\" help\\:"));
+    assert!(zsh.contains("'*:FILE:_default'"));
+
+    let fish = generate(Shell::Fish, &cmd);
+    println!("{fish}");
+    assert!(fish.contains("complete -c test-app -n 'test (__test_app_command) = test_app' -s 'f' -l 'format' -r -f -a 'json yaml' -d 'Output [format]'\n"));
+    assert!(fish.contains("complete -c test-app -n 'test (__test_app_command) = test_app__sub__leaf' -l 'config' -r -F -d 'Config: it\\'s a file'\n"));
+    assert!(!fish.contains("= test_app__sub' -f\n"));
+
+    let dir = env::temp_dir().join("tiny-args-test-completions");
+    let path = generate_to(&dir, Shell::Fish, &cmd).unwrap();
+    assert_eq!(path, dir.join("test-app.fish"));
+    assert_eq!(std::fs::read_to_string(path).unwrap(), fish);
+}
+
+#[test]
+fn test_synopsis() {
+    let usage = |cmd: Command| cmd.color(false).parse_from(mkargs(&["test-program"]));
+    let parsed = usage(
+        Command::create("test", "Tests the synopsis")
+            .arg(arg!(-'v'), value!(count), "Increases verbosity")
+            .arg(arg!(-'n'), value!(num), "Insert a number")
+            .arg(
+                arg!(-'f', --format),
+                ArgValue::Choice(&["json", "yaml"], None),
+                "Output format",
+            )
+            .add_arg(
+                Arg::new(arg!(--tag), value!(list), "Insert a tag")
+                    .duplicates(DuplicatePolicy::Append)
+                    .value_name("NAME"),
+            ),
+    )
+    .unwrap();
+    assert!(parsed.help.contains(
+        "USAGE:\n\ttest [-v]... [-n <VALUE>] [--format <json|yaml>] [--tag <NAME>]...\n"
+    ));
+    let parsed = usage(Command::create("test", "Tests the synopsis")).unwrap();
+    assert!(parsed.help.contains("USAGE:\n\ttest\n"));
+    let err = usage(
+        Command::create("test", "Tests the synopsis")
+            .arg(arg!(--dry), value!(), "Does nothing")
+            .subcommand(Command::create("sub", "A subcommand"))
+            .subcommand_required(true),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.usage(), "USAGE:\n\ttest [--dry] <SUBCOMMAND>");
+}
+
+#[test]
+fn test_wants_help() {
+    let parsed = Command::create("root", "Tests manual help")
+        .arg(arg!(--help), value!(), "Shows this help")
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(!parsed.wants_help());
+    let parsed = Command::create("root", "Tests manual help")
+        .arg(arg!(--help), value!(), "Shows this help")
+        .parse_from(mkargs(&["test-program", "--help"]))
+        .unwrap();
+    assert!(parsed.wants_help());
+    let parsed = Command::create("root", "Tests manual help")
+        .arg(arg!(--help), value!(), "Shows this help")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test-program", "--help", "sub"]))
+        .unwrap();
+    assert!(parsed.wants_help());
+    let parsed = Command::create("root", "Tests manual help")
+        .arg(arg!(--help), value!(), "Shows this help")
+        .subcommand(Command::create("sub", "A subcommand").arg(
+            arg!(-'h', --host),
+            value!(string),
+            "Insert a host",
+        ))
+        .parse_from(mkargs(&["test-program", "sub", "-h", "localhost"]))
+        .unwrap();
+    assert!(!parsed.wants_help());
+    // Only the names of the help flag count.
+    let parsed = Command::create("root", "Tests manual help")
+        .arg(arg!(-'h', --hidden), value!(), "Shows the hidden files")
+        .parse_from(mkargs(&["test-program", "-h"]))
+        .unwrap();
+    assert!(!parsed.wants_help());
+}
+
+#[test]
+fn test_command_macro() {
+    let declared = command! {
+        name: "root",
+        description: "Tests the command macro",
+        version: "1.0.0",
+        color: false,
+        args: [
+            [-'v', --verbose] => [], "Shows more details" { global: true },
+            [--num] => [num, 5], "Insert a number" { value_name: "N", deprecated: "Use --count" },
+        ],
+        subcommands: [
+            { name: "sub", description: "A subcommand", color: false },
+            {
+                name: "other",
+                description: "Another subcommand",
+                rest: ("FILE", "Some files"),
+                rest_min: 1,
+                subcommands: [{ name: "leaf", description: "A leaf" }],
+            },
+        ],
+    };
+    let built = Command::create("root", "Tests the command macro")
+        .version("1.0.0")
+        .color(false)
+        .add_arg(Arg::new(arg!(-'v', --verbose), value!(), "Shows more details").global(true))
+        .add_arg(
+            Arg::new(arg!(--num), value!(num, 5), "Insert a number")
+                .value_name("N")
+                .deprecated("Use --count"),
+        )
+        .subcommand(Command::create("sub", "A subcommand").color(false))
+        .subcommand(
+            Command::create("other", "Another subcommand")
+                .rest("FILE", "Some files")
+                .rest_min(1)
+                .subcommand(Command::create("leaf", "A leaf")),
+        );
+    assert_eq!(declared.config_template(), built.config_template());
+    let declared = declared
+        .parse_from(mkargs(&["test-program", "sub", "-v"]))
+        .unwrap();
+    let built = built
+        .parse_from(mkargs(&["test-program", "sub", "-v"]))
+        .unwrap();
+    assert_eq!(declared.help, built.help);
+    assert_eq!(declared.args.count(arg!(-'v')), 1);
+}
+
+#[test]
+fn test_value_constraints() {
+    let dir = env::temp_dir();
+    let parsed = Command::create("test", "Tests constrained values")
+        .arg(
+            arg!(--port),
+            value!(num in 1..=65535, 8080),
+            "Insert a port",
+        )
+        .arg(
+            arg!(--format),
+            value!(choice ["json", "yaml"]),
+            "Insert a format",
+        )
+        .arg(arg!(--dir), value!(path exists), "Insert a directory")
+        .parse_from(mkargs(&[
+            "test-program",
+            "--format",
+            "yaml",
+            "--dir",
+            dir.to_str().unwrap(),
+        ]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--port)).num(), Some(8080));
+    assert_eq!(parsed.args.get(arg!(--format)).string(), Some("yaml"));
+    assert_eq!(parsed.args.get(arg!(--dir)).path(), Some(&dir));
+    let err = Command::create("test", "Tests constrained values")
+        .arg(arg!(--dir), value!(path exists), "Insert a directory")
+        .parse_from(mkargs(&["test-program", "--dir", "/does/not/exist"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::InvalidValue {
+            arg: arg!(--dir),
+            value: "/does/not/exist".into(),
+            reason: "an existing path".into()
+        }
+    );
+    assert!(Command::create("test", "Tests constrained values")
+        .arg(
+            arg!(--port),
+            value!(num in 1..=65535, 8080),
+            "Insert a port",
+        )
+        .parse_from(mkargs(&["test-program", "--port", "0"]))
+        .is_err());
+    assert!(Command::create("test", "Tests constrained values")
+        .arg(arg!(--dir), value!(path exists), "Insert a directory")
+        .config_template()
+        .contains("# type: path, must exist\n#dir =\n"));
+}
+
+#[test]
+fn test_literal_arg_names() {
+    let parsed = Command::create("test", "Tests literal names")
+        .arg(arg!(--"http2"), value!(), "Uses HTTP/2")
+        .arg(
+            arg!(-'l', --"log.level"),
+            value!(string),
+            "Insert a log level",
+        )
+        .parse_from(mkargs(&["test-program", "--http2", "--log.level=debug"]))
+        .unwrap();
+    assert_eq!(parsed.args.count(arg!(--"http2")), 1);
+    assert_eq!(parsed.args.get(arg!(-'l')).string(), Some("debug"));
+    assert!(ArgName::is_valid_long("ünïcode"));
+    assert!(!ArgName::is_valid_long("no\u{3000}space"));
+    assert!(!ArgName::is_valid_long("-dash"));
+    assert!(!ArgName::is_valid_long(""));
+    assert!(!ArgName::is_valid_short(' '));
+}
+
+#[test]
+fn test_get_by_name() {
+    let parsed = Command::create("test", "Tests lookups by name")
+        .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity")
+        .arg(arg!(--x), value!(string), "Insert something")
+        .arg(arg!(-'y'), value!(), "Does something")
+        .parse_from(mkargs(&["test-program", "-vv", "--x", "z", "-y"]))
+        .unwrap();
+    for name in ["v", "-v", "verbose", "--verbose"] {
+        assert_eq!(
+            parsed.args.get_by_name(name).unwrap().count_value(),
+            Some(2)
+        );
+    }
+    assert_eq!(parsed.args.get_by_name("x").unwrap().string(), Some("z"));
+    assert_eq!(parsed.args.get_by_name("--x").unwrap().string(), Some("z"));
+    assert!(parsed.args.get_by_name("-x").is_none());
+    assert_eq!(parsed.args.get_by_name("y").unwrap().counter, 1);
+    assert!(parsed.args.get_by_name("--y").is_none());
+    for name in ["", "-", "--", "-vv", "---verbose", "in valid"] {
+        assert!(parsed.args.get_by_name(name).is_none());
+    }
+}
+
+#[test]
+fn test_positionals() {
+    let parsed = Command::create("test", "Tests positional accessors")
+        .arg(arg!(-'v'), value!(), "Shows more details")
+        .rest("VALUE", "Some values")
+        .parse_from(mkargs(&["test-program", "5", "-v", "2.5", "/some/path"]))
+        .unwrap();
+    let first = parsed.positional(0).unwrap();
+    assert_eq!(first.name(), "VALUE");
+    assert_eq!(first.index(), 0);
+    assert_eq!(first.num(), Some(5));
+    assert_eq!(first.float(), Some(5.0));
+    assert_eq!(parsed.positional(1).unwrap().num(), None);
+    assert_eq!(parsed.positional(1).unwrap().float(), Some(2.5));
+    assert_eq!(
+        parsed.positional(2).unwrap().path(),
+        Some(PathBuf::from("/some/path"))
+    );
+    let choice = ArgValue::Choice(&["5", "2.5"], None);
+    let arg = parsed.positional(1).unwrap().arg(choice.clone()).unwrap();
+    assert_eq!(arg.string(), Some("2.5"));
+    assert_eq!(arg.source(), ValueSource::CommandLine);
+    assert!(parsed.positional(2).unwrap().arg(choice).is_none());
+    assert!(parsed.positional(3).is_none());
+    let values: Vec<&str> = parsed.positionals().filter_map(|p| p.string()).collect();
+    assert_eq!(values, parsed.rest);
+    assert_eq!(parsed.positionals_named("VALUE").count(), 3);
+    assert_eq!(parsed.positionals_named("FILE").count(), 0);
+    let parsed = Command::create("test", "Tests positional accessors")
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(parsed.positional(0).is_none());
+    assert_eq!(parsed.positionals().count(), 0);
+}
+
+#[test]
+fn test_sensitive() {
+    let cmd = Command::create("test-program", "This is a test program")
+        .add_arg(
+            Arg::new(
+                arg!(-'p', --password),
+                value!(string, "hunter2"),
+                "Password",
+            )
+            .sensitive(),
+        )
+        .add_arg(Arg::new(arg!(-'v', --verbose), ArgValue::Flag, "Verbose"))
+        .arg(arg!(--user), value!(string, "root"), "User")
+        .color(false);
+    let help = help::create(&cmd, false);
+    assert!(!help.contains("hunter2"));
+    assert!(help.contains("root"));
+    assert!(!cmd.config_template().contains("hunter2"));
+
+    let parsed = cmd
+        .parse_from(mkargs(&[
+            "test-program",
+            "--password",
+            "one",
+            "--password=two",
+            "-vpthree",
+            "-p",
+            "four",
+            "--user",
+            "me",
+        ]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--password)).string(), Some("four"));
+    assert_eq!(
+        parsed.raw,
+        [
+            "--password",
+            "***",
+            "--password=***",
+            "-vp***",
+            "-p",
+            "***",
+            "--user",
+            "me"
+        ]
+    );
+
+    let err = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(--port), value!(num), "Port").sensitive())
+        .parse_from(mkargs(&["test-program", "--port", "secret"]));
+    assert!(matches!(
+        err.err().unwrap().kind(),
+        ErrorKind::InvalidValue { value, .. } if value == "***"
+    ));
+}
+
+#[test]
+fn test_redacted_debug() {
+    let parsed = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(--token), value!(string), "Token").sensitive())
+        .arg(arg!(--user), value!(string), "User")
+        .parse_from(mkargs(&[
+            "test-program",
+            "--token",
+            "hunter2",
+            "--user",
+            "me",
+        ]))
+        .unwrap();
+    let debug = format!("{:?}", parsed.args);
+    assert!(!debug.contains("hunter2"));
+    assert!(debug.contains("argvalue: ***"));
+    assert!(debug.contains("String(Some(\"me\"))"));
+    assert!(format!("{:#?}", parsed.args.get(arg!(--token))).contains("argvalue: ***,"));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_tracer() {
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let log = events.clone();
+    let parsed = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(-'p', --password), value!(string), "Password").sensitive())
+        .arg(arg!(-'v', --verbose), ArgValue::Flag, "Verbose")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(-'n', --num), value!(num), "Number")
+                .rest("FILES", "Files"),
+        )
+        .tracer(move |event| log.lock().unwrap().push(format!("{event:?}")))
+        .parse_from(mkargs(&[
+            "test-program",
+            "-vp",
+            "hunter2",
+            "run",
+            "--num=-5",
+            "file",
+        ]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--num)).num(), Some(-5));
+    let events = events.lock().unwrap();
+    assert!(!events.iter().any(|event| event.contains("hunter2")));
+    assert_eq!(events.iter().filter(|e| e.starts_with("Arg(")).count(), 3);
+    let events: Vec<&String> = events.iter().filter(|e| !e.starts_with("Arg(")).collect();
+    assert_eq!(
+        events,
+        [
+            "Subcommand { parent: \"test-program\", name: \"run\" }",
+            "Command(\"test-program\")",
+            "Token(\"-vp\")",
+            "Value { arg: Short('p'), token: \"***\" }",
+            "Command(\"run\")",
+            "Token(\"--num=-5\")",
+            "Value { arg: Long(\"num\"), token: \"-5\" }",
+            "Positional(\"file\")",
+        ]
+    );
+
+    // The tokens are pulled while the subcommands are resolved, not collected first.
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let (entered, pulled) = (log.clone(), log.clone());
+    Command::create("test-program", "This is a test program")
+        .subcommand(Command::create("run", "Runs").rest("FILES", "Files"))
+        .tracer(move |event| {
+            if let TraceEvent::Subcommand { name, .. } = event {
+                entered.lock().unwrap().push(format!("entered {name}"));
+            }
+        })
+        .parse_from_iter(["test-program", "run", "file"].into_iter().map(|token| {
+            pulled.lock().unwrap().push(token.to_string());
+            token.to_string()
+        }))
+        .unwrap();
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["test-program", "run", "entered run", "file"]
+    );
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_generated_inputs() {
+    for seed in 0..500 {
+        let mut gen = Generator::new(seed);
+        let cmd = gen.command();
+        let input = gen.input(&cmd);
+        let Ok(parsed) = cmd.parse_from(input.argv.clone()) else {
+            panic!("Seed {seed}: {:?} was not parsed", input.argv);
+        };
+        assert_eq!(parsed.rest, input.rest, "seed {seed}");
+        let lists = parsed.ancestors.iter().chain([&parsed.args]);
+        for (args, uses) in lists.zip(&input.uses) {
+            for (argname, uses) in uses {
+                assert_eq!(args.count(argname.clone()), *uses, "seed {seed}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_to_argv() {
+    let parsed = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(-'g', --global), value!(num), "Global").global(true))
+        .arg(arg!(-'q'), ArgValue::Flag, "Quiet")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(--tag), value!(list), "Tags")
+                .arg(arg!(--key), ArgValue::Bytes(None), "Key")
+                .arg(arg!(--ratio), value!(float), "Ratio")
+                .arg(arg!(--name), value!(string, "default"), "Name")
+                .rest("FILES", "Files"),
+        )
+        .parse_from(mkargs(&[
+            "test-program",
+            "-qq",
+            "-g",
+            "1",
+            "run",
+            "--tag=a",
+            "--tag",
+            "b c",
+            "--key",
+            "aGkh",
+            "--ratio",
+            "-0.5",
+            "-g2",
+            "file",
+        ]))
+        .unwrap();
+    let argv = parsed.to_argv();
+    assert_eq!(
+        argv,
+        [
+            "-q",
+            "-q",
+            "run",
+            "--tag",
+            "a",
+            "--tag",
+            "b c",
+            "--key",
+            "hex:686921",
+            "--ratio",
+            "-0.5",
+            "--global",
+            "2",
+            "file"
+        ]
+    );
+
+    let mut again = mkargs(&["test-program"]);
+    again.extend(argv.clone());
+    let reparsed = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(-'g', --global), value!(num), "Global").global(true))
+        .arg(arg!(-'q'), ArgValue::Flag, "Quiet")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(--tag), value!(list), "Tags")
+                .arg(arg!(--key), ArgValue::Bytes(None), "Key")
+                .arg(arg!(--ratio), value!(float), "Ratio")
+                .arg(arg!(--name), value!(string, "default"), "Name")
+                .rest("FILES", "Files"),
+        )
+        .parse_from(again)
+        .unwrap();
+    assert_eq!(reparsed.to_argv(), argv);
+    assert_eq!(reparsed.args.get(arg!(--name)).string(), Some("default"));
+    assert_eq!(
+        reparsed.args.get(arg!(--key)).bytes_raw(),
+        Some(&b"hi!"[..])
+    );
+
+    let line: Vec<String> = argv.iter().map(|token| quote(token)).collect();
+    assert_eq!(split(&line.join(" ")).unwrap(), argv);
+}
+
+#[test]
+fn test_overridden() {
+    env::set_var("TEST_OVERRIDDEN_OPTS", "--host example.com --verbose");
+    let names = |args: &ArgList| -> Vec<String> {
+        args.overridden().map(|a| a.argname.to_string()).collect()
+    };
+
+    let parsed = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(-'g', --global), value!(num, 1), "Global").global(true))
+        .arg(arg!(--host), value!(string, "localhost"), "Host")
+        .arg(arg!(--verbose), ArgValue::Flag, "Verbose")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(--host), value!(string, "localhost"), "Host")
+                .arg(arg!(--port), value!(num, 8080), "Port")
+                .arg(arg!(--verbose), ArgValue::Flag, "Verbose")
+                .args_env("TEST_OVERRIDDEN_OPTS"),
+        )
+        .args_env("TEST_OVERRIDDEN_OPTS")
+        .parse_from(mkargs(&[
+            "test-program",
+            "-g",
+            "2",
+            "run",
+            "--port",
+            "8080",
+        ]))
+        .unwrap();
+    assert_eq!(names(&parsed.args), ["--port", "-g, --global"]);
+    assert_eq!(names(&parsed.ancestors[0]), ["-g, --global"]);
+    assert_eq!(parsed.args.get(arg!(--host)).string(), Some("example.com"));
+    let global = parsed.args.get(arg!(--global));
+    assert_eq!(global.default_value(), &ArgValue::Num(Some(1)));
+    assert_eq!(global.num(), Some(2));
+
+    let parsed = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(-'g', --global), value!(num, 1), "Global").global(true))
+        .arg(arg!(--host), value!(string, "localhost"), "Host")
+        .arg(arg!(--verbose), ArgValue::Flag, "Verbose")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(--host), value!(string, "localhost"), "Host")
+                .arg(arg!(--port), value!(num, 8080), "Port")
+                .arg(arg!(--verbose), ArgValue::Flag, "Verbose")
+                .args_env("TEST_OVERRIDDEN_OPTS"),
+        )
+        .args_env("TEST_OVERRIDDEN_OPTS")
+        .parse_from(mkargs(&["test-program", "--host", "me"]))
+        .unwrap();
+    assert_eq!(names(&parsed.args), ["--host"]);
+    assert_eq!(parsed.args.get(arg!(--host)).string(), Some("me"));
+
+    env::set_var("TEST_OVERRIDDEN_OPTS", "--host");
+    let err = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(-'g', --global), value!(num, 1), "Global").global(true))
+        .arg(arg!(--host), value!(string, "localhost"), "Host")
+        .arg(arg!(--verbose), ArgValue::Flag, "Verbose")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(--host), value!(string, "localhost"), "Host")
+                .arg(arg!(--port), value!(num, 8080), "Port")
+                .arg(arg!(--verbose), ArgValue::Flag, "Verbose")
+                .args_env("TEST_OVERRIDDEN_OPTS"),
+        )
+        .args_env("TEST_OVERRIDDEN_OPTS")
+        .parse_from(mkargs(&["test-program", "run"]));
+    assert_eq!(
+        err.err().unwrap().kind(),
+        &ErrorKind::InvalidEnvArgs("TEST_OVERRIDDEN_OPTS".into())
+    );
+}
+
+#[test]
+fn test_value_source() {
+    env::set_var("TEST_VALUE_SOURCE_OPTS", "-v --num 1 -g 2");
+    let parsed = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(-'g', --global), value!(num, 0), "Global").global(true))
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(-'v'), ArgValue::Count(0), "Verbose")
+                .arg(arg!(--num), value!(num), "Number")
+                .arg(arg!(--name), value!(string), "Name"),
+        )
+        .args_env("TEST_VALUE_SOURCE_OPTS")
+        .parse_from(mkargs(&[
+            "test-program",
+            "-g",
+            "1",
+            "run",
+            "-v",
+            "--name",
+            "me",
+        ]))
+        .unwrap();
+    let source = |argname| parsed.args.get(argname).source();
+    assert_eq!(source(arg!(-'v')), ValueSource::CommandLine);
+    assert_eq!(
+        source(arg!(--num)),
+        ValueSource::Env("TEST_VALUE_SOURCE_OPTS")
+    );
+    assert_eq!(source(arg!(--name)), ValueSource::CommandLine);
+    assert_eq!(
+        source(arg!(--global)),
+        ValueSource::Env("TEST_VALUE_SOURCE_OPTS")
+    );
+    assert_eq!(parsed.args.get(arg!(--global)).num(), Some(2));
+    assert_eq!(
+        parsed.ancestors[0].get(arg!(--global)).source(),
+        ValueSource::CommandLine
+    );
+    assert_eq!(parsed.args.get(arg!(-'v')).count_value(), Some(2));
+}
+
+#[test]
+fn test_validator() {
+    for (input, reason) in [
+        (
+            &["test-program", "--min", "5", "run", "--max", "6"][..],
+            None,
+        ),
+        (&["test-program", "--min", "5"], None),
+        (
+            &["test-program", "--min", "-1", "run", "--max", "6"],
+            Some("'--min' cannot be negative"),
+        ),
+    ] {
+        let result = Command::create("test-program", "This is a test program")
+            .arg(arg!(--min), value!(num, 0), "Minimum")
+            .validator(
+                |parsed| match parsed.ancestors.first().unwrap_or(&parsed.args) {
+                    args if args.get(arg!(--min)).num() < Some(0) => {
+                        Err("'--min' cannot be negative".into())
+                    }
+                    _ => Ok(()),
+                },
+            )
+            .subcommand(
+                Command::create("run", "Runs")
+                    .arg(arg!(--max), value!(num, 10), "Maximum")
+                    .validator(|parsed| {
+                        let min = parsed.ancestors[0].get(arg!(--min)).num();
+                        if parsed.args.get(arg!(--max)).num() < min {
+                            return Err("'--max' must not be lower than '--min'".into());
+                        }
+                        Ok(())
+                    }),
+            )
+            .parse_from(mkargs(input));
+        assert_eq!(
+            result.err().map(|err| err.kind().clone()),
+            reason.map(|reason| ErrorKind::Validation(reason.into()))
+        );
+    }
+
+    let err = Command::create("test-program", "This is a test program")
+        .arg(arg!(--min), value!(num, 0), "Minimum")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(--max), value!(num, 10), "Maximum")
+                .validator(|parsed| {
+                    let min = parsed.ancestors[0].get(arg!(--min)).num();
+                    if parsed.args.get(arg!(--max)).num() < min {
+                        return Err("'--max' must not be lower than '--min'".into());
+                    }
+                    Ok(())
+                }),
+        )
+        .color(false)
+        .parse_from(mkargs(&["test-program", "--min", "5", "run", "--max", "4"]))
+        .err()
+        .unwrap();
+    assert_eq!(err.command(), "test-program run");
+    assert_eq!(
+        err.render(false),
+        "error: '--max' must not be lower than '--min'.\n\nUSAGE:\n\ttest-program run [--max <MAX>]\n\n\
+         For more information, try 'test-program help run'."
+    );
+}
+
+#[test]
+fn test_collect_errors() {
+    let cmd = |collect| {
+        Command::create("test-program", "This is a test program")
+            .arg(arg!(-'q'), ArgValue::Flag, "Quiet")
+            .subcommand(
+                Command::create("run", "Runs")
+                    .add_arg(
+                        Arg::new(arg!(--num), value!(num), "Number")
+                            .duplicates(DuplicatePolicy::Error),
+                    )
+                    .arg(arg!(--format), value!(choice ["json", "yaml"]), "Format")
+                    .rest("FILES", "Files")
+                    .rest_min(1),
+            )
+            .collect_errors(collect)
+            .color(false)
+    };
+    let argv = mkargs(&[
+        "test-program",
+        "-x",
+        "run",
+        "--num",
+        "1",
+        "--num=2",
+        "--format",
+        "xml",
+        "---x",
+    ]);
+    let err = cmd(false).parse_from(argv.clone()).err().unwrap();
+    assert_eq!(err.errors().count(), 1);
+    assert_eq!(err.kind(), &ErrorKind::UnknownArg(arg!(-'x')));
+
+    let err = cmd(true).parse_from(argv).err().unwrap();
+    assert_eq!(
+        err.errors().cloned().collect::<Vec<_>>(),
+        [
+            ErrorKind::UnknownArg(arg!(-'x')),
+            ErrorKind::Duplicate(arg!(--num)),
+            ErrorKind::InvalidValue {
+                arg: arg!(--format),
+                value: "xml".into(),
+                reason: "one of: json, yaml".into()
+            },
+            ErrorKind::TooManyDashes("---x".into()),
+            ErrorKind::TooFewValues {
+                name: "FILES",
+                min: 1
+            },
+        ]
+    );
+    assert_eq!(err.command(), "test-program run");
+    assert_eq!(err.to_string().lines().count(), 5);
+
+    let err = Command::create("test-program", "This is a test program")
+        .subcommand(Command::create("run", "Runs"))
+        .subcommand_required(true)
+        .collect_errors(true)
+        .parse_from(mkargs(&["test-program"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.errors().cloned().collect::<Vec<_>>(),
+        [ErrorKind::MissingSubcommand(vec!["run"])]
+    );
+}
+
+#[test]
+fn test_parse_known() {
+    let (parsed, leftovers) = Command::create("test-program", "This is a test program")
+        .arg(arg!(-'v'), ArgValue::Count(0), "Verbose")
+        .arg(arg!(--num), value!(num), "Number")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(-'q'), ArgValue::Flag, "Quiet")
+                .rest("FILES", "Files"),
+        )
+        .parse_known_from(mkargs(&[
+            "test-program",
+            "-v",
+            "--color=auto",
+            "--num",
+            "-5",
+            "-vx",
+            "run",
+            "-q",
+            "--jobs",
+            "4",
+            "--",
+            "-qv",
+        ]))
+        .unwrap();
+    assert!(parsed.is(["run"]));
+    assert_eq!(parsed.ancestors[0].get(arg!(-'v')).count_value(), Some(1));
+    assert_eq!(parsed.ancestors[0].get(arg!(--num)).num(), Some(-5));
+    assert_eq!(parsed.args.count(arg!(-'q')), 1);
+    assert_eq!(parsed.rest, ["4", "-qv"]);
+    assert_eq!(leftovers, ["--color=auto", "-vx", "--jobs"]);
+
+    let (parsed, leftovers) = Command::create("test-program", "This is a test program")
+        .arg(arg!(-'v'), ArgValue::Count(0), "Verbose")
+        .arg(arg!(--num), value!(num), "Number")
+        .subcommand(
+            Command::create("run", "Runs")
+                .arg(arg!(-'q'), ArgValue::Flag, "Quiet")
+                .rest("FILES", "Files"),
+        )
+        .parse_known_from(mkargs(&["test-program", "build", "--release"]))
+        .unwrap();
+    assert!(parsed.is([]));
+    assert_eq!(leftovers, ["build", "--release"]);
+
+    // Without positional values, `--` is kept with the tokens it ends.
+    let (parsed, leftovers) = Command::create("test-program", "This is a test program")
+        .arg(arg!(-'v'), ArgValue::Count(0), "Verbose")
+        .parse_known_from(mkargs(&["test-program", "-v", "--", "-v", "x"]))
+        .unwrap();
+    assert_eq!(parsed.args.count(arg!(-'v')), 1);
+    assert_eq!(leftovers, ["--", "-v", "x"]);
+
+    assert!(Command::create("test-program", "This is a test program")
+        .arg(arg!(--num), value!(num), "Number")
+        .parse_known_from(mkargs(&["test-program", "--num", "x"]))
+        .is_err());
+}
+
+#[test]
+fn test_value_hints() {
+    let cmd = Command::create("test-program", "This is a test program")
+        .add_arg(Arg::new(arg!(--config), value!(path), "Config"))
+        .add_arg(Arg::new(arg!(--data), value!(path), "Data").value_hint(ValueHint::DirPath))
+        .add_arg(Arg::new(arg!(--host), value!(string), "Host").value_hint(ValueHint::Hostname))
+        .add_arg(Arg::new(arg!(--user), value!(string), "User"))
+        .add_arg(Arg::new(arg!(--who), value!(string), "Who").value_hint(ValueHint::Username))
+        .add_arg(Arg::new(arg!(--run), value!(string), "Run").value_hint(ValueHint::CommandName))
+        .add_arg(Arg::new(arg!(--out), value!(path), "Out").value_hint(ValueHint::Other))
+        .add_arg(Arg::new(arg!(-'q'), ArgValue::Flag, "Quiet").value_hint(ValueHint::FilePath));
+
+    let bash = generate(Shell::Bash, &cmd);
+    assert!(
+        bash.contains("\"--config\")\n                    COMPREPLY=($(compgen -f -- \"${cur}\"))")
+    );
+    assert!(
+        bash.contains("\"--data\")\n                    COMPREPLY=($(compgen -d -- \"${cur}\"))")
+    );
+    assert!(bash.contains("compgen -A hostname"));
+    assert!(bash.contains("\"--user\")\n                    COMPREPLY=()"));
+    assert!(bash.contains("\"--out\")\n                    COMPREPLY=()"));
+    assert!(!bash.contains("\"-q\")"));
+
+    let zsh = generate(Shell::Zsh, &cmd);
+    assert!(zsh.contains("'--data=[Data]:value:_files -/'"));
+    assert!(zsh.contains("'--who=[Who]:value:_users'"));
+    assert!(zsh.contains("'--run=[Run]:value:_command_names -e'"));
+    assert!(zsh.contains("'--user=[User]:value:'"));
+
+    let fish = generate(Shell::Fish, &cmd);
+    assert!(fish.contains("-l 'host' -r -f -a '(__fish_print_hostnames)'"));
+    assert!(fish.contains("-l 'config' -r -F"));
+}
+
+#[test]
+fn test_try_accessors() {
+    let mut parsed = Command::create("test", "Tests the non-panicking accessors")
+        .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity")
+        .arg(arg!(--name), value!(string, "nobody"), "Insert a name")
+        .parse_from(mkargs(&["test-program", "-vv"]))
+        .unwrap();
+    assert_eq!(parsed.args.try_count(arg!(-'v', --verbose)), Some(2));
+    assert_eq!(parsed.args.try_count(arg!(--name)), Some(0));
+    assert_eq!(parsed.args.try_count(arg!(--missing)), None);
+    assert!(parsed.args.try_get(arg!(--missing)).is_none());
+    assert_eq!(
+        parsed.args.try_set(arg!(--missing), value!(string, "x")),
+        Err("Argument '--missing' does not exist".into())
+    );
+    assert!(parsed.args.try_set(arg!(--name), value!(num, 1)).is_err());
+    assert_eq!(parsed.args.string_or(arg!(--name), ""), "nobody");
+    parsed
+        .args
+        .try_set(arg!(--name), value!(string, "somebody"))
+        .unwrap();
+    assert_eq!(parsed.args.string_or(arg!(--name), ""), "somebody");
+}
+
+#[test]
+fn test_parse_from_iter() {
+    let argv = ["test-program", "-vv", "sub", "-n5", "a", "b"];
+    let parsed = Command::create("test", "Tests parsing from an iterator")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .arg(arg!(-'n', --num), value!(num), "Insert a number")
+                .rest("FILES", "Some files"),
+        )
+        .parse_from_iter(argv.iter().map(|token| token.to_string()))
+        .unwrap();
+    assert_eq!(parsed.args.num_or(arg!(-'n', --num), 0), 5);
+    assert_eq!(parsed.ancestors[0].try_count(arg!(-'v')), Some(2));
+    assert_eq!(parsed.rest, ["a", "b"]);
+    assert_eq!(
+        parsed.raw,
+        Command::create("test", "Tests parsing from an iterator")
+            .arg(arg!(-'v'), value!(count), "Increases verbosity")
+            .subcommand(
+                Command::create("sub", "A subcommand")
+                    .arg(arg!(-'n', --num), value!(num), "Insert a number")
+                    .rest("FILES", "Some files"),
+            )
+            .parse_from(mkargs(&argv))
+            .unwrap()
+            .raw
+    );
+}
+
+static TABLE: &[(ArgName, ArgValue, &str)] = &[
+    (arg!(-'v', --verbose), value!(count), "Increases verbosity"),
+    (arg!(--num), value!(num, 42), "Insert a number"),
+    (arg!(-'p', --"log.path"), value!(path), "Insert a path"),
+    (arg!(--level), value!(num in 1..=3), "Insert a level"),
+];
+
+#[test]
+fn test_arg_table() {
+    const HELP: ArgName = arg!(-'h', --help);
+    assert_eq!(HELP, ArgName::both('h', "help"));
+    let parsed = Command::create("test", "Tests static tables of arguments")
+        .arg_table(TABLE)
+        .arg(arg!(--name), value!(string), "Insert a name")
+        .parse_from(mkargs(&["test-program", "-vv", "--level", "2", "-p/tmp"]))
+        .unwrap();
+    assert_eq!(parsed.args.try_count(arg!(--verbose)), Some(2));
+    assert_eq!(parsed.args.num_or(arg!(--num), 0), 42);
+    assert_eq!(
+        parsed.args.try_get(arg!(--level)).unwrap().argvalue,
+        ArgValue::Range(1..=3, Some(2))
+    );
+    assert_eq!(
+        parsed.args.path_or(arg!(-'p'), Path::new("")),
+        Path::new("/tmp")
+    );
+    assert_eq!(TABLE[0].0, arg!(-'v'));
+}
+
+args! {
+    mod cli {
+        VERBOSE: [-'v', --verbose] => [count], "Increases verbosity" { global: true },
+        NAME: [--name] => [string, "nobody"], "Insert a name",
+        LEVEL: [-'l'] => [num in 1..=3], "Insert a level" { value_name: "LEVEL" },
+    }
+}
+
+#[test]
+fn test_arg_constants() {
+    let parsed = cli::register(Command::create("test", "Tests constants of arguments"))
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test-program", "-l2", "sub", "-vvv"]))
+        .unwrap();
+    assert_eq!(parsed.args.try_count(cli::VERBOSE), Some(3));
+    assert!(parsed.args.try_get(cli::NAME).is_none());
+    assert_eq!(parsed.ancestors[0].try_count(cli::LEVEL), Some(1));
+    assert_eq!(
+        parsed.ancestors[0].try_get(cli::LEVEL).unwrap().value_name,
+        Some("LEVEL")
+    );
+    let parsed = cli::register(Command::create("test", "Tests constants of arguments"))
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert_eq!(parsed.args.string_or(cli::NAME, ""), "nobody");
+    assert_eq!(cli::VERBOSE, arg!(-'v'));
+}
+
+#[test]
+fn test_plugins() {
+    let dir = env::temp_dir().join(format!("tiny-args-plugins-{}", std::process::id()));
+    let other = dir.join("other");
+    std::fs::create_dir_all(&other).unwrap();
+    for (dir, name) in [
+        (&dir, "tinycloud-backup"),
+        (&dir, "tinycloud-sync"),
+        (&dir, "tinycloud-"),
+        (&dir, "tinycloud--x"),
+        (&dir, "other-tool"),
+        (&other, "tinycloud-backup"),
+        (&other, "tinycloud-user"),
+    ] {
+        let path = dir.join(name);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        #[cfg(windows)]
+        std::fs::rename(&path, path.with_extension("exe")).unwrap();
+    }
+    let dirs = env::join_paths([&dir, &other]).unwrap();
+    let plugins = Plugin::discover_in("tinycloud-", &dirs);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let names: Vec<&str> = plugins.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["backup", "sync", "user"]);
+    assert!(plugins[0].path.starts_with(&dir) && !plugins[0].path.starts_with(&other));
+
+    let parsed = Command::create("tinycloud", "Tests plugins")
+        .color(false)
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .subcommand(Command::create("user", "Manages users"))
+        .plugins(plugins.clone())
+        .parse_from(mkargs(&["tinycloud", "-v", "sync", "-v", "--now", "help"]))
+        .unwrap();
+    assert_eq!(
+        parsed.plugin.as_ref().map(|p| p.name.as_str()),
+        Some("sync")
+    );
+    assert_eq!(parsed.rest, ["-v", "--now", "help"]);
+    assert_eq!(parsed.args.try_count(arg!(-'v')), Some(1));
+    assert_eq!(parsed.to_argv(), ["-v", "sync", "-v", "--now", "help"]);
+    let parsed = Command::create("tinycloud", "Tests plugins")
+        .color(false)
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .subcommand(Command::create("user", "Manages users"))
+        .plugins(plugins.clone())
+        .parse_from(mkargs(&["tinycloud", "user"]))
+        .unwrap();
+    assert!(parsed.plugin.is_none() && parsed.is(["user"]));
+    let help = Command::create("tinycloud", "Tests plugins")
+        .color(false)
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .subcommand(Command::create("user", "Manages users"))
+        .plugins(plugins)
+        .parse_from(mkargs(&["tinycloud"]))
+        .unwrap()
+        .help;
+    assert!(help.contains("\tbackup  Runs an external command.\n"));
+    assert_eq!(help.matches("\tuser ").count(), 1);
+}
+
+#[test]
+fn test_env_prefix() {
+    env::set_var("TEST_ENV_PREFIX_OPTS", "");
+    env::set_var("TEST_ENV_PREFIX_LOG_LEVEL", "debug");
+    env::set_var("TEST_ENV_PREFIX_DATA_DIR", "/srv");
+    env::set_var("TEST_ENV_PREFIX_DATADIR", "/srv");
+    env::set_var("TEST_ENV_PREFIX_V", "1");
+    let parsed = Command::create("test", "Tests unknown environment variables")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .arg(arg!(-'l', --"log.level"), value!(string), "Insert a level")
+        .args_env("TEST_ENV_PREFIX_OPTS")
+        .subcommand(Command::create("sub", "A subcommand").arg(
+            arg! { --data-dir },
+            value!(path),
+            "Insert a directory",
+        ))
+        .env_prefix("TEST_ENV_PREFIX_")
+        .parse_from(mkargs(&["test-program", "sub"]))
+        .unwrap();
+    assert_eq!(
+        parsed.warnings,
+        [
+            "'TEST_ENV_PREFIX_DATADIR' is not a known environment variable",
+            "'TEST_ENV_PREFIX_V' is not a known environment variable",
+        ]
+    );
+    let parsed = Command::create("test", "Tests unknown environment variables")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .arg(arg!(-'l', --"log.level"), value!(string), "Insert a level")
+        .args_env("TEST_ENV_PREFIX_OPTS")
+        .subcommand(Command::create("sub", "A subcommand").arg(
+            arg! { --data-dir },
+            value!(path),
+            "Insert a directory",
+        ))
+        .env_prefix("TEST_ENV_PREFIX_")
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert_eq!(parsed.warnings.len(), 3);
+    let parsed = Command::create("test", "Tests unknown environment variables")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .arg(arg!(-'l', --"log.level"), value!(string), "Insert a level")
+        .args_env("TEST_ENV_PREFIX_OPTS")
+        .subcommand(Command::create("sub", "A subcommand").arg(
+            arg! { --data-dir },
+            value!(path),
+            "Insert a directory",
+        ))
+        .parse_from(mkargs(&["test-program"]))
+        .unwrap();
+    assert!(parsed.warnings.is_empty());
+}
+
+#[test]
+fn test_posix_strict() {
+    let cmd = |posix| {
+        Command::create("test", "Tests POSIX-style parsing")
+            .arg(arg!(-'v'), value!(count), "Increases verbosity")
+            .arg(arg!(-'n', --num), value!(num), "Insert a number")
+            .rest("ARGS", "Some values")
+            .posix_strict(posix)
+    };
+    let input = ["test-program", "-v", "-n", "5", "run", "-v", "--num=6", "x"];
+    let parsed = cmd(true).parse_from(mkargs(&input)).unwrap();
+    assert_eq!(parsed.args.try_count(arg!(-'v')), Some(1));
+    assert_eq!(parsed.args.num_or(arg!(--num), 0), 5);
+    assert_eq!(parsed.rest, ["run", "-v", "--num=6", "x"]);
+    let parsed = cmd(false).parse_from(mkargs(&input)).unwrap();
+    assert_eq!(parsed.args.try_count(arg!(-'v')), Some(2));
+    assert_eq!(parsed.rest, ["run", "x"]);
+
+    let (parsed, leftovers) = Command::create("test", "Tests POSIX-style leftovers")
+        .arg(arg!(-'v'), value!(count), "Increases verbosity")
+        .posix_strict(true)
+        .parse_known_from(mkargs(&["test-program", "-x", "-v", "cmd", "-v"]))
+        .unwrap();
+    assert_eq!(parsed.args.try_count(arg!(-'v')), Some(1));
+    assert_eq!(leftovers, ["-x", "cmd", "-v"]);
+}
+
+#[test]
+fn test_negatable() {
+    let parsed = Command::create("test", "Tests negatable flags")
+        .add_arg(Arg::new(arg!(-'c', --color), value!(), "Colors the output").negatable())
+        .parse_from(mkargs(&["test", "-c", "--no-color"]))
+        .unwrap();
+    let color = parsed.args.try_get(arg!(--color)).unwrap();
+    assert_eq!((color.enabled(), color.counter), (Some(false), 0));
+    assert_eq!(color.source(), ValueSource::CommandLine);
+    assert_eq!(parsed.to_argv(), ["--no-color"]);
+    let parsed = Command::create("test", "Tests negatable flags")
+        .add_arg(Arg::new(arg!(-'c', --color), value!(), "Colors the output").negatable())
+        .parse_from(mkargs(&["test", "--no-color", "--color"]))
+        .unwrap();
+    assert!(parsed.args.enabled_or(arg!(--color), false));
+    assert_eq!(parsed.args.try_count(arg!(--color)), Some(1));
+    // A global flag negated before a subcommand is negated in the subcommand too.
+    let parsed = Command::create("test", "Tests negatable flags")
+        .add_arg(
+            Arg::new(arg!(--color), value!(), "Colors the output")
+                .negatable()
+                .global(true),
+        )
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test", "--no-color", "sub"]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--color)).unwrap().enabled(),
+        Some(false)
+    );
+    assert!(!parsed.args.enabled_or(arg!(--color), true));
+    let parsed = Command::create("test", "Tests negatable flags")
+        .color(false)
+        .add_arg(Arg::new(arg!(-'c', --color), value!(), "Colors the output").negatable())
+        .add_arg(
+            Arg::new(arg!(--cache), value!(), "Uses the cache")
+                .negatable()
+                .duplicates(DuplicatePolicy::Error),
+        )
+        .arg(arg!(--verbose), value!(), "Prints more")
+        .collect_errors(true)
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert!(parsed.args.enabled_or(arg!(--color), true));
+    assert!(!parsed.args.enabled_or(arg!(--cache), false));
+    assert!(parsed
+        .help
+        .contains("\t-c, --[no-]color  Colors the output\n"));
+    assert!(parsed
+        .help
+        .contains("test [--[no-]color] [--[no-]cache] [--verbose]\n"));
+
+    let argv = [
+        "test",
+        "--no-verbose",
+        "--no-color=yes",
+        "--cache",
+        "--no-cache",
+    ];
+    let err = Command::create("test", "Tests negatable flags")
+        .add_arg(Arg::new(arg!(-'c', --color), value!(), "Colors the output").negatable())
+        .add_arg(
+            Arg::new(arg!(--cache), value!(), "Uses the cache")
+                .negatable()
+                .duplicates(DuplicatePolicy::Error),
+        )
+        .arg(arg!(--verbose), value!(), "Prints more")
+        .collect_errors(true)
+        .parse_from(mkargs(&argv))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.errors().cloned().collect::<Vec<_>>(),
+        [
+            ErrorKind::UnknownArg(arg!(--"no-verbose")),
+            ErrorKind::InvalidValue {
+                arg: arg!(--color),
+                value: "yes".into(),
+                reason: "omitted".into()
+            },
+            ErrorKind::Duplicate(arg!(--cache)),
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_negatable_fail() {
+    Arg::new(arg!(--num), value!(num), "Insert a number").negatable();
+}
+
+#[test]
+fn test_num_values() {
+    let parsed = Command::create("test", "Tests arguments with more values")
+        .add_arg(Arg::new(arg!(-'m', --map), value!(list), "Maps a path").num_values(2..=2))
+        .add_arg(
+            Arg::new(arg!(--points), value!(list, ["0", "0"]), "Some points")
+                .num_values(2..=4)
+                .duplicates(DuplicatePolicy::Overwrite),
+        )
+        .rest("FILES", "Some files")
+        .parse_from(mkargs(&[
+            "test", "--map", "a", "b", "-m=c", "d", "e", "--points", "1", "2", "3",
+        ]))
+        .unwrap();
+    let map = parsed.args.try_get(arg!(--map)).unwrap();
+    assert_eq!(map.groups(), [["a", "b"], ["c", "d"]]);
+    assert_eq!(parsed.rest, ["e"]);
+    let points = parsed.args.try_get(arg!(--points)).unwrap();
+    assert_eq!(points.groups(), [["1", "2", "3"]]);
+    assert_eq!(
+        parsed.to_argv(),
+        ["--map", "a", "b", "--map", "c", "d", "--points", "1", "2", "3", "e"]
+    );
+
+    // The values are not taken as subcommands and end at the next argument.
+    let parsed = Command::create("test", "Tests arguments with more values")
+        .color(false)
+        .add_arg(Arg::new(arg!(-'m', --map), value!(list), "Maps a path").num_values(2..=2))
+        .add_arg(
+            Arg::new(arg!(--points), value!(list, ["0", "0"]), "Some points")
+                .num_values(2..=4)
+                .duplicates(DuplicatePolicy::Overwrite),
+        )
+        .add_arg(
+            Arg::new(arg!(--keys), value!(list), "Some keys")
+                .num_values(1..=2)
+                .sensitive(),
+        )
+        .rest("FILES", "Some files")
+        .subcommand(Command::create("b", "A subcommand"))
+        .parse_from(mkargs(&[
+            "test", "--map=a", "b", "--keys", "x", "y", "--points", "1", "2", "b",
+        ]))
+        .unwrap();
+    assert_eq!(parsed.name, "test");
+    let points = parsed.args.try_get(arg!(--points)).unwrap();
+    assert_eq!(points.groups(), [["1", "2", "b"]]);
+    assert_eq!(parsed.raw[2..5], ["--keys", "***", "***"]);
+    let parsed = Command::create("test", "Tests arguments with more values")
+        .color(false)
+        .add_arg(Arg::new(arg!(-'m', --map), value!(list), "Maps a path").num_values(2..=2))
+        .add_arg(
+            Arg::new(arg!(--points), value!(list, ["0", "0"]), "Some points")
+                .num_values(2..=4)
+                .duplicates(DuplicatePolicy::Overwrite),
+        )
+        .add_arg(
+            Arg::new(arg!(--keys), value!(list), "Some keys")
+                .num_values(1..=2)
+                .sensitive(),
+        )
+        .rest("FILES", "Some files")
+        .subcommand(Command::create("b", "A subcommand"))
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--points)).unwrap().groups(),
+        [["0", "0"]]
+    );
+    assert!(parsed
+        .args
+        .try_get(arg!(--map))
+        .unwrap()
+        .groups()
+        .is_empty());
+    assert!(parsed
+        .help
+        .contains("[--map <MAP> <MAP>]... [--points <POINTS> <POINTS> [<POINTS>]...]"));
+
+    let err = Command::create("test", "Tests arguments with more values")
+        .add_arg(Arg::new(arg!(-'m', --map), value!(list), "Maps a path").num_values(2..=2))
+        .parse_from(mkargs(&["test", "--map", "a", "-m", "c"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::InvalidValue {
+            arg: arg!(--map),
+            value: "a".into(),
+            reason: "exactly 2 values".into()
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_num_values_fail() {
+    Arg::new(arg!(--num), value!(num), "Insert a number").num_values(2..=2);
+}
+
+#[test]
+fn test_merge_values() {
+    let cmd = |merge| {
+        Command::create("test", "Tests merged values")
+            .color(false)
+            .add_arg(
+                Arg::new(arg!(-'t', --tag), value!(list, ["default"]), "Some tags")
+                    .delimiter(',')
+                    .merge(merge),
+            )
+            .add_arg(
+                Arg::new(arg!(--path), value!(paths), "Some paths")
+                    .split_paths(true)
+                    .merge(merge),
+            )
+    };
+    let input = [
+        "test",
+        "--tag",
+        "a,b",
+        "-t=b",
+        "--tag=c,a",
+        "--path",
+        "/x:/y",
+        "--path",
+        "/x",
+    ];
+    let parsed = cmd(MergePolicy::Append).parse_from(mkargs(&input)).unwrap();
+    let tag = parsed.args.try_get(arg!(--tag)).unwrap();
+    assert_eq!(tag.list().unwrap(), ["a", "b", "b", "c", "a"]);
+    assert_eq!(
+        parsed
+            .args
+            .try_get(arg!(--path))
+            .unwrap()
+            .paths()
+            .unwrap()
+            .len(),
+        3
+    );
+    assert!(parsed.help.contains("[--tag <TAG>[,<TAG>]...]..."));
+
+    let input = [
+        "test", "--tag", "a,b,a", "--tag", "b,c", "--path", "/x:/y", "--path", "/x",
+    ];
+    let parsed = cmd(MergePolicy::Dedupe).parse_from(mkargs(&input)).unwrap();
+    let tag = parsed.args.try_get(arg!(--tag)).unwrap();
+    assert_eq!(tag.list().unwrap(), ["a", "b", "c"]);
+    assert_eq!(
+        parsed.args.try_get(arg!(--path)).unwrap().paths().unwrap(),
+        ["/x", "/y"].map(PathBuf::from)
+    );
+    assert_eq!(
+        parsed.to_argv(),
+        ["--tag", "a", "--tag", "b", "--tag", "c", "--path", "/x", "--path", "/y"]
+    );
+
+    let err = cmd(MergePolicy::Error)
+        .parse_from(mkargs(&input))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::InvalidValue {
+            arg: arg!(-'t', --tag),
+            value: "a".into(),
+            reason: "a value not given yet".into()
+        }
+    );
+    let parsed = cmd(MergePolicy::Error)
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--tag)).unwrap().list().unwrap(),
+        ["default"]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_merge_values_fail() {
+    Arg::new(arg!(--map), value!(list), "Maps a path")
+        .num_values(2..=2)
+        .delimiter(',');
+}
+
+#[test]
+fn test_render_help() {
+    let cmd = Command::create(
+        "test",
+        "Tests rendering the help page into a buffer at a given width",
+    )
+    .color(false)
+    .add_arg(
+        Arg::new(arg!(-'n', --name), value!(string), "Inserts a name").long_description(
+            "Inserts the name that is shown to the other users, unless they muted you",
+        ),
+    )
+    .arg(
+        arg!(--a - very - long - name),
+        value!(),
+        "Does something that takes a long time to describe",
+    );
+    let render = |options| {
+        let mut buf = String::new();
+        help::render(&cmd, &mut buf, options).unwrap();
+        buf
+    };
+    let mut buf = Vec::new();
+    cmd.print_help(&mut buf).unwrap();
+    assert_eq!(
+        format!("{}\n", render(HelpOptions::default())),
+        String::from_utf8(buf).unwrap()
+    );
+
+    let options = HelpOptions {
+        width: Some(50),
+        long: true,
+        ..HelpOptions::default()
+    };
+    let page = render(options);
+    assert!(page.contains("\t-n, --name          Inserts the name that\n\t                    is shown to the other\n"));
+    assert!(page.contains(
+        "\t--a-very-long-name  Does something that\n\t                    takes a long time to\n"
+    ));
+    for line in page.lines() {
+        let columns = line.chars().fold(0, |col, c| match c {
+            '\t' => (col / 8 + 1) * 8,
+            _ => col + 1,
+        });
+        assert!(columns <= 50 || !line.trim().contains(' '), "{line:?}");
+    }
+
+    #[cfg(feature = "color")]
+    {
+        let colored = render(HelpOptions {
+            color: true,
+            ..options
+        });
+        assert!(colored.contains('\x1b'));
+        assert!(colored
+            .contains("Inserts the name that\n\t                    is shown to the other\n"));
+    }
+}
+
+#[test]
+fn test_plain_help() {
+    let cmd = Command::create("test", "Tests the plain help page")
+        .color(true)
+        .arg(
+            arg!(--name),
+            value!(string),
+            "Inserts the name that is shown to the other users of the service, unless they muted you",
+        );
+    let page = help::plain(&cmd);
+    assert!(!page.contains('\x1b'));
+    assert!(page.contains(
+        "\t--name  Inserts the name that is shown to the other users of the\n\t        service, unless they muted you\n"
+    ));
+    assert_eq!(page, help::plain(&cmd));
+}
+
+#[test]
+fn test_available_subcommands() {
+    let parsed = Command::create("test", "Tests the available subcommands")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user"))
+                .subcommand(Command::create("remove", "Removes a user")),
+        )
+        .subcommand(Command::create("sync", "Syncs the files"))
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert_eq!(
+        parsed.available_subcommands(),
+        [("user", "Manages users"), ("sync", "Syncs the files")]
+    );
+    let parsed = Command::create("test", "Tests the available subcommands")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user"))
+                .subcommand(Command::create("remove", "Removes a user")),
+        )
+        .parse_from(mkargs(&["test", "user"]))
+        .unwrap();
+    assert_eq!(
+        parsed.available_subcommands(),
+        [("add", "Adds a user"), ("remove", "Removes a user")]
+    );
+    let parsed = Command::create("test", "Tests the available subcommands")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user")),
+        )
+        .parse_from(mkargs(&["test", "user", "add"]))
+        .unwrap();
+    assert!(parsed.available_subcommands().is_empty());
+}
+
+#[cfg(feature = "semver")]
+#[test]
+fn test_versions() {
+    let version = |text: &str| text.parse::<Version>().unwrap();
+    assert_eq!(
+        version("1.2.3-beta.2+build.5"),
+        Version::new(1, 2, 3).pre("beta.2").build("build.5")
+    );
+    for text in [
+        "1.2",
+        "1.2.3.4",
+        "01.2.3",
+        "1.2.3-",
+        "1.2.3-a..b",
+        "1.2.x",
+        "",
+    ] {
+        assert!(text.parse::<Version>().is_err(), "{text}");
+    }
+    let mut versions = [
+        "1.0.0",
+        "1.0.0-rc.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta.11",
+        "1.0.0-alpha",
+        "1.0.0-beta",
+        "1.0.0-alpha.1",
+        "1.0.0-beta.2",
+        "0.9.9",
+        "2.0.0",
+    ]
+    .map(version);
+    versions.sort();
+    assert_eq!(
+        versions.map(|v| v.to_string()),
+        [
+            "0.9.9",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "2.0.0",
+        ]
+    );
+
+    let matches =
+        |req: &str, text: &str| req.parse::<VersionReq>().unwrap().matches(&version(text));
+    for (req, yes, no) in [
+        ("^1.2.3", ["1.2.3", "1.9.0"], ["1.2.2", "2.0.0"]),
+        ("1.2", ["1.2.0", "1.3.1"], ["1.1.9", "2.0.0"]),
+        ("^0.2.3", ["0.2.3", "0.2.9"], ["0.3.0", "0.2.2"]),
+        ("^0.0.3", ["0.0.3", "0.0.3+build"], ["0.0.4", "0.0.2"]),
+        ("~1.2.3", ["1.2.3", "1.2.9"], ["1.3.0", "1.2.2"]),
+        ("=1.2", ["1.2.0", "1.2.7"], ["1.3.0", "1.1.0"]),
+        (">1.2", ["1.3.0", "2.0.0"], ["1.2.9", "1.0.0"]),
+        ("<=1.2", ["1.2.9", "0.1.0"], ["1.3.0", "2.0.0"]),
+        (">=1.2, <2", ["1.2.0", "1.9.9"], ["2.0.0", "1.1.0"]),
+        (
+            ">=1.2.3-beta",
+            ["1.2.3-rc", "1.2.3"],
+            ["1.2.4-rc", "1.2.3-alpha"],
+        ),
+        ("*", ["0.0.1", "9.9.9"], ["1.0.0-rc", "0.1.0-alpha"]),
+    ] {
+        for text in yes {
+            assert!(matches(req, text), "{req} {text}");
+        }
+        for text in no {
+            assert!(!matches(req, text), "{req} {text}");
+        }
+    }
+    for text in ["", ">=", "1.2-beta", "^1.2.3.4", ">=1.2,", "1.x"] {
+        assert!(text.parse::<VersionReq>().is_err(), "{text}");
+    }
+
+    let parsed = Command::create("test", "Tests versions")
+        .color(false)
+        .arg(
+            arg!(--target),
+            ArgValue::Other(Box::new(Some(Version::new(1, 0, 0)))),
+            "A version",
+        )
+        .arg(
+            arg!(--requires),
+            ArgValue::Other(Box::new(None::<VersionReq>)),
+            "A requirement",
+        )
+        .parse_from(mkargs(&["test", "--requires", "~1.2"]))
+        .unwrap();
+    let target = parsed.args.try_get(arg!(--target)).unwrap();
+    assert_eq!(target.version(), Some(&Version::new(1, 0, 0)));
+    let requires = parsed.args.try_get(arg!(--requires)).unwrap();
+    assert_eq!(requires.version_req().unwrap().to_string(), "~1.2");
+    assert!(parsed.help.contains("[default: 1.0.0]"));
+    let err = Command::create("test", "Tests versions")
+        .arg(
+            arg!(--target),
+            ArgValue::Other(Box::new(Some(Version::new(1, 0, 0)))),
+            "A version",
+        )
+        .parse_from(mkargs(&["test", "--target", "1.2"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::InvalidValue {
+            arg: arg!(--target),
+            value: "1.2".into(),
+            reason: "a version like 1.2.3".into()
+        }
+    );
+}
+
+#[cfg(feature = "semver")]
+#[test]
+#[should_panic]
+fn test_incompatible_plugin() {
+    let plugin = Plugin {
+        name: "backup".into(),
+        path: "/usr/bin/test-backup".into(),
+    };
+    Command::create("test", "Tests incompatible plugins")
+        .version("2.0.0")
+        .compatible_plugin(plugin, &"^1.2".parse().unwrap());
+}
+
+#[test]
+fn test_command_metadata() {
+    let help = Command::create("test", "Tests the metadata of commands")
+        .color(false)
+        .homepage("https://example.com")
+        .repository("https://example.com/repo")
+        .license("GPL-3.0")
+        .example("test sub --all", "Does everything")
+        .example("test sub", "")
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .repository("https://example.com/sub")
+                .example("test sub --all", "Does everything"),
+        )
+        .subcommand(Command::create("other", "Another subcommand"))
+        .parse_from(mkargs(&["test"]))
+        .unwrap()
+        .help;
+    assert!(help.ends_with(
+        "EXAMPLES:\n\ttest sub --all\n\t\tDoes everything\n\ttest sub\n\nHomepage: https://example.com\nRepository: https://example.com/repo\nLicensed under GPL-3.0"
+    ));
+    let help = Command::create("test", "Tests the metadata of commands")
+        .color(false)
+        .homepage("https://example.com")
+        .repository("https://example.com/repo")
+        .subcommand(Command::create("sub", "A subcommand").repository("https://example.com/sub"))
+        .parse_from(mkargs(&["test", "sub"]))
+        .unwrap()
+        .help;
+    assert!(help.contains("Homepage: https://example.com\nRepository: https://example.com/sub\n"));
+    let help = Command::create("test", "Tests the metadata of commands")
+        .color(false)
+        .repository("https://example.com/repo")
+        .example("test sub", "")
+        .subcommand(Command::create("other", "Another subcommand"))
+        .parse_from(mkargs(&["test", "other"]))
+        .unwrap()
+        .help;
+    assert!(!help.contains("EXAMPLES:"));
+    assert!(help.contains("Repository: https://example.com/repo\n"));
+}
+
+#[test]
+fn test_arg_example() {
+    let help = Command::create("test", "Tests the examples of arguments")
+        .color(false)
+        .add_arg(
+            Arg::new(arg!(-'t', --timeout), value!(string, "30s"), "A timeout")
+                .example("--timeout 1m30s"),
+        )
+        .add_arg(
+            Arg::new(
+                arg!(--a - very - long - name),
+                value!(string),
+                "A long name",
+            )
+            .example("--a-very-long-name \x1b[31mx"),
+        )
+        .arg(arg!(--other), value!(), "Another argument")
+        .parse_from(mkargs(&["test"]))
+        .unwrap()
+        .help;
+    assert!(help.contains(
+        "\t-t, --timeout       A timeout [default: 30s]\n\t                    example: --timeout 1m30s\n\t--a-very-long-name  A long name\n\t                    example: --a-very-long-name \\u{1b}[31mx\n\t--other             Another argument\n"
+    ));
+}
+
+#[test]
+fn test_expect_accessors() {
+    let parsed = Command::create("test", "Tests the checked accessors")
+        .arg(arg!(--name), value!(string), "A name")
+        .arg(arg!(--port), value!(num in 1..=65535, 8080), "A port")
+        .arg(arg!(--ratio), value!(float), "A ratio")
+        .arg(arg!(--tags), value!(list), "Some tags")
+        .arg(arg!(-'v'), value!(count), "Verbosity")
+        .arg(arg!(--path), value!(path exists), "A path")
+        .parse_from(mkargs(&["test", "--name", "me", "-vv", "--tags", "a"]))
+        .unwrap();
+    let arg = |name| parsed.args.try_get(name).unwrap();
+    assert_eq!(arg(arg!(--name)).value_kind(), ValueKind::String);
+    assert_eq!(arg(arg!(--name)).expect_string(), Ok(Some("me")));
+    assert_eq!(arg(arg!(--port)).expect_num(), Ok(Some(8080)));
+    assert_eq!(arg(arg!(--ratio)).expect_float(), Ok(None));
+    assert_eq!(arg(arg!(--tags)).expect_list(), Ok(&["a".to_string()][..]));
+    assert_eq!(arg(arg!(-'v')).expect_count(), Ok(2));
+    assert_eq!(arg(arg!(--path)).expect_path(), Ok(None));
+    assert_eq!(
+        arg(arg!(--port)).expect_string(),
+        Err(WrongKind {
+            arg: arg!(--port),
+            expected: &[ValueKind::String, ValueKind::Choice],
+            found: ValueKind::Range
+        })
+    );
+    assert_eq!(arg(arg!(--ratio)).num(), None);
+    assert!(arg(arg!(--ratio)).expect_num().is_err());
+    assert!(arg(arg!(--tags)).expect_paths().is_err());
+    assert!(arg(arg!(-'v')).expect_bytes().is_err());
+}
+
+#[test]
+fn test_overwritten_values() {
+    let parsed = Command::create("test", "Tests the overwritten values")
+        .arg(arg!(--path), value!(path), "A path")
+        .arg(arg!(--tags), value!(list), "Some tags")
+        .parse_from(mkargs(&[
+            "test", "--path", "/a", "--tags", "x", "--tags", "y",
+        ]))
+        .unwrap();
+    assert!(parsed.overwritten_values().is_empty());
+
+    let parsed = Command::create("test", "Tests the overwritten values")
+        .add_arg(Arg::new(arg!(--path), value!(path), "A path").global(true))
+        .add_arg(Arg::new(arg!(--token), value!(string), "A token").sensitive())
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&[
+            "test", "--token", "s1", "--path", "/a", "--token", "s2", "sub", "--path", "/b",
+            "--path", "/c",
+        ]))
+        .unwrap();
+    assert_eq!(
+        parsed.overwritten_values(),
+        [
+            (arg!(--token), vec!["***".to_string()]),
+            (arg!(--path), vec!["/a".to_string(), "/b".to_string()]),
+        ]
+    );
+    assert_eq!(
+        parsed.args.try_get(arg!(--path)).unwrap().path(),
+        Some(&"/c".into())
+    );
+}
+
+#[test]
+fn test_name_conflicts() {
+    let parsed = Command::create("test", "Tests the name conflicts")
+        .arg(arg!(-'o', --out), value!(path), "An output")
+        .subcommand(Command::create("a", "A subcommand").arg(arg!(--output), value!(), "A flag"))
+        .subcommand(Command::create("b", "A subcommand").arg(arg!(-'o', --out), value!(), "A flag"))
+        .parse_from(mkargs(&["test", "b", "-o"]))
+        .unwrap();
+    assert_eq!(parsed.args.try_count(arg!(--out)), Some(1));
+    assert!(arg!(-'o', --out).conflicts(&arg!(-'o', --output)));
+    assert!(arg!(-'o', --out).conflicts(&arg!(-'O', --out)));
+    assert!(!arg!(-'o', --out).conflicts(&arg!(-'o')));
+}
+
+#[test]
+#[should_panic(expected = "share only one name")]
+fn test_name_conflicts_fail() {
+    let _ = Command::create("test", "Tests the name conflicts")
+        .add_arg(Arg::new(arg!(-'o', --out), value!(path), "An output").global(true))
+        .subcommand(Command::create("sub", "A subcommand").arg(
+            arg!(-'o', --output),
+            value!(path),
+            "Another output",
+        ));
+}
+
+#[test]
+fn test_help_providers() {
+    let backup = Command::create("backup", "Backup arguments")
+        .provider("Backup plugin")
+        .arg(arg!(--snapshot), value!(), "Takes a snapshot first")
+        .subcommand(Command::create("restore", "Restores a backup"));
+    let cmd = Command::create("test", "Tests the providers")
+        .arg(arg!(--config), value!(path), "Uses another configuration")
+        .add_arg(Arg::new(arg!(--sync), value!(), "Syncs the files").provider("Sync plugin"))
+        .subcommand(Command::create("serve", "Starts the server"))
+        .extend(backup);
+    let page = help::plain(&cmd);
+    assert!(page.contains(
+        "ARGS:\n\tCore:\n\t--config    Uses another configuration\n\n\tSync plugin:\n\t--sync      Syncs the files\n\n\tBackup plugin:\n\t--snapshot  Takes a snapshot first\n"
+    ));
+    assert!(page.contains(
+        "SUBCOMMANDS:\n\tCore:\n\tserve       Starts the server\n\thelp        Shows the help of the given subcommand.\n\n\tBackup plugin:\n\trestore     Restores a backup\n\n"
+    ));
+    let plain = help::plain(&Command::create("test", "Tests the providers").arg(
+        arg!(--config),
+        value!(path),
+        "Uses another configuration",
+    ));
+    assert!(!plain.contains("Core"));
+}
+
+#[test]
+fn test_verbosity() {
+    let parsed = Command::create("test", "Tests the verbosity")
+        .verbosity_flags()
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert_eq!(parsed.verbosity(), 0);
+    let parsed = Command::create("test", "Tests the verbosity")
+        .verbosity_flags()
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test", "-qq"]))
+        .unwrap();
+    assert_eq!(parsed.verbosity(), -2);
+    let parsed = Command::create("test", "Tests the verbosity")
+        .verbosity_flags()
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test", "-v", "sub", "--verbose", "-vq"]))
+        .unwrap();
+    assert_eq!(parsed.verbosity(), 2);
+
+    let parsed = Command::create("test", "Tests the verbosity")
+        .arg(arg!(-'q', --query), value!(string), "A query")
+        .verbosity_flags()
+        .parse_from(mkargs(&["test", "-v", "-q", "x"]))
+        .unwrap();
+    assert_eq!(parsed.verbosity(), 0);
+    assert!(parsed.args.try_get(arg!(--quiet)).is_none());
+    let parsed = Command::create("test", "Tests the verbosity")
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert!(parsed.args.try_get(arg!(--verbose)).is_none());
+}
+
+#[test]
+fn test_to_json() {
+    env::set_var("TINY_ARGS_TEST_JSON", "--ratio inf");
+    let parsed = Command::create("test", "Tests the JSON output")
+        .args_env("TINY_ARGS_TEST_JSON")
+        .add_arg(Arg::new(arg!(-'d'), value!(), "A global flag").global(true))
+        .add_arg(Arg::new(arg!(--token), value!(string), "A token").sensitive())
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .arg(arg!(--name), value!(string), "A name")
+                .arg(arg!(--ratio), value!(float), "A ratio")
+                .arg(arg!(--tags), value!(list), "Some tags")
+                .arg(arg!(--key), ArgValue::Bytes(None), "A key")
+                .arg(arg!(--path), value!(path), "A path"),
+        )
+        .parse_from(mkargs(&[
+            "test", "-d", "--token", "secret", "sub", "--name", "\"me\"\n", "--tags", "a",
+            "--tags", "b", "--key", "hex:6869",
+        ]))
+        .unwrap();
+    assert_eq!(
+        parsed.to_json(),
+        concat!(
+            r#"{"command":"sub","path":["sub"],"args":{"#,
+            r#""token":{"value":"***","source":"command_line"},"#,
+            r#""name":{"value":"\"me\"\n","source":"command_line"},"#,
+            r#""ratio":{"value":"inf","source":"env","env":"TINY_ARGS_TEST_JSON"},"#,
+            r#""tags":{"value":["a","b"],"source":"command_line"},"#,
+            r#""key":{"value":"hex:6869","source":"command_line"},"#,
+            r#""path":{"value":null,"source":"default"},"#,
+            r#""d":{"value":true,"source":"command_line"}},"#,
+            r#""rest":[],"plugin":null}"#
+        )
+    );
+}
+
+#[test]
+fn test_attached_short_values() {
+    let parsed = Command::create("test", "Tests the values attached to short arguments")
+        .arg(arg!(-'n'), value!(num), "A number")
+        .arg(arg!(-'s'), value!(string), "A string")
+        .add_arg(Arg::new(arg!(-'k'), value!(string), "A key").sensitive())
+        .arg(arg!(-'v'), value!(count), "Verbosity")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test", "-n=5", "-vs==x", "-k=secret", "sub"]))
+        .unwrap();
+    assert_eq!(
+        parsed.ancestors[0].try_get(arg!(-'n')).unwrap().num(),
+        Some(5)
+    );
+    assert_eq!(
+        parsed.ancestors[0].try_get(arg!(-'s')).unwrap().string(),
+        Some("=x")
+    );
+    assert_eq!(
+        parsed.ancestors[0].try_get(arg!(-'k')).unwrap().string(),
+        Some("secret")
+    );
+    assert_eq!(parsed.raw, ["-n=5", "-vs==x", "-k=***", "sub"]);
+    let parsed = Command::create("test", "Tests the values attached to short arguments")
+        .arg(arg!(-'s'), value!(string), "A string")
+        .parse_from(mkargs(&["test", "-s="]))
+        .unwrap();
+    assert_eq!(parsed.args.try_get(arg!(-'s')).unwrap().string(), Some(""));
+    let err = Command::create("test", "Tests the values attached to short arguments")
+        .arg(arg!(-'n'), value!(num), "A number")
+        .parse_from(mkargs(&["test", "-n=five"]))
+        .err()
+        .unwrap();
+    assert!(matches!(err.kind(), ErrorKind::InvalidValue { value, .. } if value == "five"));
+}
+
+#[test]
+fn test_missing_value() {
+    let missing = |input: &[&str], argname: ArgName| {
+        let err = Command::create("test", "Tests the missing values")
+            .arg(arg!(-'n', --num), value!(num), "A number")
+            .arg(arg!(--name), value!(string), "A name")
+            .arg(arg!(-'v'), value!(count), "Verbosity")
+            .subcommand(Command::create("sub", "A subcommand"))
+            .parse_from(mkargs(input))
+            .err()
+            .unwrap();
+        assert_eq!(*err.kind(), ErrorKind::MissingValue(argname), "{input:?}");
+    };
+    missing(&["test", "--num"], arg!(--num));
+    missing(&["test", "-vn"], arg!(-'n'));
+    missing(&["test", "--num", "--"], arg!(--num));
+    missing(&["test", "--name", "sub"], arg!(--name));
+    let err = Command::create("test", "Tests the missing values")
+        .arg(arg!(-'n', --num), value!(num), "A number")
+        .parse_from(mkargs(&["test", "--num"]))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "missing value for '-n, --num'.");
+    let parsed = Command::create("test", "Tests the missing values")
+        .arg(arg!(-'n', --num), value!(num), "A number")
+        .arg(arg!(--name), value!(string), "A name")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .parse_from(mkargs(&["test", "--name=--", "-n", "-1", "sub"]))
+        .unwrap();
+    assert_eq!(
+        parsed.ancestors[0].try_get(arg!(--name)).unwrap().string(),
+        Some("--")
+    );
+    let err = Command::create("test", "Tests the missing values")
+        .arg(arg!(-'n', --num), value!(num), "A number")
+        .arg(arg!(--name), value!(string), "A name")
+        .collect_errors(true)
+        .parse_from(mkargs(&["test", "--name", "--", "--num"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::MissingValue(arg!(--name)));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_mutated_inputs() {
+    for seed in 0..500 {
+        let mut gen = Generator::new(seed);
+        let cmd = gen.command();
+        let mut argv = gen.input(&cmd).argv;
+        gen.mutate(&mut argv);
+        let _ = cmd.parse_from(argv);
+    }
+}
+
+#[test]
+fn test_dashes() {
+    let error = |input: &[&str]| {
+        Command::create("test", "Tests the dashes")
+            .arg(arg!(-'x', --x), ArgValue::Flag, "A flag")
+            .arg(arg!(--name), value!(string), "A name")
+            .color(false)
+            .parse_from(mkargs(input))
+            .err()
+            .unwrap()
+    };
+    let err = error(&["test", "---x"]);
+    assert_eq!(*err.kind(), ErrorKind::TooManyDashes("---x".into()));
+    assert_eq!(
+        err.to_string(),
+        "'---x' starts with too many dashes: use '-' or '--'."
+    );
+    let err = error(&["test", "-"]);
+    assert_eq!(*err.kind(), ErrorKind::LoneDash);
+    assert_eq!(
+        err.to_string(),
+        "'-' can only be given as the value of an argument."
+    );
+    let err = error(&["test", "-x", "--"]);
+    assert_eq!(*err.kind(), ErrorKind::TrailingDoubleDash);
+    assert_eq!(err.to_string(), "'--' is not followed by anything.");
+    let parsed = Command::create("test", "Tests the dashes")
+        .arg(arg!(--name), value!(string), "A name")
+        .parse_from(mkargs(&["test", "--name", "-"]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--name)).unwrap().string(),
+        Some("-")
+    );
+    let parsed = Command::create("test", "Tests the dashes")
+        .arg(arg!(-'x', --x), ArgValue::Flag, "A flag")
+        .rest("FILES", "Files")
+        .parse_from(mkargs(&["test", "-", "-x"]))
+        .unwrap();
+    assert_eq!(parsed.rest, ["-"]);
+}
+
+#[test]
+fn test_parsed_metadata() {
+    let parsed = Command::create("test", "Tests the metadata")
+        .version("1.2.3")
+        .author("Someone")
+        .license("GPL-3.0")
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .version("2.0.0")
+                .license("MIT"),
+        )
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert_eq!(parsed.description, "Tests the metadata");
+    assert_eq!(parsed.version, Some("1.2.3"));
+    assert_eq!(parsed.author, Some("Someone"));
+    assert_eq!(parsed.license, Some("GPL-3.0"));
+    let parsed = Command::create("test", "Tests the metadata")
+        .version("1.2.3")
+        .author("Someone")
+        .license("GPL-3.0")
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .version("2.0.0")
+                .license("MIT"),
+        )
+        .parse_from(mkargs(&["test", "sub"]))
+        .unwrap();
+    assert_eq!(parsed.description, "A subcommand");
+    assert_eq!(parsed.version, Some("2.0.0"));
+    assert_eq!(parsed.author, Some("Someone"));
+    assert_eq!(parsed.license, Some("MIT"));
+    let parsed = Command::create("bare", "No metadata")
+        .parse_from(mkargs(&["bare"]))
+        .unwrap();
+    assert_eq!(
+        (parsed.version, parsed.author, parsed.license),
+        (None, None, None)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_utf8_policy() {
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+    let cmd = |policy| {
+        Command::create("test", "Tests the UTF-8 policies")
+            .arg(arg!(--file), value!(path), "A file")
+            .arg(
+                arg!(-'i', --include),
+                ArgValue::PathList(vec![]),
+                "Included files",
+            )
+            .arg(arg!(--name), value!(string), "A name")
+            .utf8_policy(policy)
+    };
+    let invalid = |prefix: &str| {
+        let mut bytes = prefix.as_bytes().to_vec();
+        bytes.extend(b"caf\xe9");
+        OsString::from_vec(bytes)
+    };
+    let args = |tokens: Vec<OsString>| iter::once("test".into()).chain(tokens);
+
+    let err = cmd(Utf8Policy::Strict)
+        .parse_from_os(args(vec!["--file".into(), invalid("")]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::NonUtf8Arg("caf\u{FFFD}".into()));
+    assert_eq!(err.to_string(), "'caf\u{FFFD}' is not valid UTF-8.");
+
+    let parsed = cmd(Utf8Policy::Lossy)
+        .parse_from_os(args(vec![invalid("--name=")]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--name)).unwrap().string(),
+        Some("caf\u{FFFD}")
+    );
+
+    let parsed = cmd(Utf8Policy::Paths)
+        .parse_from_os(args(vec![
+            invalid("--file="),
+            invalid("-i"),
+            "-i".into(),
+            invalid("lib/"),
+        ]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--file)).unwrap().path().unwrap(),
+        &invalid("")
+    );
+    assert_eq!(
+        parsed
+            .args
+            .try_get(arg!(--include))
+            .unwrap()
+            .paths()
+            .unwrap(),
+        [PathBuf::from(invalid("")), PathBuf::from(invalid("lib/"))]
+    );
+    let err = cmd(Utf8Policy::Paths)
+        .parse_from_os(args(vec!["--name".into(), invalid("")]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::NonUtf8Arg("caf\u{FFFD}".into()));
+}
+
+#[test]
+fn test_placement() {
+    let parsed = Command::create("test", "Tests the placements")
+        .add_arg(
+            Arg::new(arg!(--config), value!(path), "A configuration file")
+                .global(true)
+                .placement(Placement::Before),
+        )
+        .add_arg(
+            Arg::new(arg!(-'d', --dry), ArgValue::Flag, "Dry run")
+                .global(true)
+                .placement(Placement::After),
+        )
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user")),
+        )
+        .color(false)
+        .parse_from(mkargs(&[
+            "test", "--config", "a.toml", "user", "-d", "add", "--dry",
+        ]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--config)).unwrap().path(),
+        Some(&"a.toml".into())
+    );
+    assert_eq!(parsed.args.try_count(arg!(--dry)), Some(2));
+    let err = Command::create("test", "Tests the placements")
+        .add_arg(
+            Arg::new(arg!(--config), value!(path), "A configuration file")
+                .global(true)
+                .placement(Placement::Before),
+        )
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user")),
+        )
+        .parse_from(mkargs(&["test", "user", "add", "--config", "a.toml"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::MisplacedArg {
+            arg: arg!(--config),
+            placement: Placement::Before
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "'--config' must be given before the subcommand."
+    );
+    let err = Command::create("test", "Tests the placements")
+        .add_arg(
+            Arg::new(arg!(--config), value!(path), "A configuration file")
+                .global(true)
+                .placement(Placement::Before),
+        )
+        .add_arg(
+            Arg::new(arg!(-'d', --dry), ArgValue::Flag, "Dry run")
+                .global(true)
+                .placement(Placement::After),
+        )
+        .subcommand(Command::create("user", "Manages users"))
+        .collect_errors(true)
+        .parse_from(mkargs(&["test", "-d", "--config=a.toml", "user"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.errors().cloned().collect::<Vec<_>>(),
+        [ErrorKind::MisplacedArg {
+            arg: arg!(-'d', --dry),
+            placement: Placement::After
+        }]
+    );
+    assert_eq!(
+        err.to_string(),
+        "'-d, --dry' must be given after the subcommand."
+    );
+}
+
+#[test]
+#[should_panic(expected = "can only be placed if it is global")]
+fn test_placement_fail() {
+    let _ = Command::create("test", "Tests the placements")
+        .add_arg(Arg::new(arg!(--config), value!(path), "A config").placement(Placement::Before))
+        .parse_from(mkargs(&["test"]));
+}
+
+#[test]
+fn test_incremental_parser() {
+    let cmd = Command::create("test", "Tests the incremental parser")
+        .arg(arg!(-'v', --verbose), value!(count), "Verbosity")
+        .add_arg(
+            Arg::new(arg!(--config), value!(path), "A configuration file")
+                .global(true)
+                .duplicates(DuplicatePolicy::Error),
+        )
+        .subcommand(
+            Command::create("user", "Manages users")
+                .arg(arg!(-'n', --num), value!(num), "A number")
+                .arg(arg!(--kind), value!(choice ["admin", "guest"]), "A kind")
+                .rest("NAMES", "Names"),
+        )
+        .plugins(vec![Plugin {
+            name: "ext".into(),
+            path: "/bin/ext".into(),
+        }]);
+    let mut parser = Parser::new(&cmd);
+    assert_eq!(parser.command().name, "test");
+    assert_eq!(
+        parser.candidates(),
+        ["user", "help", "ext", "--verbose", "--config"]
+    );
+    assert_eq!(parser.push("-x"), Err(ErrorKind::UnknownArg(arg!(-'x'))));
+    assert_eq!(
+        parser.push("other"),
+        Err(ErrorKind::UnknownSubcommand("other".into()))
+    );
+    parser.push("-vv").unwrap();
+    parser.push("--config").unwrap();
+    assert_eq!(parser.expecting(), Some(&arg!(--config)));
+    assert_eq!(
+        parser.push("user"),
+        Err(ErrorKind::MissingValue(arg!(--config)))
+    );
+    parser.push("app.toml").unwrap();
+    assert_eq!(parser.expecting(), None);
+    parser.push("user").unwrap();
+    assert_eq!(parser.path(), ["user"]);
+    assert_eq!(parser.candidates(), ["--num", "--kind"]);
+    assert_eq!(
+        parser.push("--config=b.toml"),
+        Err(ErrorKind::Duplicate(arg!(--config)))
+    );
+    assert!(matches!(
+        parser.push("-nx"),
+        Err(ErrorKind::InvalidValue { .. })
+    ));
+    parser.push("-n5").unwrap();
+    parser.push("--kind").unwrap();
+    assert_eq!(parser.candidates(), ["admin", "guest"]);
+    assert!(matches!(
+        parser.push("root"),
+        Err(ErrorKind::InvalidValue { .. })
+    ));
+    assert_eq!(parser.expecting(), Some(&arg!(--kind)));
+    parser.push("admin").unwrap();
+    parser.push("someone").unwrap();
+    assert_eq!(
+        parser.tokens(),
+        ["-vv", "--config", "app.toml", "user", "-n5", "--kind", "admin", "someone"]
+    );
+    let tokens = parser.tokens().to_vec();
+    let parsed = cmd
+        .parse_from_iter(iter::once("test".into()).chain(tokens))
+        .unwrap();
+    assert_eq!(parsed.rest, ["someone"]);
+
+    let cmd = Command::create("test", "Tests the plugins").plugins(vec![Plugin {
+        name: "ext".into(),
+        path: "/bin/ext".into(),
+    }]);
+    let mut parser = Parser::new(&cmd);
+    parser.push("ext").unwrap();
+    parser.push("--anything").unwrap();
+    assert!(parser.candidates().is_empty());
+}
+
+#[test]
+fn test_require_one_of() {
+    let parse = |input: &[&str]| {
+        Command::create("test", "Tests the required groups")
+            .arg(arg!(-'v'), value!(count), "Verbosity")
+            .arg(arg!(-'f', --file), value!(path), "A file")
+            .arg(arg!(--url), value!(string), "A URL")
+            .arg(arg!(--stdin), ArgValue::Flag, "Reads stdin")
+            .require_one_of([arg!(--file), arg!(--url), arg!(--stdin)])
+            .subcommand(
+                Command::create("sub", "A subcommand")
+                    .arg(arg!(--a), ArgValue::Flag, "A")
+                    .arg(arg!(--b), ArgValue::Flag, "B")
+                    .require_one_of([arg!(--a), arg!(--b)]),
+            )
+            .color(false)
+            .parse_from(mkargs(input))
+    };
+    for input in [
+        &["test", "-f", "x"][..],
+        &["test", "--stdin"],
+        &["test", "--url=u", "sub", "--b"],
+    ] {
+        assert!(parse(input).is_ok(), "{input:?}");
+    }
+    let err = parse(&["test", "-v"]).err().unwrap();
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::MissingOneOf(vec![arg!(--file), arg!(--url), arg!(--stdin)])
+    );
+    assert_eq!(
+        err.to_string(),
+        "one of '--file', '--url', '--stdin' is required."
+    );
+    assert!(err
+        .render(false)
+        .contains("\ttest [-v]... (--file <FILE> | --url <URL> | --stdin) [SUBCOMMAND]\n"));
+    let err = parse(&["test", "--stdin", "sub"]).err().unwrap();
+    assert_eq!(err.command(), "test sub");
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::MissingOneOf(vec![arg!(--a), arg!(--b)])
+    );
+    let err = Command::create("test", "Tests the required groups")
+        .arg(arg!(--url), value!(string), "A URL")
+        .arg(arg!(--stdin), ArgValue::Flag, "Reads stdin")
+        .require_one_of([arg!(--url), arg!(--stdin)])
+        .subcommand(
+            Command::create("sub", "A subcommand")
+                .arg(arg!(--a), ArgValue::Flag, "A")
+                .arg(arg!(--b), ArgValue::Flag, "B")
+                .require_one_of([arg!(--a), arg!(--b)]),
+        )
+        .collect_errors(true)
+        .parse_from(mkargs(&["test", "sub"]))
+        .err()
+        .unwrap();
+    assert_eq!(err.errors().count(), 2);
+}
+
+#[test]
+fn test_trim_and_empty_values() {
+    let parsed = Command::create("test", "Tests the empty values")
+        .add_arg(
+            Arg::new(arg!(--user), value!(string), "A username")
+                .trim(true)
+                .allow_empty(false),
+        )
+        .add_arg(Arg::new(arg!(-'n'), value!(num), "A number").trim(true))
+        .add_arg(
+            Arg::new(arg!(--tag), value!(list), "Tags")
+                .delimiter(',')
+                .trim(true)
+                .allow_empty(false),
+        )
+        .arg(arg!(--name), value!(string), "A name")
+        .parse_from(mkargs(&[
+            "test", "--user", " root\t", "-n", " 5 ", "--tag", "a, b", "--name", "",
+        ]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--user)).unwrap().string(),
+        Some("root")
+    );
+    assert_eq!(parsed.args.try_get(arg!(-'n')).unwrap().num(), Some(5));
+    assert_eq!(
+        parsed.args.try_get(arg!(--tag)).unwrap().list().unwrap(),
+        ["a", "b"]
+    );
+    assert_eq!(
+        parsed.args.try_get(arg!(--name)).unwrap().string(),
+        Some("")
+    );
+    for input in [
+        &["test", "--user", ""][..],
+        &["test", "--user=  "],
+        &["test", "--tag", "a,,b"],
+    ] {
+        let err = Command::create("test", "Tests the empty values")
+            .add_arg(
+                Arg::new(arg!(--user), value!(string), "A username")
+                    .trim(true)
+                    .allow_empty(false),
+            )
+            .add_arg(
+                Arg::new(arg!(--tag), value!(list), "Tags")
+                    .delimiter(',')
+                    .trim(true)
+                    .allow_empty(false),
+            )
+            .parse_from(mkargs(input))
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err.kind(), ErrorKind::InvalidValue { reason, .. } if reason == "non-empty"),
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn test_autocorrect() {
+    let cmd = |autocorrect| {
+        Command::create("test", "Tests the autocorrection")
+            .subcommand(
+                Command::create("status", "Shows the status")
+                    .subcommand(Command::create("remote", "Shows the remote status")),
+            )
+            .subcommand(Command::create("stash", "Stashes"))
+            .subcommand(Command::create("add", "Adds"))
+            .subcommand(Command::create("push", "Pushes"))
+            .subcommand(Command::create("pull", "Pulls"))
+            .autocorrect(autocorrect)
+    };
+    let parsed = cmd(Autocorrect::Run)
+        .parse_from(mkargs(&["test", "stauts", "remte"]))
+        .unwrap();
+    assert_eq!(parsed.path(), ["status", "remote"]);
+    assert_eq!(
+        parsed.warnings,
+        [
+            "'stauts' was corrected to 'status'",
+            "'remte' was corrected to 'remote'"
+        ]
+    );
+    // Too far from every subcommand, or as close to two of them.
+    for token in ["pusl", "xyz"] {
+        let err = cmd(Autocorrect::Run)
+            .parse_from(mkargs(&["test", token]))
+            .err()
+            .unwrap();
+        assert_eq!(*err.kind(), ErrorKind::UnknownSubcommand(token.into()));
+    }
+    let err = cmd(Autocorrect::Off)
+        .parse_from(mkargs(&["test", "stauts"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownSubcommand("stauts".into()));
+    // Nobody can answer the prompt without a terminal.
+    let err = cmd(Autocorrect::Prompt)
+        .parse_from(mkargs(&["test", "stauts"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnknownSubcommand("stauts".into()));
+}
+
+#[test]
+fn test_only_with() {
+    let parse = |input: &[&str]| {
+        Command::create("test", "Tests the arguments tied to subcommands")
+            .add_arg(
+                Arg::new(arg!(-'f', --follow), ArgValue::Flag, "Follow the output")
+                    .global(true)
+                    .only_with("logs"),
+            )
+            .add_arg(
+                Arg::new(arg!(--lines), value!(num), "Number of lines")
+                    .only_with("logs")
+                    .only_with("tail"),
+            )
+            .subcommand(Command::create("logs", "Prints the logs"))
+            .subcommand(Command::create("tail", "Prints the last lines"))
+            .subcommand(Command::create("status", "Prints the status"))
+            .collect_errors(true)
+            .color(false)
+            .parse_from(mkargs(input))
+    };
+    for input in [
+        &["test", "-f", "--lines", "5", "logs", "--follow"][..],
+        &["test", "--lines", "5", "tail"],
+        &["test", "status"],
+    ] {
+        assert!(parse(input).is_ok(), "{input:?}");
+    }
+    let err = parse(&["test", "-f", "--lines", "5", "status", "-f"])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.errors().cloned().collect::<Vec<_>>(),
+        [
+            ErrorKind::RequiresSubcommand {
+                arg: arg!(-'f', --follow),
+                subcommands: vec!["logs"]
+            },
+            ErrorKind::RequiresSubcommand {
+                arg: arg!(--lines),
+                subcommands: vec!["logs", "tail"]
+            }
+        ]
+    );
+    let err = parse(&["test", "--lines", "5", "status"]).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "'--lines' can only be used with the subcommands: logs, tail."
+    );
+}
+
+#[test]
+#[should_panic(expected = "which is not a subcommand of 'test'")]
+fn test_only_with_fail() {
+    let _ = Command::create("test", "Tests the arguments tied to subcommands")
+        .add_arg(Arg::new(arg!(--follow), ArgValue::Flag, "Follow the output").only_with("logs"))
+        .parse_from(mkargs(&["test"]));
+}
+
+#[test]
+fn test_terminal_info() {
+    let terminal = TerminalInfo {
+        width: Some(40),
+        color: true,
+        unicode: true,
+    };
+    let cmd = Command::create("test", "Tests the terminal information")
+        .arg(
+            arg!(--name),
+            value!(num),
+            "Inserts the name that is shown to the other users",
+        )
+        .terminal(terminal)
+        .color(false);
+    let mut buf = Vec::new();
+    cmd.print_help(&mut buf).unwrap();
+    let help = String::from_utf8(buf).unwrap();
+    assert!(
+        help.contains("\t--name  Inserts the name that is\n\t        shown to the other users\n")
+    );
+    assert!(!help.contains('\x1b'));
+    let err = cmd
+        .parse_from(mkargs(&["test", "--name", "x"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.terminal(),
+        TerminalInfo {
+            color: false,
+            ..terminal
+        }
+    );
+    let mut buf = Vec::new();
+    err.print(&mut buf).unwrap();
+    assert!(!String::from_utf8(buf).unwrap().contains('\x1b'));
+    let options = HelpOptions::from(terminal);
+    assert_eq!((options.width, options.color), (Some(40), true));
+    assert_eq!(TerminalInfo::detect(), TerminalInfo::detect());
+}
+
+#[test]
+fn test_take_values() {
+    let mut parsed = Command::create("test", "Tests taking the values")
+        .arg(arg!(--payload), value!(string), "A payload")
+        .arg(arg!(--tag), value!(list), "A tag")
+        .arg(arg!(-'v'), value!(count), "Verbosity")
+        .parse_from(mkargs(&[
+            "test",
+            "--payload",
+            "{\"a\": 1}",
+            "--tag",
+            "x",
+            "-vv",
+        ]))
+        .unwrap();
+    let ptr = parsed
+        .args
+        .try_get(arg!(--payload))
+        .and_then(Arg::string)
+        .unwrap()
+        .as_ptr();
+    match parsed.args.take(arg!(--payload)) {
+        Some(ArgValue::String(Some(payload))) => {
+            assert_eq!(payload, "{\"a\": 1}");
+            assert_eq!(payload.as_ptr(), ptr);
+        }
+        other => panic!("{other:?}"),
+    }
+    assert_eq!(parsed.args.take(arg!(--payload)), None);
+    assert_eq!(parsed.args.take(arg!(--other)), None);
+    assert_eq!(parsed.args.try_count(arg!(-'v')), Some(2));
+    let names: Vec<ArgName> = parsed.args.into_values().map(|(name, _)| name).collect();
+    assert_eq!(names, [arg!(--tag), arg!(-'v')]);
+}
+
+#[test]
+fn test_arglist_filters() {
+    let parsed = Command::create("test", "Tests the filters of the argument list")
+        .arg(arg!(-'d', --db - debug), value!(), "Debugs the database")
+        .arg(arg!(--db - url), value!(string), "The database URL")
+        .arg(arg!(-'v'), value!(count), "Verbosity")
+        .arg(arg!(--dbx), value!(list), "Not a database argument")
+        .parse_from(mkargs(&["test", "-dvv", "--db-url", "x"]))
+        .unwrap();
+    let names = |args: Vec<&Arg>| -> Vec<ArgName> {
+        args.into_iter().map(|arg| arg.argname.clone()).collect()
+    };
+    assert_eq!(
+        names(parsed.args.flags().collect()),
+        [arg!(-'d', --db - debug), arg!(-'v')]
+    );
+    assert_eq!(
+        names(parsed.args.valued().collect()),
+        [arg!(--db - url), arg!(--dbx)]
+    );
+    assert_eq!(
+        names(parsed.args.matching("db-").collect()),
+        [arg!(-'d', --db - debug), arg!(--db - url)]
+    );
+    assert_eq!(parsed.args.matching("db").count(), 3);
+    assert_eq!(parsed.args.matching("").count(), 3);
+    assert_eq!(
+        names(
+            parsed
+                .args
+                .matching("db-")
+                .filter(|a| a.counter > 0)
+                .collect()
+        ),
+        [arg!(-'d', --db - debug), arg!(--db - url)]
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid() {
+    let id = "urn:uuid:F81D4FAE-7DEC-11D0-A765-00A0C91E6BF6"
+        .parse::<Uuid>()
+        .unwrap();
+    assert_eq!(id.to_string(), "f81d4fae-7dec-11d0-a765-00a0c91e6bf6");
+    assert_eq!(id.version(), 1);
+    assert_eq!(id.as_bytes()[..2], [0xf8, 0x1d]);
+    assert_eq!(Uuid::from_bytes(*id.as_bytes()), id);
+    assert_eq!(
+        "00000000-0000-0000-0000-000000000000".parse::<Uuid>(),
+        Ok(Uuid::NIL)
+    );
+    for text in [
+        "",
+        "f81d4fae7dec11d0a76500a0c91e6bf6",
+        "f81d4fae-7dec-11d0-a765-00a0c91e6bf",
+        "f81d4fae-7dec-11d0-a765-00a0c91e6bf6a",
+        "f81d4fae-7dec-11d0a765-00a0c91e6bf6",
+        "f81d4fae-7dec-11d0-a765-00a0c91e6bg6",
+        "{f81d4fae-7dec-11d0-a765-00a0c91e6bf6}",
+        "f81d4fae-7dec-11d0-a765-00a0c91e6bé",
+    ] {
+        assert!(text.parse::<Uuid>().is_err(), "{text}");
+    }
+    let parsed = Command::create("test", "Tests the UUIDs")
+        .arg(
+            arg!(--id),
+            ArgValue::Other(Box::new(Some(Uuid::NIL))),
+            "A resource",
+        )
+        .color(false)
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    assert_eq!(
+        parsed.args.try_get(arg!(--id)).unwrap().uuid(),
+        Some(Uuid::NIL)
+    );
+    let parsed = Command::create("test", "Tests the UUIDs")
+        .arg(
+            arg!(--id),
+            ArgValue::Other(Box::new(Some(Uuid::NIL))),
+            "A resource",
+        )
+        .color(false)
+        .parse_from(mkargs(&[
+            "test",
+            "--id",
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+        ]))
+        .unwrap();
+    assert_eq!(
+        parsed
+            .args
+            .try_get(arg!(--id))
+            .unwrap()
+            .uuid()
+            .unwrap()
+            .version(),
+        4
+    );
+    let err = Command::create("test", "Tests the UUIDs")
+        .arg(
+            arg!(--id),
+            ArgValue::Other(Box::new(Some(Uuid::NIL))),
+            "A resource",
+        )
+        .color(false)
+        .parse_from(mkargs(&["test", "--id", "42"]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "'--id' value's must be a UUID like 67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+}
+
+#[test]
+fn test_ratio() {
+    let parse = |input: &[&str]| {
+        Command::create("test", "Tests the ratios")
+            .arg(arg!(--quota), value!(ratio, 0.25), "A quota")
+            .color(false)
+            .parse_from(mkargs(input))
+    };
+    for (input, expected) in [
+        ("75%", 0.75),
+        ("0.75", 0.75),
+        ("100%", 1.0),
+        ("0%", 0.0),
+        ("1", 1.0),
+        ("12.5%", 0.125),
+    ] {
+        let parsed = parse(&["test", "--quota", input]).unwrap();
+        assert_eq!(
+            parsed.args.try_get(arg!(--quota)).unwrap().ratio(),
+            Some(expected),
+            "{input}"
+        );
+    }
+    let invalid = |input: &str| match parse(&["test", "--quota", input]).err().unwrap().kind() {
+        ErrorKind::InvalidValue { reason, .. } => reason.clone(),
+        kind => panic!("{kind:?}"),
+    };
+    assert_eq!(invalid("101%"), "between 0% and 100%");
+    assert_eq!(invalid("-0.1"), "between 0% and 100%");
+    assert_eq!(invalid("1.5"), "between 0% and 100%");
+    assert_eq!(
+        invalid("half"),
+        "a percentage like 75% or a ratio like 0.75"
+    );
+    assert_eq!(
+        invalid("nan%"),
+        "a percentage like 75% or a ratio like 0.75"
+    );
+    assert_eq!(invalid("%"), "a percentage like 75% or a ratio like 0.75");
+    let mut buf = Vec::new();
+    Command::create("test", "Tests the ratios")
+        .arg(arg!(--quota), value!(ratio, 0.25), "A quota")
+        .color(false)
+        .print_help(&mut buf)
+        .unwrap();
+    assert!(String::from_utf8(buf)
+        .unwrap()
+        .contains("A quota [default: 25%]"));
+    let parsed = parse(&["test"]).unwrap();
+    let arg = parsed.args.try_get(arg!(--quota)).unwrap();
+    assert_eq!(arg.value_kind(), ValueKind::Ratio);
+    assert_eq!(arg.expect_ratio(), Ok(Some(0.25)));
+    assert!(arg.expect_float().is_err());
+}
+
+#[cfg(feature = "glob")]
+#[test]
+fn test_glob() {
+    let glob = |pattern: &str| pattern.parse::<Glob>().unwrap();
+    for (pattern, text, expected) in [
+        ("*.rs", "main.rs", true),
+        ("*.rs", "src/main.rs", false),
+        ("src/*.rs", "src/main.rs", true),
+        ("**/*.rs", "main.rs", true),
+        ("**/*.rs", "src/a/b/main.rs", true),
+        ("src/**", "src/a/b", true),
+        ("src/**", "src", false),
+        ("src/**/test", "src/test", true),
+        ("src/**/test", "src/a/b/test", true),
+        ("src/**/test", "src/atest", false),
+        ("?.txt", "a.txt", true),
+        ("?.txt", "ab.txt", false),
+        ("?", "/", false),
+        ("[abc].txt", "b.txt", true),
+        ("[!abc].txt", "b.txt", false),
+        ("[a-c][0-9]", "c7", true),
+        ("[]]", "]", true),
+        ("[a-]", "-", true),
+        ("\\*", "*", true),
+        ("\\*", "a", false),
+        ("a*b*c", "aXbYbZc", true),
+        ("a*b*c", "aXbYbZ", false),
+        ("", "", true),
+    ] {
+        assert_eq!(glob(pattern).matches(text), expected, "{pattern} {text}");
+    }
+    assert!(glob("**/*.tmp").matches_path(std::path::Path::new("cache/x.tmp")));
+    assert_eq!(glob("a/**").to_string(), "a/**");
+    for (pattern, reason) in [
+        ("[a-", "unclosed '['"),
+        ("[]", "unclosed '['"),
+        ("[z-a]", "invalid range 'z-a'"),
+        ("a**", "'**' must be a whole path component"),
+        ("**b", "'**' must be a whole path component"),
+        ("a\\", "trailing '\\'"),
+    ] {
+        assert_eq!(
+            pattern.parse::<Glob>(),
+            Err(format!("a valid glob pattern: {reason}")),
+            "{pattern}"
+        );
+    }
+    let parsed = Command::create("test", "Tests the glob patterns")
+        .arg(
+            arg!(--include),
+            ArgValue::Other(Box::new(Some(glob("*")))),
+            "Included files",
+        )
+        .parse_from(mkargs(&["test"]))
+        .unwrap();
+    let include = parsed.args.try_get(arg!(--include)).unwrap().glob();
+    assert_eq!(include.map(Glob::as_str), Some("*"));
+}
+
+#[test]
+fn test_epilogue() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let cmd = Command::create("test", "Tests the epilogues")
+        .arg(arg!(--name), value!(string), "A name")
+        .example("test --name me", "Uses a name")
+        .license("MIT")
+        .epilogue_with(|| {
+            let calls = CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+            format!("Plugins: backup\nCalls: {calls}\x1b[2J\n")
+        })
+        .color(false);
+    let render = |cmd: &Command| {
+        let mut buf = Vec::new();
+        cmd.print_help(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    };
+    assert!(render(&cmd).ends_with(
+        "\t\tUses a name\n\nPlugins: backup\nCalls: 1\\u{1b}[2J\n\nLicensed under MIT\n"
+    ));
+    assert!(render(&cmd).contains("Calls: 2"));
+    let parsed = cmd.parse_from(mkargs(&["test"])).unwrap();
+    assert!(parsed.help.contains("Calls: 3"));
+    let help = render(
+        &Command::create("test", "Tests the epilogues")
+            .epilogue("See the manual.")
+            .epilogue_with(String::new)
+            .color(false),
+    );
+    assert!(!help.contains("See the manual."));
+    assert!(render(
+        &Command::create("test", "Tests the epilogues")
+            .epilogue("See the manual.")
+            .color(false)
+    )
+    .ends_with("See the manual.\n\n\n"));
+}
+
+#[test]
+fn test_empty_argv() {
+    let parsed = Command::create("test", "Tests the empty command lines")
+        .arg(arg!(--num), value!(num, 3), "A number")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .multicall(true)
+        .color(false)
+        .parse_from(Vec::new())
+        .unwrap();
+    assert_eq!(parsed.name, "test");
+    assert_eq!(parsed.args.num_or(arg!(--num), 0), 3);
+    assert!(parsed.raw.is_empty());
+    let parsed = Command::create("test", "Tests the empty command lines")
+        .arg(arg!(--num), value!(num, 3), "A number")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .multicall(true)
+        .color(false)
+        .parse_from_os(Vec::new())
+        .unwrap();
+    assert_eq!(parsed.name, "test");
+    let parsed = Command::create("test", "Tests the empty command lines")
+        .arg(arg!(--num), value!(num, 3), "A number")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .multicall(true)
+        .color(false)
+        .parse_from_args_only(mkargs(&["--num", "5", "sub"]))
+        .unwrap();
+    assert_eq!(parsed.name, "sub");
+    assert_eq!(
+        parsed.ancestors[0].try_get(arg!(--num)).unwrap().num(),
+        Some(5)
+    );
+    let parsed = Command::create("test", "Tests the empty command lines")
+        .arg(arg!(--num), value!(num, 3), "A number")
+        .subcommand(Command::create("sub", "A subcommand"))
+        .multicall(true)
+        .color(false)
+        .parse_from_args_only(Vec::new())
+        .unwrap();
+    assert_eq!(parsed.name, "test");
+}
+
+#[test]
+fn test_command_getters() {
+    let cmd = Command::create("tinycloud", "The Tiny Cloud client")
+        .arg(arg!(-'v', --verbose), value!(count), "Increases verbosity")
+        .arg(arg!(--config), value!(path), "Uses another configuration")
+        .subcommand(
+            Command::create("user", "Manages users")
+                .subcommand(Command::create("add", "Adds a user")),
+        )
+        .subcommand(Command::create("serve", "Starts the server"));
+    assert_eq!(cmd.name(), "tinycloud");
+    assert_eq!(cmd.description(), "The Tiny Cloud client");
+    let args: Vec<(&ArgName, &str)> = cmd
+        .args()
+        .iter()
+        .map(|arg| (&arg.argname, arg.description))
+        .collect();
+    assert_eq!(
+        args,
+        [
+            (&arg!(-'v', --verbose), "Increases verbosity"),
+            (&arg!(--config), "Uses another configuration")
+        ]
+    );
+    let user = &cmd.subcommands()[0];
+    assert_eq!((user.name(), user.description()), ("user", "Manages users"));
+    assert_eq!(user.subcommands()[0].name(), "add");
+    assert!(user.args().is_empty());
+    assert_eq!(cmd.subcommands()[1].name(), "serve");
+}
+
+#[test]
+fn test_tokenizer() {
+    let mut tokens = Tokenizer::new(mkargs(&[
+        "--name=a=b",
+        "---x",
+        "-vn5",
+        "--",
+        "-",
+        "sub",
+        "--key",
+        "--",
+    ]));
+    assert_eq!(tokens.size_hint(), (8, Some(8)));
+    let long = |name: &str, value: Option<&str>| Token::LongOpt {
+        name: name.into(),
+        value: value.map(String::from),
+    };
+    assert_eq!(tokens.next(), Some(long("name", Some("a=b"))));
+    assert_eq!(tokens.next(), Some(long("-x", None)));
+    assert_eq!(tokens.peek(), Some(Token::ShortCluster("vn5".into())));
+    assert_eq!(tokens.next(), Some(Token::ShortCluster("vn5".into())));
+    assert_eq!(tokens.next(), Some(Token::Separator));
+    assert_eq!(tokens.next(), Some(Token::Positional("-".into())));
+    assert_eq!(tokens.next(), Some(Token::Positional("sub".into())));
+    assert_eq!(tokens.next(), Some(long("key", None)));
+    assert_eq!(tokens.next_value(), Some(Token::Value("--".into())));
+    assert_eq!(tokens.next_value(), None);
+    let input = ["--name=a=b", "---x", "-vn5", "--", "-", "", "-é"];
+    for token in input {
+        assert_eq!(String::from(Token::new(token.into())), token);
+    }
+    let mut tokens = Tokenizer::new(mkargs(&["-v", "a", "b"]));
+    tokens.next();
+    assert_eq!(tokens.remaining(), ["a", "b"]);
+
+    // `--` ends the arguments, even the names of the subcommands.
+    let parse = |input: &[&str]| {
+        Command::create("test", "Tests the separator")
+            .arg(arg!(-'x'), ArgValue::Flag, "A flag")
+            .rest("FILE", "Files")
+            .subcommand(Command::create("sub", "A subcommand"))
+            .parse_from(mkargs(input))
+    };
+    assert_eq!(parse(&["test", "a", "--", "-x"]).unwrap().rest, ["a", "-x"]);
+    assert_eq!(parse(&["test", "--", "a"]).unwrap().rest, ["a"]);
+    let parsed = parse(&["test", "-x", "--", "sub", "--", "-x"]).unwrap();
+    assert!(parsed.is([]));
+    assert_eq!(parsed.args.try_count(arg!(-'x')), Some(1));
+    assert_eq!(parsed.rest, ["sub", "--", "-x"]);
+    let err = Command::create("test", "Tests the separator")
+        .arg(arg!(-'x'), ArgValue::Flag, "A flag")
+        .parse_from(mkargs(&["test", "--", "-x"]))
+        .err()
+        .unwrap();
+    assert_eq!(*err.kind(), ErrorKind::UnexpectedToken("-x".into()));
+}
+
+#[test]
+#[should_panic(
+    expected = "Subcommand 'sync' of 'Sync plugin' and subcommand 'Sync' of 'Backup plugin' differ only in case."
+)]
+fn test_extend_case_collision() {
+    let sync = Command::create("sync", "Sync plugin")
+        .provider("Sync plugin")
+        .subcommand(Command::create("sync", "Syncs the files"));
+    let backup = Command::create("backup", "Backup plugin")
+        .provider("Backup plugin")
+        .subcommand(Command::create("Sync", "Syncs the backups"));
+    Command::create("tinycloud", "Tests merging")
+        .subcommand(Command::create("serve", "Starts the server"))
+        .extend(sync)
+        .extend(backup);
+}
+
+#[test]
+#[should_panic(
+    expected = "Subcommand 'sync' of 'files' and subcommand 'Sync' of 'files' differ only in case."
+)]
+fn test_nested_case_collision() {
+    let files = Command::create("files", "Files plugin")
+        .provider("Files plugin")
+        .subcommand(
+            Command::create("files", "Manages the files")
+                .subcommand(Command::create("sync", "Syncs the files"))
+                .subcommand(Command::create("Sync", "Syncs the files again")),
+        );
+    Command::create("tinycloud", "Tests merging").extend(files);
+}
+
+#[test]
+fn test_help_and_version_win() {
+    let error = |input: &[&str], collect_errors| {
+        Command::create("root", "Tests the order of evaluation")
+            .version("1.0.0")
+            .auto_version(true)
+            .help_flag(arg!(-'h', --help))
+            .arg(arg!(--port), value!(num), "Insert a port")
+            .arg(arg!(--a), value!(), "First of the group")
+            .arg(arg!(--b), value!(), "Second of the group")
+            .require_one_of([arg!(--a), arg!(--b)])
+            .rest("FILE", "Files to upload")
+            .rest_min(1)
+            .validator(|_| Err("never valid".into()))
+            .subcommand(Command::create("sub", "A subcommand").arg(
+                arg!(--num),
+                value!(num),
+                "A number",
+            ))
+            .collect_errors(collect_errors)
+            .parse_from(mkargs(input))
+            .err()
+            .unwrap()
+    };
+    for input in [
+        &["root", "--help"][..],
+        &["root", "--port", "abc", "--unknown", "--help"],
+        &["root", "--help", "--port", "abc"],
+        &["root", "--version", "--help"],
+        &["root", "sub", "--num", "x", "--help"],
+    ] {
+        assert!(
+            matches!(error(input, false).kind(), ErrorKind::DisplayHelp(_)),
+            "{input:?}"
+        );
+    }
+    for input in [
+        &["root", "--version"][..],
+        &["root", "--port", "abc", "--version"],
+        &["root", "--unknown", "--version", "--port"],
+    ] {
+        assert!(
+            matches!(error(input, false).kind(), ErrorKind::DisplayVersion(_)),
+            "{input:?}"
+        );
+    }
+    // Without the flags, the first error is reported.
+    let err = error(&["root", "--port", "abc", "--unknown"], false);
+    assert!(matches!(err.kind(), ErrorKind::InvalidValue { .. }));
+    assert!(matches!(
+        error(&["root", "--port", "abc", "sub", "--help"], false).kind(),
+        ErrorKind::DisplayHelp(_)
+    ));
+    // Errors of the parent commands come first.
+    let err = error(&["root", "--port", "abc", "sub", "--num", "x"], false);
+    assert!(matches!(err.kind(), ErrorKind::InvalidValue { .. }));
+    assert_eq!(err.command(), "root");
+    let err = error(&["root", "--port", "abc", "--unknown"], true);
+    assert_eq!(err.errors().count(), 4);
+}
+
+#[test]
+fn test_visibility() {
+    let cmd = Command::create("root", "Tests the visibility")
+        .arg(arg!(--port), value!(num), "Insert a port")
+        .add_arg(
+            Arg::new(arg!(--reset - admin), value!(), "Resets the admin")
+                .visibility(Visibility::Internal),
+        )
+        .add_arg(
+            Arg::new(arg!(--debug - dump), value!(), "Dumps the state")
+                .visibility(Visibility::Hidden),
+        )
+        .subcommand(
+            Command::create("maintenance", "Maintenance tasks")
+                .visibility(Visibility::Internal)
+                .subcommand(Command::create("vacuum", "Vacuums the database")),
+        )
+        .subcommand(Command::create("serve", "Serves the files"))
+        .color(false);
+    let page = help::plain(&cmd);
+    assert!(page.contains("--port") && page.contains("serve"));
+    assert!(!page.contains("--reset-admin") && !page.contains("maintenance"));
+    assert!(!page.contains("--debug-dump"));
+    let mut buf = Vec::new();
+    cmd.print_help(&mut buf).unwrap();
+    let page = String::from_utf8(buf).unwrap();
+    assert!(page.contains("--reset-admin") && page.contains("maintenance"));
+    assert!(!page.contains("--debug-dump"));
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+        let script = completion::generate(shell, &cmd);
+        assert!(
+            script.contains("port") && script.contains("serve"),
+            "{shell:?}"
+        );
+        for name in ["reset-admin", "debug-dump", "maintenance", "vacuum"] {
+            assert!(!script.contains(name), "{shell:?} {name}");
+        }
+    }
+    let template = cmd.config_template();
+    assert!(template.contains("port") && !template.contains("reset"));
+    assert!(!template.contains("debug") && !template.contains("maintenance"));
+    // Whatever their visibility, arguments and subcommands are parsed.
+    let parsed = cmd
+        .parse_from(mkargs(&[
+            "root",
+            "--debug-dump",
+            "--reset-admin",
+            "maintenance",
+            "vacuum",
+        ]))
+        .unwrap();
+    assert_eq!(parsed.name, "vacuum");
+    assert_eq!(parsed.ancestors[0].count(arg!(--debug - dump)), 1);
+}
+
+#[test]
+fn test_color_flag() {
+    let parse = |input: &[&str]| {
+        Command::create("root", "Tests the color option")
+            .color_flag()
+            .help_flag(arg!(-'h', --help))
+            .terminal(TerminalInfo {
+                color: true,
+                ..TerminalInfo::PLAIN
+            })
+            .subcommand(Command::create("sub", "A subcommand").color(true))
+            .parse_from(mkargs(input))
+    };
+    let parsed = parse(&["root", "sub"]).unwrap();
+    assert_eq!(parsed.color_choice(), ColorChoice::Auto);
+    assert!(parsed.help.contains("--color <WHEN>"));
+    let parsed = parse(&["root", "--color=always", "sub", "--color", "never"]).unwrap();
+    assert_eq!(parsed.color_choice(), ColorChoice::Never);
+    // The choice is known before the errors are found, even those of the subcommands.
+    for (input, color) in [
+        (&["root", "--color", "never", "idk"][..], false),
+        (&["root", "sub", "--idk", "--color=never"], false),
+        (
+            &[
+                "root", "--color", "never", "--color", "auto", "sub", "--idk",
+            ],
+            true,
+        ),
+        (&["root", "--color", "sometimes"], true),
+        (&["root", "--", "--color", "never"], true),
+    ] {
+        let err = parse(input).err().unwrap();
+        assert_eq!(err.terminal().color, color, "{input:?}");
+    }
+    let err = parse(&["root", "--color", "never", "-h"]).err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::DisplayHelp(page) if !page.contains('\x1b')));
+    // Commands that already have the option keep it.
+    let parsed = Command::create("root", "Tests the color option")
+        .color_flag()
+        .arg(arg!(--color), value!(), "Colors the output")
+        .parse_from(mkargs(&["root", "--color"]))
+        .unwrap();
+    assert_eq!(parsed.color_choice(), ColorChoice::Auto);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(value!(float, 1.0).display().unwrap(), "1");
+    assert_eq!(value!(float, -2.50).display().unwrap(), "-2.5");
+    assert_eq!(value!(float, 1e-12).display().unwrap(), "0.000000000001");
+    assert_eq!(value!(float, f64::INFINITY).display().unwrap(), "inf");
+    assert_eq!(value!(num, 8080).display().unwrap(), "8080");
+    assert_eq!(value!(count).display().unwrap(), "0");
+    assert_eq!(value!().display(), None);
+    assert_eq!(value!(list).display(), None);
+    assert_eq!(
+        ArgValue::Bytes(Some(b"hi!".to_vec())).display().unwrap(),
+        "hex:686921"
+    );
+    let mut buf = Vec::new();
+    Command::create("root", "Tests the display of values")
+        .arg(arg!(--quota), value!(ratio, 0.07), "Insert a quota")
+        .color(false)
+        .print_help(&mut buf)
+        .unwrap();
+    assert!(String::from_utf8(buf).unwrap().contains("[default: 7%]"));
+    let parsed = Command::create("root", "Tests the display of values")
+        .arg(arg!(--quota), value!(ratio, 0.07), "Insert a quota")
+        .arg(arg!(--scale), value!(float), "Insert a scale")
+        .add_arg(Arg::new(arg!(--token), value!(string), "Insert a token").sensitive())
+        .add_arg(Arg::new(arg!(--cache), value!(), "Caches the files").negatable())
+        .arg(arg!(--dry - run), value!(), "Does nothing")
+        .parse_from(mkargs(&[
+            "root",
+            "--scale",
+            "0.1",
+            "--scale",
+            "0.7",
+            "--quota",
+            "0.5",
+            "--token",
+            "abc",
+            "--no-cache",
+            "--dry-run",
+        ]))
+        .unwrap();
+    let display = |argname| parsed.args.get(argname).display();
+    assert_eq!(display(arg!(--quota)).unwrap(), "50%");
+    assert_eq!(display(arg!(--scale)).unwrap(), "0.7");
+    assert_eq!(display(arg!(--token)).unwrap(), "***");
+    assert_eq!(display(arg!(--cache)).unwrap(), "unset");
+    assert_eq!(display(arg!(--dry - run)).unwrap(), "set");
+    // The default values are not secret.
+    let parsed = Command::create("root", "Tests the display of values")
+        .arg(arg!(--quota), value!(ratio, 0.07), "Insert a quota")
+        .add_arg(Arg::new(arg!(--token), value!(string), "Insert a token").sensitive())
+        .arg(arg!(--dry - run), value!(), "Does nothing")
+        .parse_from(mkargs(&["root", "--quota", "0.07", "--quota", "1"]))
+        .unwrap();
+    assert_eq!(parsed.args.get(arg!(--token)).display(), None);
+    assert_eq!(
+        parsed.args.get(arg!(--dry - run)).display().unwrap(),
+        "unset"
+    );
+    assert_eq!(
+        parsed.overwritten_values(),
+        [(arg!(--quota), vec!["7%".to_string()])]
+    );
+}
+
+#[test]
+fn test_into_subcommand() {
+    let cmd = Command::create("root", "Tests detached subcommands").subcommand(
+        Command::create("user", "Manages the users")
+            .subcommand(Command::create("add", "Adds a user")),
+    );
+    assert_eq!(cmd.find([]).unwrap().name(), "root");
+    assert_eq!(cmd.find(["user", "add"]).unwrap().name(), "add");
+    assert!(cmd.find(["add"]).is_none());
+    assert!(cmd.into_subcommand(["user", "remove"]).is_none());
+    let add = |input: &[&str]| {
+        Command::create("root", "Tests detached subcommands")
+            .version("1.0.0")
+            .help_flag(arg!(-'h', --help))
+            .strings(Strings {
+                unknown_arg: "'{arg}' is not known.",
+                ..Strings::ENGLISH
+            })
+            .add_arg(Arg::new(arg!(--config), value!(path), "Insert a config").global(true))
+            .add_arg(Arg::new(arg!(--trace), value!(), "Traces everything").global(true))
+            .subcommand(
+                Command::create("user", "Manages the users")
+                    .without_global(arg!(--trace))
+                    .subcommand(Command::create("add", "Adds a user").arg(
+                        arg!(--name),
+                        value!(string),
+                        "Insert the name",
+                    )),
+            )
+            .into_subcommand(["user", "add"])
+            .unwrap()
+            .parse_from(mkargs(input))
+    };
+    let parsed = add(&["add", "--name", "me", "--config", "/etc/root.toml"]).unwrap();
+    assert_eq!(parsed.path(), ["user", "add"]);
+    assert_eq!(parsed.version, Some("1.0.0"));
+    assert_eq!(
+        parsed.args.get(arg!(--config)).path().unwrap(),
+        &PathBuf::from("/etc/root.toml")
+    );
+    assert!(parsed.help.contains("root user add"));
+    // It is parsed like in the whole tree.
+    let err = add(&["add", "--trace"]).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        Command::create("root", "Tests detached subcommands")
+            .strings(Strings {
+                unknown_arg: "'{arg}' is not known.",
+                ..Strings::ENGLISH
+            })
+            .add_arg(Arg::new(arg!(--trace), value!(), "Traces everything").global(true))
+            .subcommand(
+                Command::create("user", "Manages the users")
+                    .without_global(arg!(--trace))
+                    .subcommand(Command::create("add", "Adds a user")),
+            )
+            .parse_from(mkargs(&["root", "user", "add", "--trace"]))
+            .err()
+            .unwrap()
+            .to_string()
+    );
+    assert!(err.to_string().contains("is not known"));
+    let err = add(&["add", "-h"]).err().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::DisplayHelp(_)));
+}
+
+#[test]
+fn test_error_codes() {
+    let kinds = [
+        ErrorKind::UnknownArg(arg!(--a)),
+        ErrorKind::InvalidArg("-\u{301}".into()),
+        ErrorKind::NonUtf8Arg("\u{fffd}".into()),
+        ErrorKind::TooManyDashes("---a".into()),
+        ErrorKind::LoneDash,
+        ErrorKind::TrailingDoubleDash,
+        ErrorKind::UnexpectedToken("a".into()),
+        ErrorKind::InvalidValue {
+            arg: arg!(--a),
+            value: "a".into(),
+            reason: "a number".into(),
+        },
+        ErrorKind::MissingValue(arg!(--a)),
+        ErrorKind::MisplacedArg {
+            arg: arg!(--a),
+            placement: Placement::Before,
+        },
+        ErrorKind::MissingOneOf(vec![arg!(--a), arg!(--b)]),
+        ErrorKind::RequiresSubcommand {
+            arg: arg!(--a),
+            subcommands: vec!["sub"],
+        },
+        ErrorKind::Duplicate(arg!(--a)),
+        ErrorKind::UnknownSubcommand("a".into()),
+        ErrorKind::MissingSubcommand(vec!["sub"]),
+        ErrorKind::InvalidEnvArgs("ARGS".into()),
+        ErrorKind::InvalidLine(SplitError::TrailingBackslash),
+        ErrorKind::TooFewValues {
+            name: "FILE",
+            min: 1,
+        },
+        ErrorKind::Validation("no".into()),
+    ];
+    let mut codes: Vec<&str> = kinds.iter().map(ErrorKind::code).collect();
+    assert!(codes.iter().all(|code| code.starts_with("E_")
+        && code
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_')));
+    codes.sort();
+    codes.dedup();
+    assert_eq!(codes.len(), kinds.len());
+    // The codes do not depend on the language of the messages.
+    let err = Command::create("root", "Tests the error codes")
+        .strings(Strings {
+            unknown_arg: "'{arg}' non è un argomento valido.",
+            ..Strings::ENGLISH
+        })
+        .collect_errors(true)
+        .parse_from(mkargs(&["root", "--what", "idk"]))
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), "E_UNKNOWN_ARG");
+    let codes: Vec<&str> = err.errors().map(ErrorKind::code).collect();
+    assert_eq!(codes, ["E_UNKNOWN_ARG", "E_UNEXPECTED_TOKEN"]);
+}
+
+#[test]
+fn test_prefixes() {
+    let plus = Prefixes {
+        plus: true,
+        ..Prefixes::DEFAULT
+    };
+    let legacy = Command::create("legacy", "Tests the prefixes")
+        .arg(arg!(-'x', --trace), value!(), "Traces the commands")
+        .arg(arg!(-'e'), value!(), "Exits on errors")
+        .arg(arg!(-'n', --num), value!(num), "Insert a number")
+        .prefixes(plus)
+        .subcommand(
+            Command::create("run", "Runs a script")
+                .arg(arg!(-'u'), value!(), "Fails on unset variables")
+                .rest("SCRIPT", "The script"),
+        );
+    let mut parser = Parser::new(&legacy);
+    parser.push("+x").unwrap();
+    assert!(parser.push("+n").is_err());
+    let parsed = legacy
+        .parse_from(mkargs(&[
+            "legacy", "-ex", "+xe", "-x", "run", "+u", "boot.sh",
+        ]))
+        .unwrap();
+    let ancestor = &parsed.ancestors[0];
+    assert_eq!(ancestor.get(arg!(--trace)).enabled(), Some(true));
+    assert_eq!(ancestor.get(arg!(-'e')).enabled(), Some(false));
+    assert_eq!(parsed.args.get(arg!(-'u')).enabled(), Some(false));
+    assert_eq!(parsed.rest, ["boot.sh"]);
+    assert_eq!(parsed.to_argv(), ["--trace", "+e", "run", "+u", "boot.sh"]);
+    let err = |input: &[&str]| {
+        Command::create("legacy", "Tests the prefixes")
+            .arg(arg!(-'n', --num), value!(num), "Insert a number")
+            .prefixes(plus)
+            .parse_from(mkargs(input))
+            .err()
+            .unwrap()
+    };
+    assert_eq!(
+        err(&["legacy", "+n", "5"]).kind(),
+        &ErrorKind::InvalidArg("+n".into())
+    );
+    assert_eq!(
+        err(&["legacy", "+y"]).kind(),
+        &ErrorKind::UnknownArg(arg!(-'y'))
+    );
+    // Without the plus prefix, it is an unknown subcommand.
+    let err = Command::create("legacy", "Tests the prefixes")
+        .arg(arg!(-'x', --trace), value!(), "Traces the commands")
+        .subcommand(Command::create("run", "Runs a script"))
+        .parse_from(mkargs(&["legacy", "+x"]))
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), &ErrorKind::UnknownSubcommand("+x".into()));
+    assert_eq!(
+        Tokenizer::new(["+x".into()]).prefixes(plus).next(),
+        Some(Token::PlusCluster("x".into()))
+    );
+
+    // Without the short arguments, negative numbers are positional values.
+    let long = Prefixes {
+        short: false,
+        plus: false,
+    };
+    let calc = Command::create("calc", "Tests the prefixes")
+        .arg(arg!(--scale), value!(num), "Insert a scale")
+        .rest("NUMBER", "The numbers to add")
+        .version("1.0.0")
+        .auto_version(true)
+        .verbosity_flags()
+        .prefixes(long);
+    let mut parser = Parser::new(&calc);
+    parser.push("-5").unwrap();
+    assert!(parser.push("-V").is_ok() && parser.push("--version").is_ok());
+    let parsed = calc
+        .parse_from(mkargs(&["calc", "-5", "--scale", "2", "-v", "--verbose"]))
+        .unwrap();
+    assert_eq!(parsed.rest, ["-5", "-v"]);
+    assert_eq!(parsed.verbosity(), 1);
+    assert!(parsed.help.contains("\t--version") && !parsed.help.contains("-V"));
+}
+
+#[test]
+#[should_panic(expected = "The argument '-h, --help' of 'calc' has a short name")]
+fn test_prefixes_short_names() {
+    let _ = Command::create("calc", "Tests the prefixes")
+        .help_flag(arg!(-'h', --help))
+        .prefixes(Prefixes {
+            short: false,
+            plus: false,
+        })
+        .parse_from(mkargs(&["calc"]));
+}
+
+#[test]
+fn test_unused_args() {
+    let parsed = Command::create("deploy", "Tests the unused arguments")
+        .arg(arg!(--dry - run), value!(), "Does nothing")
+        .arg(arg!(--target), value!(string, "local"), "Insert a target")
+        .arg(arg!(--tags), value!(list), "Insert the tags")
+        .arg(arg!(--trace), value!(), "Traces the commands")
+        .add_arg(Arg::new(arg!(--token), value!(string), "Insert a token").global(true))
+        .color_flag()
+        .verbosity_flags()
+        .subcommand(Command::create("run", "Runs the deployment"))
+        .parse_from(mkargs(&[
+            "deploy",
+            "--dry-run",
+            "--tags",
+            "a",
+            "--trace",
+            "--color",
+            "never",
+            "-v",
+        ]))
+        .unwrap();
+    // Defaults are not reported, nor is the color option, which the parser reads itself.
+    assert_eq!(
+        parsed.finish(),
+        [
+            "'--dry-run' was given but never read",
+            "'--tags' was given but never read",
+            "'--trace' was given but never read",
+            "'-v, --verbose' was given but never read",
+        ]
+    );
+    assert_eq!(parsed.args.try_count(arg!(--dry - run)), Some(1));
+    assert_eq!(parsed.args.try_get(arg!(--tags)).unwrap().groups().len(), 1);
+    parsed.args.try_get(arg!(--trace)).unwrap().mark_used();
+    assert_eq!(parsed.verbosity(), 1);
+    assert!(parsed.finish().is_empty());
+    // Rebuilding the command line does not read the arguments.
+    let parsed = Command::create("deploy", "Tests the unused arguments")
+        .arg(arg!(--tags), value!(list), "Insert the tags")
+        .parse_from(mkargs(&["deploy", "--tags", "a"]))
+        .unwrap();
+    assert_eq!(parsed.to_argv(), ["--tags", "a"]);
+    assert!(parsed.to_json().contains("tags"));
+    assert_eq!(parsed.finish().len(), 1);
+
+    // A global argument is read if any of its copies was.
+    let parsed = Command::create("deploy", "Tests the unused arguments")
+        .add_arg(Arg::new(arg!(--token), value!(string), "Insert a token").global(true))
+        .subcommand(Command::create("run", "Runs the deployment"))
+        .parse_from(mkargs(&["deploy", "--token", "secret", "run"]))
+        .unwrap();
+    assert_eq!(parsed.finish(), ["'--token' was given but never read"]);
+    assert_eq!(
+        parsed.args.try_get(arg!(--token)).unwrap().string(),
+        Some("secret")
+    );
+    assert!(parsed.finish().is_empty());
+    assert!(!parsed.ancestors[0]
+        .try_get(arg!(--token))
+        .unwrap()
+        .is_used());
 }
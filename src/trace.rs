@@ -0,0 +1,74 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::sync::Arc;
+
+use crate::*;
+
+/// Something that happened while parsing, given to the tracer of the command.
+///
+/// Values of sensitive arguments are replaced with `***` (see [`Arg::sensitive`]).
+/// See [`Command::tracer`].
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub enum TraceEvent<'a> {
+    /// The parser entered a subcommand.
+    Subcommand {
+        /// The name of the command the subcommand belongs to.
+        parent: &'a str,
+
+        /// The name of the subcommand.
+        name: &'a str,
+    },
+
+    /// The parser started reading the arguments given to a command.
+    Command(&'a str),
+
+    /// A token was read as an argument (e.g. `--num` or `-abc`).
+    Token(&'a str),
+
+    /// A token was taken as the value of an argument.
+    Value {
+        /// The argument the value is given to.
+        arg: &'a ArgName,
+
+        /// The token.
+        token: &'a str,
+    },
+
+    /// An argument was used. It carries the argument with the converted value.
+    Arg(&'a Arg),
+
+    /// A token was taken as a positional value. See [`Command::rest`].
+    Positional(&'a str),
+}
+
+// A function that receives the parsing events. See `Command::tracer`.
+#[derive(Clone)]
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub(crate) struct Tracer(pub Arc<dyn Fn(&TraceEvent) + Send + Sync>);
+
+impl Tracer {
+    pub fn emit(&self, event: TraceEvent) {
+        (self.0)(&event)
+    }
+}
@@ -0,0 +1,104 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use crate::*;
+
+/// What to do with command line arguments that are not valid UTF-8.
+///
+/// See [`Command::utf8_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Utf8Policy {
+    /// Such an argument is an error ([`ErrorKind::NonUtf8Arg`]). This is the default.
+    Strict,
+
+    /// Invalid sequences are replaced with `U+FFFD` (see [`String::from_utf8_lossy`]).
+    Lossy,
+
+    /// Such an argument is passed through untouched if it is the value of an
+    /// [`ArgValue::Path`] or of an [`ArgValue::PathList`], otherwise it is an error.
+    Paths,
+}
+
+// The arguments converted to UTF-8, along with the lossy conversion and the original of each
+// argument that is not valid UTF-8.
+pub(crate) struct Converted {
+    pub args: Vec<String>,
+    pub invalid: Vec<(String, OsString)>,
+}
+
+pub(crate) fn convert(args: impl IntoIterator<Item = OsString>) -> Converted {
+    let mut invalid = Vec::new();
+    let args = args
+        .into_iter()
+        .map(|arg| {
+            arg.into_string().unwrap_or_else(|arg| {
+                let lossy = arg.to_string_lossy().into_owned();
+                invalid.push((lossy.clone(), arg));
+                lossy
+            })
+        })
+        .collect();
+    Converted { args, invalid }
+}
+
+// The parts of the token that can be a value: the whole token or, for an argument, what follows
+// any of its leading ASCII characters (e.g. `--path=value`, `-pvalue`).
+fn candidates(token: &OsStr) -> Vec<&OsStr> {
+    let bytes = token.as_encoded_bytes();
+    let mut candidates = vec![token];
+    if bytes.first() == Some(&b'-') {
+        for (i, _) in bytes
+            .iter()
+            .enumerate()
+            .skip(1)
+            .take_while(|(_, b)| b.is_ascii())
+        {
+            // SAFETY: the bytes are split right after an ASCII character.
+            candidates.push(unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[i + 1..]) });
+        }
+    }
+    candidates
+}
+
+// Replaces the first path that is the lossy conversion of the token (or of its value) with the
+// original. Returns whether or not it was found.
+pub(crate) fn restore(args: &mut ArgList, token: &OsStr) -> bool {
+    for candidate in candidates(token) {
+        let lossy = PathBuf::from(candidate.to_string_lossy().into_owned());
+        for arg in &mut args.args {
+            let paths: &mut [PathBuf] = match &mut arg.argvalue {
+                ArgValue::Path(Some(path)) => std::slice::from_mut(path),
+                ArgValue::PathList(paths) => paths,
+                _ => continue,
+            };
+            if let Some(path) = paths.iter_mut().find(|path| **path == lossy) {
+                *path = Path::new(candidate).into();
+                return true;
+            }
+        }
+    }
+    false
+}
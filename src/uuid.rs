@@ -0,0 +1,132 @@
+// This file is part of the Tiny Cloud project.
+// You can find the source code of every repository here:
+//		https://github.com/personal-tiny-cloud
+//
+// Copyright (C) 2024  hex0x0000
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//
+// Email: hex0x0000@protonmail.com
+
+use std::{fmt, str::FromStr};
+
+use crate::ArgValueKind;
+
+/// A UUID (e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`), as defined by
+/// [RFC 4122](https://www.rfc-editor.org/rfc/rfc4122). Requires the `uuid` feature.
+///
+/// It is parsed from the hyphenated form, in either case and optionally prefixed with
+/// `urn:uuid:`, and displayed in lowercase.
+///
+/// It can be used as the value of an argument with [`ArgValue::Other`](crate::ArgValue::Other),
+/// holding an optional default, and read with [`Arg::uuid`](crate::Arg::uuid).
+///
+/// # Example
+///
+/// ```rust
+/// # use tiny_args::*;
+/// let parsed = Command::create("myapp", "This is my cool app.")
+///     .arg(arg!(--id), ArgValue::Other(Box::new(None::<Uuid>)), "Insert a resource ID.")
+///     .parse_from_str("--id 67E55044-10B1-426F-9247-BB680E5FE0C8")
+///     .unwrap();
+/// let id = parsed.args.try_get(arg!(--id)).unwrap().uuid().unwrap();
+/// assert_eq!(id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+/// assert_eq!(id.version(), 4);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// The nil UUID, with every bit set to zero.
+    pub const NIL: Uuid = Uuid([0; 16]);
+
+    /// Creates a [`Uuid`] from its bytes, in big-endian order.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the bytes of the UUID, in big-endian order.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Returns the version of the UUID (e.g. `4` for random UUIDs), `0` for the nil UUID.
+    pub const fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+}
+
+// The lengths of the hyphen separated groups of hexadecimal digits.
+const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+impl FromStr for Uuid {
+    type Err = String;
+
+    /// Parses a hyphenated UUID. On failure it returns what the UUID must be.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || String::from("a UUID like 67e55044-10b1-426f-9247-bb680e5fe0c8");
+        let text = text.strip_prefix("urn:uuid:").unwrap_or(text);
+        let groups: Vec<&str> = text.split('-').collect();
+        if groups.len() != GROUPS.len()
+            || groups
+                .iter()
+                .zip(GROUPS)
+                .any(|(group, len)| group.len() != len)
+        {
+            return Err(invalid());
+        }
+        let digits: Vec<u8> = groups
+            .concat()
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?;
+        let mut bytes = [0; 16];
+        for (byte, pair) in bytes.iter_mut().zip(digits.chunks(2)) {
+            *byte = pair[0] << 4 | pair[1];
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ArgValueKind for Option<Uuid> {
+    fn parse(&mut self, input: &str) -> Result<(), String> {
+        *self = Some(input.parse()?);
+        Ok(())
+    }
+
+    fn type_name(&self) -> &str {
+        "uuid"
+    }
+
+    fn default_display(&self) -> Option<String> {
+        self.as_ref().map(Uuid::to_string)
+    }
+
+    fn clone_box(&self) -> Box<dyn ArgValueKind> {
+        Box::new(*self)
+    }
+}